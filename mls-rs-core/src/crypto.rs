@@ -300,6 +300,11 @@ pub trait CryptoProvider: Send + Sync {
 }
 
 /// Provides all cryptographic operations required by MLS for a given cipher suite.
+///
+/// When building with `--cfg mls_build_async`, the operations declared `async`
+/// (including signing, signature verification and HPKE) are asynchronous. This
+/// allows implementations backed by remote key services such as HSMs, where
+/// the [`SignatureSecretKey`] is only a handle to a key held by the service.
 #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
 #[cfg_attr(all(target_arch = "wasm32", mls_build_async), maybe_async::must_be_async(?Send))]
 #[cfg_attr(
@@ -375,6 +380,27 @@ pub trait CipherSuiteProvider: Send + Sync {
         len: usize,
     ) -> Result<Zeroizing<Vec<u8>>, Self::Error>;
 
+    /// Generate key material of length `len` for each additional input in `infos` by expanding
+    /// the same pseudo-random key `prk`. The output is in the same order as `infos`.
+    ///
+    /// The result must be the same as calling [kdf_expand](CipherSuiteProvider::kdf_expand)
+    /// for each element of `infos`, which is what the default implementation does. Providers
+    /// can override it to compute the expansions together.
+    async fn kdf_expand_batch(
+        &self,
+        prk: &[u8],
+        infos: &[&[u8]],
+        len: usize,
+    ) -> Result<Vec<Zeroizing<Vec<u8>>>, Self::Error> {
+        let mut out = Vec::with_capacity(infos.len());
+
+        for info in infos {
+            out.push(self.kdf_expand(prk, info, len).await?);
+        }
+
+        Ok(out)
+    }
+
     /// Return the size of pseudo-random key `prk` outputted by [kdf_extract](CipherSuiteProvider::kdf_extract)
     /// and inputted to [kdf_expand](CipherSuiteProvider::kdf_expand).
     fn kdf_extract_size(&self) -> usize;
@@ -23,6 +23,7 @@ pub use list::*;
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[cfg_attr(all(feature = "ffi", not(test)), safer_ffi_gen::ffi_type)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
 #[repr(transparent)]
 pub struct ExtensionType(u16);
 
@@ -87,6 +88,8 @@ pub enum ExtensionError {
     DeserializationError(AnyError),
     #[cfg_attr(feature = "std", error("incorrect extension type: {0:?}"))]
     IncorrectType(ExtensionType),
+    #[cfg_attr(feature = "std", error("duplicate entry at index {0}"))]
+    DuplicateEntry(usize),
 }
 
 impl IntoAnyError for ExtensionError {
@@ -268,4 +271,14 @@ mod tests {
 
         assert_matches!(AnotherTestExtension::from_extension(&ext), Err(ExtensionError::IncorrectType(found)) if found == 42.into());
     }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn extension_type_serializes_as_number() {
+        let json = serde_json::to_string(&ExtensionType(42)).unwrap();
+        assert_eq!(json, "42");
+
+        let decoded: ExtensionType = serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded, ExtensionType(42));
+    }
 }
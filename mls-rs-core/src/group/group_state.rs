@@ -49,6 +49,29 @@ impl EpochRecord {
     }
 }
 
+/// Pending state updates of a single group, as passed to
+/// [`GroupStateStorage::write`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GroupWrite {
+    pub state: GroupState,
+    pub epoch_inserts: Vec<EpochRecord>,
+    pub epoch_updates: Vec<EpochRecord>,
+}
+
+impl GroupWrite {
+    pub fn new(
+        state: GroupState,
+        epoch_inserts: Vec<EpochRecord>,
+        epoch_updates: Vec<EpochRecord>,
+    ) -> Self {
+        Self {
+            state,
+            epoch_inserts,
+            epoch_updates,
+        }
+    }
+}
+
 /// Storage that can persist and reload a group state.
 ///
 /// A group state is recorded as a combination of the current state
@@ -101,7 +124,42 @@ pub trait GroupStateStorage: Send + Sync {
         epoch_updates: Vec<EpochRecord>,
     ) -> Result<(), Self::Error>;
 
+    /// Write pending state updates of several groups.
+    ///
+    /// Each element of `writes` is handled as in
+    /// [`write`](GroupStateStorage::write). The default implementation calls
+    /// `write` for each of them in turn, so a failure can leave the groups
+    /// before it updated. Implementations that support transactions should
+    /// override it to apply all the writes atomically.
+    async fn write_many(&mut self, writes: Vec<GroupWrite>) -> Result<(), Self::Error> {
+        for write in writes {
+            self.write(write.state, write.epoch_inserts, write.epoch_updates)
+                .await?;
+        }
+
+        Ok(())
+    }
+
     /// The [`EpochRecord::id`] value that is associated with a stored
     /// prior epoch for a particular group.
     async fn max_epoch_id(&self, group_id: &[u8]) -> Result<Option<u64>, Self::Error>;
+
+    /// The smallest [`EpochRecord::id`] value that is associated with a stored
+    /// prior epoch for a particular group.
+    ///
+    /// The default implementation looks up prior epochs from
+    /// [`max_epoch_id`](GroupStateStorage::max_epoch_id) downward until one
+    /// is missing. Implementations that can find the value directly should
+    /// override it.
+    async fn min_epoch_id(&self, group_id: &[u8]) -> Result<Option<u64>, Self::Error> {
+        let Some(mut min_epoch_id) = self.max_epoch_id(group_id).await? else {
+            return Ok(None);
+        };
+
+        while min_epoch_id > 0 && self.epoch(group_id, min_epoch_id - 1).await?.is_some() {
+            min_epoch_id -= 1;
+        }
+
+        Ok(Some(min_epoch_id))
+    }
 }
@@ -15,6 +15,7 @@ use mls_rs_codec::{MlsDecode, MlsEncode, MlsSize};
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[cfg_attr(all(feature = "ffi", not(test)), safer_ffi_gen::ffi_type)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
 #[repr(transparent)]
 pub struct ProposalType(u16);
 
@@ -69,3 +70,26 @@ impl ProposalType {
         ProposalType::GROUP_CONTEXT_EXTENSIONS,
     ];
 }
+
+#[cfg(all(test, feature = "serde"))]
+mod tests {
+    use alloc::vec;
+    use alloc::vec::Vec;
+
+    use super::ProposalType;
+
+    #[test]
+    fn proposal_types_serialize_as_numbers() {
+        let proposal_types = vec![
+            ProposalType::ADD,
+            ProposalType::PSK,
+            ProposalType::new(0xF000),
+        ];
+
+        let json = serde_json::to_string(&proposal_types).unwrap();
+        assert_eq!(json, "[1,4,61440]");
+
+        let decoded: Vec<ProposalType> = serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded, proposal_types);
+    }
+}
@@ -55,3 +55,24 @@ impl ProtocolVersion {
         [Self::MLS_10].into_iter()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use super::ProtocolVersion;
+
+    #[test]
+    fn all_contains_named_versions() {
+        let all = ProtocolVersion::all().collect::<Vec<_>>();
+
+        assert!(all.contains(&ProtocolVersion::MLS_10));
+    }
+
+    #[test]
+    fn raw_value_conversions_match_named_versions() {
+        assert_eq!(ProtocolVersion::from(1), ProtocolVersion::MLS_10);
+        assert_eq!(u16::from(ProtocolVersion::MLS_10), 1);
+        assert_eq!(ProtocolVersion::MLS_10.raw_value(), 1);
+    }
+}
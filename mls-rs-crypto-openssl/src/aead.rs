@@ -6,8 +6,9 @@ use std::{fmt::Debug, ops::Deref};
 
 use mls_rs_core::{crypto::CipherSuite, error::IntoAnyError};
 use mls_rs_crypto_traits::{AeadId, AeadType, AES_TAG_LEN};
-use openssl::symm::{decrypt_aead, encrypt_aead, Cipher};
+use openssl::symm::{encrypt_aead, Cipher, Crypter, Mode};
 use thiserror::Error;
+use zeroize::Zeroizing;
 
 #[derive(Debug, Error)]
 pub enum AeadError {
@@ -92,6 +93,15 @@ impl AeadType for Aead {
         Ok([&ciphertext, &tag as &[u8]].concat())
     }
 
+    /// Decrypt and authenticate `ciphertext`.
+    ///
+    /// The only check performed before decryption is on the ciphertext
+    /// length, which is public. The whole ciphertext is always decrypted
+    /// and the tag is then compared by OpenSSL in constant time, so the
+    /// time taken to fail does not depend on which bytes of the tag or
+    /// ciphertext are invalid. Any failure, including an invalid tag,
+    /// results in the same [`AeadError::OpensslError`] and the partially
+    /// decrypted plaintext is zeroized without being inspected.
     #[allow(clippy::needless_lifetimes)]
     async fn open<'a>(
         &self,
@@ -107,7 +117,16 @@ impl AeadType for Aead {
         let (data, tag) = ciphertext.split_at(ciphertext.len() - AES_TAG_LEN);
         let aad = aad.unwrap_or_default();
 
-        decrypt_aead(self.cipher, key, Some(nonce), aad, data, tag).map_err(Into::into)
+        let mut crypter = Crypter::new(self.cipher, Mode::Decrypt, key, Some(nonce))?;
+        crypter.aad_update(aad)?;
+
+        let mut plaintext = Zeroizing::new(vec![0u8; data.len() + self.cipher.block_size()]);
+        let len = crypter.update(data, &mut plaintext)?;
+
+        crypter.set_tag(tag)?;
+        let rest = crypter.finalize(&mut plaintext[len..])?;
+
+        Ok(plaintext[..len + rest].to_vec())
     }
 
     fn key_size(&self) -> usize {
@@ -181,6 +200,28 @@ mod test {
         }
     }
 
+    #[test]
+    fn tampering_fails_uniformly() {
+        for aead in get_aeads() {
+            let key = vec![42u8; aead.key_size()];
+            let nonce = vec![42u8; aead.nonce_size()];
+
+            let ciphertext = aead.seal(&key, b"message", None, &nonce).unwrap();
+
+            // Flipping a bit of the encrypted data, the first byte of the tag or
+            // the last byte of the tag must fail through the same tag check.
+            for i in [0, ciphertext.len() - AES_TAG_LEN, ciphertext.len() - 1] {
+                let mut tampered = ciphertext.clone();
+                tampered[i] ^= 1;
+
+                assert_matches!(
+                    aead.open(&key, &tampered, None, &nonce),
+                    Err(AeadError::OpensslError(_))
+                );
+            }
+        }
+    }
+
     #[test]
     fn aad_mismatch() {
         for aead in get_aeads() {
@@ -24,6 +24,7 @@ pub enum EcPublicKey {
     X25519(x25519_dalek::PublicKey),
     Ed25519(ed25519_dalek::VerifyingKey),
     P256(p256::PublicKey),
+    #[cfg(feature = "p384")]
     P384(p384::PublicKey),
 }
 
@@ -31,6 +32,7 @@ pub enum EcPrivateKey {
     X25519(x25519_dalek::StaticSecret),
     Ed25519(ed25519_dalek::SigningKey),
     P256(p256::SecretKey),
+    #[cfg(feature = "p384")]
     P384(p384::SecretKey),
 }
 
@@ -87,6 +89,7 @@ impl core::fmt::Debug for EcPrivateKey {
             Self::X25519(_) => f.write_str("X25519 Secret Key"),
             Self::Ed25519(_) => f.write_str("Ed25519 Secret Key"),
             Self::P256(_) => f.write_str("P256 Secret Key"),
+            #[cfg(feature = "p384")]
             Self::P384(_) => f.write_str("P384 Secret Key"),
         }
     }
@@ -112,6 +115,7 @@ pub fn pub_key_from_uncompressed(bytes: &[u8], curve: Curve) -> Result<EcPublicK
         Curve::Ed25519 => Ok(EcPublicKey::Ed25519(
             ed25519_dalek::VerifyingKey::from_bytes(bytes.try_into()?)?,
         )),
+        #[cfg(feature = "p384")]
         Curve::P384 => {
             let encoded_point =
                 p384::EncodedPoint::from_bytes(bytes).map_err(|_| EcError::EcKeyInvalidKeyData)?;
@@ -132,6 +136,7 @@ pub fn pub_key_to_uncompressed(key: &EcPublicKey) -> Result<Vec<u8>, EcError> {
         EcPublicKey::X25519(key) => Ok(key.to_bytes().to_vec()),
         EcPublicKey::Ed25519(key) => Ok(key.to_bytes().to_vec()),
         EcPublicKey::P256(key) => Ok(key.as_affine().to_encoded_point(false).as_bytes().to_vec()),
+        #[cfg(feature = "p384")]
         EcPublicKey::P384(key) => Ok(key.as_affine().to_encoded_point(false).as_bytes().to_vec()),
     }
 }
@@ -145,6 +150,7 @@ pub fn generate_private_key(curve: Curve) -> Result<EcPrivateKey, EcError> {
         Curve::Ed25519 => Ok(EcPrivateKey::Ed25519(ed25519_dalek::SigningKey::generate(
             &mut OsRng,
         ))),
+        #[cfg(feature = "p384")]
         Curve::P384 => Ok(EcPrivateKey::P384(p384::SecretKey::random(&mut OsRng))),
         _ => Err(EcError::UnsupportedCurve),
     }
@@ -160,6 +166,7 @@ pub fn private_key_from_bytes(bytes: &[u8], curve: Curve) -> Result<EcPrivateKey
             .map_err(|_| EcError::EcKeyInvalidKeyData)
             .map(|bytes: &[u8; 32]| EcPrivateKey::X25519(x25519_dalek::StaticSecret::from(*bytes))),
         Curve::Ed25519 => ed25519_private_from_bytes(bytes),
+        #[cfg(feature = "p384")]
         Curve::P384 => p384::SecretKey::from_slice(bytes)
             .map_err(|_| EcError::EcKeyInvalidKeyData)
             .map(EcPrivateKey::P384),
@@ -177,6 +184,7 @@ pub fn private_key_to_bytes(key: &EcPrivateKey) -> Result<Vec<u8>, EcError> {
         EcPrivateKey::X25519(key) => Ok(key.to_bytes().to_vec()),
         EcPrivateKey::Ed25519(key) => Ok(key.to_keypair_bytes().to_vec()),
         EcPrivateKey::P256(key) => Ok(key.to_bytes().to_vec()),
+        #[cfg(feature = "p384")]
         EcPrivateKey::P384(key) => Ok(key.to_bytes().to_vec()),
     }
 }
@@ -186,6 +194,7 @@ pub fn private_key_to_public(private_key: &EcPrivateKey) -> Result<EcPublicKey,
         EcPrivateKey::X25519(key) => Ok(EcPublicKey::X25519(x25519_dalek::PublicKey::from(key))),
         EcPrivateKey::Ed25519(key) => Ok(EcPublicKey::Ed25519(key.verifying_key())),
         EcPrivateKey::P256(key) => Ok(EcPublicKey::P256(key.public_key())),
+        #[cfg(feature = "p384")]
         EcPrivateKey::P384(key) => Ok(EcPublicKey::P384(key.public_key())),
     }
 }
@@ -202,6 +211,7 @@ fn ecdh_p256(
     Ok(shared_secret.raw_secret_bytes().to_vec())
 }
 
+#[cfg(feature = "p384")]
 fn ecdh_p384(
     private_key: &p384::SecretKey,
     public_key: &p384::PublicKey,
@@ -241,6 +251,7 @@ pub fn private_key_ecdh(
                 Err(EcError::EcdhKeyTypeMismatch)
             }
         }
+        #[cfg(feature = "p384")]
         EcPrivateKey::P384(private_key) => {
             if let EcPublicKey::P384(remote_public) = remote_public {
                 ecdh_p384(private_key, remote_public)
@@ -262,6 +273,7 @@ pub fn sign_p256(private_key: &p256::SecretKey, data: &[u8]) -> Result<Vec<u8>,
     Ok(signature.to_der().to_bytes().to_vec())
 }
 
+#[cfg(feature = "p384")]
 pub fn sign_p384(private_key: &p384::SecretKey, data: &[u8]) -> Result<Vec<u8>, EcError> {
     let signing_key = p384::ecdsa::SigningKey::from(private_key);
 
@@ -289,6 +301,7 @@ pub fn verify_p256(
     Ok(is_valid)
 }
 
+#[cfg(feature = "p384")]
 pub fn verify_p384(
     public_key: &p384::PublicKey,
     signature: &[u8],
@@ -98,6 +98,7 @@ pub fn pub_key_to_spki(key: &EcPublicKey) -> Result<Vec<u8>, EcX509Error> {
             .to_public_key_der()
             .map_err(|_| EcX509Error::NistSpkiError)?
             .to_vec()),
+        #[cfg(feature = "p384")]
         EcPublicKey::P384(key) => Ok(key
             .to_public_key_der()
             .map_err(|_| EcX509Error::NistSpkiError)?
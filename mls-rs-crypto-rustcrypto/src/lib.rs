@@ -111,6 +111,7 @@ impl RustCryptoProvider {
     pub fn all_supported_cipher_suites() -> Vec<CipherSuite> {
         vec![
             CipherSuite::P256_AES128,
+            #[cfg(feature = "p384")]
             CipherSuite::P384_AES256,
             CipherSuite::CURVE25519_AES128,
             CipherSuite::CURVE25519_CHACHA,
@@ -388,3 +389,14 @@ fn mls_core_tests() {
         mls_rs_core::crypto::test_suite::verify_hpke_encap_tests(&mut hpke, cs);
     }
 }
+
+#[cfg(not(feature = "p384"))]
+#[test]
+fn p384_is_not_supported_without_feature() {
+    let provider = RustCryptoProvider::new();
+
+    assert!(!RustCryptoProvider::all_supported_cipher_suites().contains(&CipherSuite::P384_AES256));
+    assert!(provider
+        .cipher_suite_provider(CipherSuite::P384_AES256)
+        .is_none());
+}
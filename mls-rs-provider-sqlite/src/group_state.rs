@@ -2,8 +2,11 @@
 // Copyright by contributors to this project.
 // SPDX-License-Identifier: (Apache-2.0 OR MIT)
 
-use mls_rs_core::group::{EpochRecord, GroupState, GroupStateStorage};
-use rusqlite::{params, Connection, OptionalExtension};
+use mls_rs_core::{
+    crypto::CipherSuite,
+    group::{EpochRecord, GroupState, GroupStateStorage, GroupWrite},
+};
+use rusqlite::{params, Connection, OptionalExtension, Transaction};
 use std::{
     fmt::Debug,
     sync::{Arc, Mutex},
@@ -11,13 +14,41 @@ use std::{
 
 use crate::SqLiteDataStorageError;
 
+#[cfg(feature = "compression")]
+use flate2::{read::ZlibDecoder, write::ZlibEncoder, Compression};
+#[cfg(feature = "compression")]
+use std::io::{Read, Write};
+
 pub(crate) const DEFAULT_EPOCH_RETENTION_LIMIT: u64 = 3;
 
+/// First byte of compressed snapshots and epoch data. Uncompressed mls-rs
+/// snapshots and epoch data start with a 0 byte from their version fields.
+const COMPRESSED_BLOB_HEADER: u8 = 0xff;
+
+#[cfg(feature = "compression")]
+fn decompress(data: &[u8]) -> Result<Vec<u8>, SqLiteDataStorageError> {
+    let mut decoded = Vec::new();
+
+    ZlibDecoder::new(data)
+        .read_to_end(&mut decoded)
+        .map(|_| decoded)
+        .map_err(|e| SqLiteDataStorageError::DataConversionError(e.into()))
+}
+
+#[cfg(not(feature = "compression"))]
+fn decompress(_data: &[u8]) -> Result<Vec<u8>, SqLiteDataStorageError> {
+    Err(SqLiteDataStorageError::DataConversionError(
+        "reading compressed data requires the compression feature".into(),
+    ))
+}
+
 #[derive(Debug, Clone)]
 /// SQLite Storage for MLS group states.
 pub struct SqLiteGroupStateStorage {
     connection: Arc<Mutex<Connection>>,
     max_epoch_retention: u64,
+    #[cfg(feature = "compression")]
+    compression: bool,
 }
 
 impl SqLiteGroupStateStorage {
@@ -25,16 +56,54 @@ impl SqLiteGroupStateStorage {
         SqLiteGroupStateStorage {
             connection: Arc::new(Mutex::new(connection)),
             max_epoch_retention: DEFAULT_EPOCH_RETENTION_LIMIT,
+            #[cfg(feature = "compression")]
+            compression: false,
         }
     }
 
     pub fn with_max_epoch_retention(self, max_epoch_retention: u64) -> Self {
         Self {
-            connection: self.connection,
             max_epoch_retention,
+            ..self
+        }
+    }
+
+    /// Compress group snapshots and epoch data with zlib before storing them.
+    ///
+    /// Compressed data is marked with a 1 byte header and decompressed when
+    /// read regardless of this setting, so a database can hold both
+    /// compressed and uncompressed data.
+    #[cfg(feature = "compression")]
+    pub fn with_compression(self, compression: bool) -> Self {
+        Self {
+            compression,
+            ..self
         }
     }
 
+    fn encode_blob(&self, data: Vec<u8>) -> Result<Vec<u8>, SqLiteDataStorageError> {
+        #[cfg(feature = "compression")]
+        if self.compression {
+            let mut encoder =
+                ZlibEncoder::new(vec![COMPRESSED_BLOB_HEADER], Compression::default());
+
+            return encoder
+                .write_all(&data)
+                .and_then(|_| encoder.finish())
+                .map_err(|e| SqLiteDataStorageError::DataConversionError(e.into()));
+        }
+
+        Ok(data)
+    }
+
+    fn decode_blob(&self, data: Vec<u8>) -> Result<Vec<u8>, SqLiteDataStorageError> {
+        if data.first() != Some(&COMPRESSED_BLOB_HEADER) {
+            return Ok(data);
+        }
+
+        decompress(&data[1..])
+    }
+
     /// List all the group ids for groups that are stored.
     pub fn group_ids(&self) -> Result<Vec<Vec<u8>>, SqLiteDataStorageError> {
         let connection = self.connection.lock().unwrap();
@@ -68,6 +137,52 @@ impl SqLiteGroupStateStorage {
             .map_err(|e| SqLiteDataStorageError::SqlEngineError(e.into()))
     }
 
+    /// Cipher suite used by a stored group, or `None` if the group is not
+    /// stored.
+    ///
+    /// Only the bytes holding the cipher suite are read from the stored
+    /// snapshot. mls-rs snapshots start with a 2 byte snapshot version
+    /// followed by the group context, whose protocol version and cipher suite
+    /// take 2 bytes each. If the snapshot is compressed, it is read and
+    /// decompressed entirely instead.
+    pub fn group_cipher_suite(
+        &self,
+        group_id: &[u8],
+    ) -> Result<Option<CipherSuite>, SqLiteDataStorageError> {
+        let prefix = self
+            .connection
+            .lock()
+            .unwrap()
+            .query_row(
+                "SELECT substr(snapshot, 1, 6) FROM mls_group WHERE group_id = ?",
+                [group_id],
+                |row| row.get::<_, Vec<u8>>(0),
+            )
+            .optional()
+            .map_err(|e| SqLiteDataStorageError::SqlEngineError(e.into()))?;
+
+        let snapshot = match prefix {
+            Some(prefix) if prefix.first() == Some(&COMPRESSED_BLOB_HEADER) => {
+                self.get_snapshot_data(group_id)?
+            }
+            prefix => prefix,
+        };
+
+        let bytes = snapshot.map(|snapshot| snapshot.get(4..6).unwrap_or_default().to_vec());
+
+        bytes
+            .map(|bytes| {
+                <[u8; 2]>::try_from(bytes)
+                    .map(|bytes| CipherSuite::from(u16::from_be_bytes(bytes)))
+                    .map_err(|_| {
+                        SqLiteDataStorageError::DataConversionError(
+                            "snapshot too short to contain a cipher suite".into(),
+                        )
+                    })
+            })
+            .transpose()
+    }
+
     pub fn max_epoch_retention(&self) -> u64 {
         self.max_epoch_retention
     }
@@ -85,7 +200,9 @@ impl SqLiteGroupStateStorage {
                 |row| row.get::<_, Vec<u8>>(0),
             )
             .optional()
-            .map_err(|e| SqLiteDataStorageError::SqlEngineError(e.into()))
+            .map_err(|e| SqLiteDataStorageError::SqlEngineError(e.into()))?
+            .map(|data| self.decode_blob(data))
+            .transpose()
     }
 
     fn get_epoch_data(
@@ -102,7 +219,9 @@ impl SqLiteGroupStateStorage {
                 |row| row.get::<_, Vec<u8>>(0),
             )
             .optional()
-            .map_err(|e| SqLiteDataStorageError::SqlEngineError(e.into()))
+            .map_err(|e| SqLiteDataStorageError::SqlEngineError(e.into()))?
+            .map(|data| self.decode_blob(data))
+            .transpose()
     }
 
     fn max_epoch_id(&self, group_id: &[u8]) -> Result<Option<u64>, SqLiteDataStorageError> {
@@ -117,6 +236,18 @@ impl SqLiteGroupStateStorage {
             .map_err(|e| SqLiteDataStorageError::SqlEngineError(e.into()))
     }
 
+    fn min_epoch_id(&self, group_id: &[u8]) -> Result<Option<u64>, SqLiteDataStorageError> {
+        let connection = self.connection.lock().unwrap();
+
+        connection
+            .query_row(
+                "SELECT MIN(epoch_id) FROM epoch WHERE group_id = ?",
+                params![group_id],
+                |row| row.get::<_, Option<u64>>(0),
+            )
+            .map_err(|e| SqLiteDataStorageError::SqlEngineError(e.into()))
+    }
+
     fn update_group_state(
         &self,
         group_id: &[u8],
@@ -124,13 +255,52 @@ impl SqLiteGroupStateStorage {
         inserts: Vec<EpochRecord>,
         updates: Vec<EpochRecord>,
     ) -> Result<(), SqLiteDataStorageError> {
-        let mut max_epoch_id = None;
+        let mut connection = self.connection.lock().unwrap();
+        let transaction = connection
+            .transaction()
+            .map_err(|e| SqLiteDataStorageError::SqlEngineError(e.into()))?;
+
+        self.write_group_state(&transaction, group_id, group_snapshot, inserts, updates)?;
+
+        // Execute the full transaction
+        transaction
+            .commit()
+            .map_err(|e| SqLiteDataStorageError::SqlEngineError(e.into()))
+    }
 
+    fn update_group_states(&self, writes: Vec<GroupWrite>) -> Result<(), SqLiteDataStorageError> {
         let mut connection = self.connection.lock().unwrap();
         let transaction = connection
             .transaction()
             .map_err(|e| SqLiteDataStorageError::SqlEngineError(e.into()))?;
 
+        for write in writes {
+            self.write_group_state(
+                &transaction,
+                &write.state.id,
+                write.state.data,
+                write.epoch_inserts,
+                write.epoch_updates,
+            )?;
+        }
+
+        // Execute the full transaction, nothing is written if any group failed
+        transaction
+            .commit()
+            .map_err(|e| SqLiteDataStorageError::SqlEngineError(e.into()))
+    }
+
+    fn write_group_state(
+        &self,
+        transaction: &Transaction,
+        group_id: &[u8],
+        group_snapshot: Vec<u8>,
+        inserts: Vec<EpochRecord>,
+        updates: Vec<EpochRecord>,
+    ) -> Result<(), SqLiteDataStorageError> {
+        let mut max_epoch_id = None;
+        let group_snapshot = self.encode_blob(group_snapshot)?;
+
         // Upsert into the group table to set the most recent snapshot
         transaction.execute(
             "INSERT INTO mls_group (group_id, snapshot) VALUES (?, ?) ON CONFLICT(group_id) DO UPDATE SET snapshot=excluded.snapshot",
@@ -144,7 +314,7 @@ impl SqLiteGroupStateStorage {
             transaction
                 .execute(
                     "INSERT INTO epoch (group_id, epoch_id, epoch_data) VALUES (?, ?, ?)",
-                    params![group_id, epoch.id, epoch.data],
+                    params![group_id, epoch.id, self.encode_blob(epoch.data)?],
                 )
                 .map(|_| ())
                 .map_err(|e| SqLiteDataStorageError::SqlEngineError(e.into()))?;
@@ -155,7 +325,7 @@ impl SqLiteGroupStateStorage {
             transaction
                 .execute(
                     "UPDATE epoch SET epoch_data = ? WHERE group_id = ? AND epoch_id = ?",
-                    params![epoch.data, group_id, epoch.id],
+                    params![self.encode_blob(epoch.data)?, group_id, epoch.id],
                 )
                 .map(|_| ())
                 .map_err(|e| SqLiteDataStorageError::SqlEngineError(e.into()))
@@ -175,10 +345,7 @@ impl SqLiteGroupStateStorage {
             }
         }
 
-        // Execute the full transaction
-        transaction
-            .commit()
-            .map_err(|e| SqLiteDataStorageError::SqlEngineError(e.into()))
+        Ok(())
     }
 }
 
@@ -199,6 +366,10 @@ impl GroupStateStorage for SqLiteGroupStateStorage {
         self.update_group_state(&group_id, snapshot_data, inserts, updates)
     }
 
+    async fn write_many(&mut self, writes: Vec<GroupWrite>) -> Result<(), Self::Error> {
+        self.update_group_states(writes)
+    }
+
     async fn state(&self, group_id: &[u8]) -> Result<Option<Vec<u8>>, Self::Error> {
         self.get_snapshot_data(group_id)
     }
@@ -207,6 +378,10 @@ impl GroupStateStorage for SqLiteGroupStateStorage {
         self.max_epoch_id(group_id)
     }
 
+    async fn min_epoch_id(&self, group_id: &[u8]) -> Result<Option<u64>, Self::Error> {
+        self.min_epoch_id(group_id)
+    }
+
     async fn epoch(&self, group_id: &[u8], epoch_id: u64) -> Result<Option<Vec<u8>>, Self::Error> {
         self.get_epoch_data(group_id, epoch_id)
     }
@@ -214,6 +389,8 @@ impl GroupStateStorage for SqLiteGroupStateStorage {
 
 #[cfg(test)]
 mod tests {
+    use assert_matches::assert_matches;
+
     use crate::{
         SqLiteDataStorageEngine,
         {connection_strategy::MemoryStrategy, test_utils::gen_rand_bytes},
@@ -292,6 +469,127 @@ mod tests {
         assert_eq!(epoch.unwrap(), test_data.epoch_0.data);
     }
 
+    #[test]
+    fn group_cipher_suites_can_be_read() {
+        let storage = get_test_storage();
+
+        // Snapshot version, protocol version and cipher suite as in mls-rs snapshots
+        let groups = [
+            (test_group_id(), CipherSuite::CURVE25519_AES128),
+            (test_group_id(), CipherSuite::P256_AES128),
+        ];
+
+        for (group_id, cipher_suite) in &groups {
+            let mut snapshot = [1u16, 1, **cipher_suite]
+                .iter()
+                .flat_map(|v| v.to_be_bytes())
+                .collect::<Vec<_>>();
+
+            snapshot.extend(test_snapshot());
+
+            storage
+                .update_group_state(group_id, snapshot, vec![], vec![])
+                .unwrap();
+        }
+
+        for (group_id, cipher_suite) in groups {
+            assert_eq!(
+                storage.group_cipher_suite(&group_id).unwrap(),
+                Some(cipher_suite)
+            );
+        }
+
+        assert_eq!(storage.group_cipher_suite(&test_group_id()).unwrap(), None);
+    }
+
+    #[cfg(feature = "compression")]
+    #[test]
+    fn compressed_and_uncompressed_state_can_be_mixed() {
+        let storage = get_test_storage();
+        let compressed_storage = storage.clone().with_compression(true);
+
+        let snapshot = [1u16, 1, *CipherSuite::P256_AES128]
+            .iter()
+            .flat_map(|v| v.to_be_bytes())
+            .chain(test_snapshot())
+            .collect::<Vec<_>>();
+
+        let group_ids = [test_group_id(), test_group_id()];
+
+        storage
+            .update_group_state(&group_ids[0], snapshot.clone(), vec![], vec![])
+            .unwrap();
+
+        compressed_storage
+            .update_group_state(&group_ids[1], snapshot.clone(), vec![], vec![])
+            .unwrap();
+
+        for storage in [&storage, &compressed_storage] {
+            for group_id in &group_ids {
+                assert_eq!(
+                    storage.get_snapshot_data(group_id).unwrap(),
+                    Some(snapshot.clone())
+                );
+
+                assert_eq!(
+                    storage.group_cipher_suite(group_id).unwrap(),
+                    Some(CipherSuite::P256_AES128)
+                );
+            }
+        }
+    }
+
+    #[cfg(feature = "compression")]
+    #[test]
+    fn compressed_state_round_trips_and_is_smaller() {
+        let storage = get_test_storage().with_compression(true);
+        let group_id = test_group_id();
+
+        // Snapshots of large groups repeat similar leaf node structures
+        let leaf = gen_rand_bytes(200);
+        let snapshot = leaf.repeat(500);
+
+        let epoch = EpochRecord {
+            data: leaf.repeat(50),
+            id: 0,
+        };
+
+        storage
+            .update_group_state(&group_id, snapshot.clone(), vec![epoch.clone()], vec![])
+            .unwrap();
+
+        assert_eq!(
+            storage.get_snapshot_data(&group_id).unwrap(),
+            Some(snapshot.clone())
+        );
+
+        assert_eq!(
+            storage.get_epoch_data(&group_id, 0).unwrap(),
+            Some(epoch.data)
+        );
+
+        assert_eq!(
+            storage.group_cipher_suite(&group_id).unwrap(),
+            Some(CipherSuite::from(u16::from_be_bytes([
+                snapshot[4],
+                snapshot[5]
+            ])))
+        );
+
+        let stored_len = storage
+            .connection
+            .lock()
+            .unwrap()
+            .query_row(
+                "SELECT length(snapshot) FROM mls_group WHERE group_id = ?",
+                [&group_id],
+                |row| row.get::<_, i64>(0),
+            )
+            .unwrap();
+
+        assert!(stored_len < snapshot.len() as i64);
+    }
+
     #[test]
     fn snapshot_and_epoch_can_be_updated() {
         let test_data = setup_group_storage_test();
@@ -328,6 +626,134 @@ mod tests {
         );
     }
 
+    #[test]
+    fn several_groups_can_be_written_at_once() {
+        let test_data = setup_group_storage_test();
+        let new_group_id = test_group_id();
+        let new_snapshot = test_snapshot();
+        let updated_snapshot = test_snapshot();
+
+        let writes = vec![
+            GroupWrite::new(
+                GroupState {
+                    id: new_group_id.clone(),
+                    data: new_snapshot.clone(),
+                },
+                vec![test_epoch(0)],
+                vec![],
+            ),
+            GroupWrite::new(
+                GroupState {
+                    id: test_data.group_id.clone(),
+                    data: updated_snapshot.clone(),
+                },
+                vec![test_epoch(1)],
+                vec![],
+            ),
+        ];
+
+        test_data.storage.update_group_states(writes).unwrap();
+
+        let storage = &test_data.storage;
+
+        assert_eq!(
+            storage.get_snapshot_data(&new_group_id).unwrap(),
+            Some(new_snapshot)
+        );
+
+        assert_eq!(
+            storage.get_snapshot_data(&test_data.group_id).unwrap(),
+            Some(updated_snapshot)
+        );
+
+        assert_eq!(storage.max_epoch_id(&test_data.group_id).unwrap(), Some(1));
+    }
+
+    #[test]
+    fn failed_write_of_several_groups_is_rolled_back() {
+        let test_data = setup_group_storage_test();
+        let new_group_id = test_group_id();
+
+        let writes = vec![
+            GroupWrite::new(
+                GroupState {
+                    id: new_group_id.clone(),
+                    data: test_snapshot(),
+                },
+                vec![test_epoch(0)],
+                vec![],
+            ),
+            // Epoch 0 is already stored for this group, so the insert fails
+            GroupWrite::new(
+                GroupState {
+                    id: test_data.group_id.clone(),
+                    data: test_snapshot(),
+                },
+                vec![test_epoch(0)],
+                vec![],
+            ),
+        ];
+
+        let res = test_data.storage.update_group_states(writes);
+
+        assert!(res.is_err());
+
+        let storage = &test_data.storage;
+
+        assert_eq!(storage.get_snapshot_data(&new_group_id).unwrap(), None);
+        assert_eq!(storage.max_epoch_id(&new_group_id).unwrap(), None);
+
+        assert_eq!(
+            storage.get_snapshot_data(&test_data.group_id).unwrap(),
+            Some(test_data.snapshot)
+        );
+
+        assert_eq!(
+            storage.get_epoch_data(&test_data.group_id, 0).unwrap(),
+            Some(test_data.epoch_0.data)
+        );
+    }
+
+    #[test]
+    fn failed_epoch_update_is_rolled_back() {
+        let test_data = setup_group_storage_test();
+
+        // Make every epoch update fail after the snapshot and inserts were written
+        test_data
+            .storage
+            .connection
+            .lock()
+            .unwrap()
+            .execute_batch(
+                "CREATE TEMP TRIGGER fail_epoch_update BEFORE UPDATE ON epoch
+                BEGIN SELECT RAISE(ABORT, 'injected failure'); END;",
+            )
+            .unwrap();
+
+        let res = test_data.storage.update_group_state(
+            &test_data.group_id,
+            test_snapshot(),
+            vec![test_epoch(1)],
+            vec![test_epoch(0)],
+        );
+
+        assert_matches!(res, Err(SqLiteDataStorageError::SqlEngineError(_)));
+
+        let storage = &test_data.storage;
+
+        assert_eq!(
+            storage.get_snapshot_data(&test_data.group_id).unwrap(),
+            Some(test_data.snapshot)
+        );
+
+        assert_eq!(storage.max_epoch_id(&test_data.group_id).unwrap(), Some(0));
+
+        assert_eq!(
+            storage.get_epoch_data(&test_data.group_id, 0).unwrap(),
+            Some(test_data.epoch_0.data)
+        );
+    }
+
     #[test]
     fn epochs_are_truncated() {
         test_epochs_are_truncated(9);
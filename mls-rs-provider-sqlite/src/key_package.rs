@@ -47,6 +47,31 @@ impl SqLiteKeyPackageStorage {
             .map_err(|e| SqLiteDataStorageError::SqlEngineError(e.into()))
     }
 
+    /// Insert a key package, replacing any key package already stored with
+    /// the same id.
+    pub fn upsert(
+        &mut self,
+        id: &[u8],
+        key_package: KeyPackageData,
+    ) -> Result<(), SqLiteDataStorageError> {
+        let connection = self.connection.lock().unwrap();
+
+        connection
+            .execute(
+                "INSERT INTO key_package (id, expiration, data) VALUES (?,?,?)
+                ON CONFLICT(id) DO UPDATE SET expiration=excluded.expiration, data=excluded.data",
+                params![
+                    id,
+                    key_package.expiration,
+                    key_package
+                        .mls_encode_to_vec()
+                        .map_err(|e| SqLiteDataStorageError::DataConversionError(e.into()))?
+                ],
+            )
+            .map(|_| ())
+            .map_err(|e| SqLiteDataStorageError::SqlEngineError(e.into()))
+    }
+
     fn get(&self, id: &[u8]) -> Result<Option<KeyPackageData>, SqLiteDataStorageError> {
         let connection = self.connection.lock().unwrap();
 
@@ -75,19 +100,36 @@ impl SqLiteKeyPackageStorage {
             .map_err(|e| SqLiteDataStorageError::SqlEngineError(e.into()))
     }
 
+    /// Delete all key packages from storage, returning the number of key
+    /// packages deleted.
+    pub fn delete_all(&self) -> Result<u64, SqLiteDataStorageError> {
+        let connection = self.connection.lock().unwrap();
+
+        connection
+            .execute("DELETE FROM key_package", params![])
+            .map(|deleted| deleted as u64)
+            .map_err(|e| SqLiteDataStorageError::SqlEngineError(e.into()))
+    }
+
     pub fn delete_expired(&self) -> Result<(), SqLiteDataStorageError> {
         self.delete_expired_by_time(MlsTime::now().seconds_since_epoch())
     }
 
     pub fn delete_expired_by_time(&self, time: u64) -> Result<(), SqLiteDataStorageError> {
+        self.prune_expired(MlsTime::from(time)).map(|_| ())
+    }
+
+    /// Delete all key packages whose lifetime ended before `now`, returning
+    /// the number of key packages deleted.
+    pub fn prune_expired(&self, now: MlsTime) -> Result<u64, SqLiteDataStorageError> {
         let connection = self.connection.lock().unwrap();
 
         connection
             .execute(
                 "DELETE FROM key_package where expiration < ?",
-                params![time],
+                params![now.seconds_since_epoch()],
             )
-            .map(|_| ())
+            .map(|deleted| deleted as u64)
             .map_err(|e| SqLiteDataStorageError::SqlEngineError(e.into()))
     }
 
@@ -128,7 +170,8 @@ mod tests {
         {connection_strategy::MemoryStrategy, test_utils::gen_rand_bytes},
     };
     use assert_matches::assert_matches;
-    use mls_rs_core::{crypto::HpkeSecretKey, key_package::KeyPackageData};
+    use mls_rs_core::{crypto::HpkeSecretKey, key_package::KeyPackageData, time::MlsTime};
+    use rusqlite::params;
 
     fn test_storage() -> SqLiteKeyPackageStorage {
         SqLiteDataStorageEngine::new(MemoryStrategy)
@@ -176,6 +219,28 @@ mod tests {
         assert_matches!(dupe_res, Err(SqLiteDataStorageError::SqlEngineError(_)));
     }
 
+    #[test]
+    fn upsert_should_replace() {
+        let mut storage = test_storage();
+        let (key_package_id, key_package) = test_key_package();
+
+        storage.upsert(&key_package_id, key_package).unwrap();
+
+        let (_, mut new_key_package) = test_key_package();
+        new_key_package.expiration = 456;
+
+        let insert_res = storage.insert(&key_package_id, new_key_package.clone());
+        assert_matches!(insert_res, Err(SqLiteDataStorageError::SqlEngineError(_)));
+
+        storage
+            .upsert(&key_package_id, new_key_package.clone())
+            .unwrap();
+
+        let from_storage = storage.get(&key_package_id).unwrap().unwrap();
+        assert_eq!(from_storage, new_key_package);
+        assert_eq!(storage.count().unwrap(), 1);
+    }
+
     #[test]
     fn key_package_not_found() {
         let mut storage = test_storage();
@@ -226,6 +291,80 @@ mod tests {
         assert!(storage.get(&data[3].0).unwrap().is_none());
     }
 
+    #[test]
+    fn prune_expired_key_packages() {
+        let mut storage = test_storage();
+        let now = MlsTime::now().seconds_since_epoch();
+
+        let data = [now - 100, now - 1, now + 100, now + 3600].map(|exp| {
+            let mut kp = test_key_package();
+            kp.1.expiration = exp;
+            kp
+        });
+
+        for (id, data) in &data {
+            storage.insert(id, data.clone()).unwrap();
+        }
+
+        assert_eq!(storage.prune_expired(MlsTime::from(now)).unwrap(), 2);
+
+        assert!(storage.get(&data[0].0).unwrap().is_none());
+        assert!(storage.get(&data[1].0).unwrap().is_none());
+        storage.get(&data[2].0).unwrap().unwrap();
+        storage.get(&data[3].0).unwrap().unwrap();
+
+        assert_eq!(storage.prune_expired(MlsTime::from(now)).unwrap(), 0);
+        assert_eq!(storage.count().unwrap(), 2);
+    }
+
+    #[test]
+    fn expiration_column_is_populated_and_indexed() {
+        let mut storage = test_storage();
+
+        let data = [10, 20, 30, 40].map(|exp| {
+            let mut kp = test_key_package();
+            kp.1.expiration = exp;
+            kp
+        });
+
+        for (id, data) in &data {
+            storage.insert(id, data.clone()).unwrap();
+        }
+
+        let connection = storage.connection.lock().unwrap();
+
+        let expiration: u64 = connection
+            .query_row(
+                "SELECT expiration FROM key_package WHERE id = ?",
+                params![data[2].0],
+                |row| row.get(0),
+            )
+            .unwrap();
+
+        assert_eq!(expiration, 30);
+
+        let in_range: Vec<Vec<u8>> = connection
+            .prepare(
+                "SELECT id FROM key_package WHERE expiration BETWEEN ? AND ? ORDER BY expiration",
+            )
+            .unwrap()
+            .query_map(params![15, 35], |row| row.get(0))
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+
+        assert_eq!(in_range, vec![data[1].0.clone(), data[2].0.clone()]);
+
+        let index_used: bool = connection
+            .prepare("EXPLAIN QUERY PLAN SELECT id FROM key_package WHERE expiration < ?")
+            .unwrap()
+            .query_map(params![15], |row| row.get::<_, String>(3))
+            .unwrap()
+            .any(|detail| detail.unwrap().contains("key_package_exp"));
+
+        assert!(index_used);
+    }
+
     #[test]
     fn key_count() {
         let mut storage = test_storage();
@@ -240,4 +379,19 @@ mod tests {
 
         assert_eq!(storage.count().unwrap(), 10);
     }
+
+    #[test]
+    fn delete_all_key_packages() {
+        let mut storage = test_storage();
+
+        let test_packages = (0..10).map(|_| test_key_package()).collect::<Vec<_>>();
+
+        for (key_package_id, key_package) in &test_packages {
+            storage.insert(key_package_id, key_package.clone()).unwrap();
+        }
+
+        assert_eq!(storage.delete_all().unwrap(), 10);
+        assert_eq!(storage.count().unwrap(), 0);
+        assert_eq!(storage.delete_all().unwrap(), 0);
+    }
 }
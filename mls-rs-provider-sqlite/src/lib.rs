@@ -5,7 +5,7 @@
 use connection_strategy::ConnectionStrategy;
 use group_state::SqLiteGroupStateStorage;
 use psk::SqLitePreSharedKeyStorage;
-use rusqlite::Connection;
+use rusqlite::{Connection, OptionalExtension};
 use storage::{SqLiteApplicationStorage, SqLiteKeyPackageStorage};
 use thiserror::Error;
 
@@ -46,6 +46,9 @@ pub enum SqLiteDataStorageError {
     #[error("invalid key, must use SqlCipherKey::RawKeyWithSalt with plaintext_header_size > 0")]
     /// Invalid SQLCipher key header.
     SqlCipherKeyInvalidWithHeader,
+    #[error("database integrity check failed: {0}")]
+    /// The database did not pass the SQLite integrity check.
+    IntegrityCheckFailed(String),
 }
 
 impl mls_rs_core::error::IntoAnyError for SqLiteDataStorageError {
@@ -90,6 +93,28 @@ where
         Ok(connection)
     }
 
+    /// Check that the database is reachable, passes the SQLite integrity check
+    /// and contains all tables of the current schema.
+    pub fn health_check(&self) -> Result<(), SqLiteDataStorageError> {
+        let connection = self.create_connection()?;
+
+        let integrity = connection
+            .pragma_query_value(None, "integrity_check", |row| row.get::<_, String>(0))
+            .map_err(|e| SqLiteDataStorageError::SqlEngineError(e.into()))?;
+
+        if integrity != "ok" {
+            return Err(SqLiteDataStorageError::IntegrityCheckFailed(integrity));
+        }
+
+        SCHEMA_TABLES.iter().try_for_each(|table| {
+            connection
+                .query_row(&format!("SELECT 1 FROM {table} LIMIT 1"), [], |_| Ok(()))
+                .optional()
+                .map(|_| ())
+                .map_err(|e| SqLiteDataStorageError::SqlEngineError(e.into()))
+        })
+    }
+
     /// Returns a struct that implements the `GroupStateStorage` trait for use in MLS.
     pub fn group_state_storage(&self) -> Result<SqLiteGroupStateStorage, SqLiteDataStorageError> {
         Ok(SqLiteGroupStateStorage::new(self.create_connection()?))
@@ -115,6 +140,8 @@ where
     }
 }
 
+const SCHEMA_TABLES: [&str; 5] = ["mls_group", "epoch", "key_package", "psk", "kvs"];
+
 fn create_tables_v1(connection: &Connection) -> Result<(), SqLiteDataStorageError> {
     connection
         .execute_batch(
@@ -152,7 +179,13 @@ fn create_tables_v1(connection: &Connection) -> Result<(), SqLiteDataStorageErro
 
 #[cfg(test)]
 mod tests {
-    use crate::{connection_strategy::MemoryStrategy, SqLiteDataStorageEngine};
+    use assert_matches::assert_matches;
+    use tempfile::NamedTempFile;
+
+    use crate::{
+        connection_strategy::{FileConnectionStrategy, MemoryStrategy},
+        SqLiteDataStorageEngine, SqLiteDataStorageError,
+    };
 
     #[test]
     pub fn user_version_test() {
@@ -170,4 +203,31 @@ mod tests {
 
         assert_eq!(current_schema, 1);
     }
+
+    #[test]
+    fn health_check_passes_on_fresh_database() {
+        let database = SqLiteDataStorageEngine::new(MemoryStrategy).unwrap();
+
+        database.health_check().unwrap();
+    }
+
+    #[test]
+    fn health_check_fails_on_missing_table() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let database =
+            SqLiteDataStorageEngine::new(FileConnectionStrategy::new(temp_file.path())).unwrap();
+
+        database.health_check().unwrap();
+
+        database
+            .create_connection()
+            .unwrap()
+            .execute("DROP TABLE psk", [])
+            .unwrap();
+
+        assert_matches!(
+            database.health_check(),
+            Err(SqLiteDataStorageError::SqlEngineError(_))
+        );
+    }
 }
@@ -133,4 +133,14 @@ mod tests {
 
         assert!(storage.get(&psk_id).unwrap().is_none());
     }
+
+    #[test]
+    fn test_not_found() {
+        let (psk_id, psk) = test_psk();
+        let storage = test_storage();
+
+        storage.insert(&psk_id, &psk).unwrap();
+
+        assert!(storage.get(&gen_rand_bytes(32)).unwrap().is_none());
+    }
 }
@@ -267,6 +267,9 @@ pub enum ReceivedMessage {
     Welcome,
     /// Validated key package.
     KeyPackage,
+    /// A commit for a future epoch was buffered until the group
+    /// reaches `epoch`.
+    BufferedCommit { epoch: u64 },
 }
 
 /// Supported cipher suites.
@@ -767,6 +770,9 @@ impl Group {
             group::ReceivedMessage::GroupInfo(_) => Ok(ReceivedMessage::GroupInfo),
             group::ReceivedMessage::Welcome => Ok(ReceivedMessage::Welcome),
             group::ReceivedMessage::KeyPackage(_) => Ok(ReceivedMessage::KeyPackage),
+            group::ReceivedMessage::BufferedCommit { epoch } => {
+                Ok(ReceivedMessage::BufferedCommit { epoch })
+            }
         }
     }
 }
@@ -0,0 +1,30 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// Copyright by contributors to this project.
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+use criterion::{BatchSize, BenchmarkId, Criterion};
+use mls_rs::{test_utils::benchmarks::create_large_group, CipherSuite};
+
+// Path secrets are encrypted in parallel by the `rayon` feature. Run with
+// `RAYON_NUM_THREADS=1` to compare with a single thread.
+fn bench(c: &mut Criterion) {
+    let cipher_suite = CipherSuite::CURVE25519_AES128;
+    let size = 4096;
+    let group = create_large_group(cipher_suite, size);
+    let mut bench_group = c.benchmark_group("large_group_commit");
+
+    bench_group.bench_with_input(
+        BenchmarkId::new(format!("{cipher_suite:?}"), size),
+        &size,
+        |b, _| {
+            b.iter_batched_ref(
+                || group.clone(),
+                |group| group.commit(vec![]).unwrap(),
+                BatchSize::SmallInput,
+            )
+        },
+    );
+}
+
+criterion::criterion_group!(benches, bench);
+criterion::criterion_main!(benches);
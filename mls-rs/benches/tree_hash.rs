@@ -0,0 +1,30 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// Copyright by contributors to this project.
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+use criterion::{BatchSize, Criterion};
+use mls_rs::{test_utils::benchmarks::TreeHashBenchmark, CipherSuite};
+
+fn bench(c: &mut Criterion) {
+    let tree = TreeHashBenchmark::new(CipherSuite::CURVE25519_AES128, 4096);
+    let mut bench_group = c.benchmark_group("tree_hash");
+
+    bench_group.bench_function("full", |b| {
+        b.iter_batched_ref(
+            || tree.without_hashes(),
+            |tree| tree.tree_hash(),
+            BatchSize::SmallInput,
+        )
+    });
+
+    bench_group.bench_function("incremental", |b| {
+        b.iter_batched_ref(
+            || tree.clone(),
+            |tree| tree.update_leaf_hash(1234),
+            BatchSize::SmallInput,
+        )
+    });
+}
+
+criterion::criterion_group!(benches, bench);
+criterion::criterion_main!(benches);
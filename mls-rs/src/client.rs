@@ -3,10 +3,16 @@
 // SPDX-License-Identifier: (Apache-2.0 OR MIT)
 
 use crate::cipher_suite::CipherSuite;
-use crate::client_builder::{recreate_config, BaseConfig, ClientBuilder, MakeConfig};
+#[cfg(not(all(feature = "wasm", target_arch = "wasm32")))]
+use crate::client_builder::BaseConfig;
+#[cfg(all(feature = "wasm", target_arch = "wasm32"))]
+use crate::client_builder::DefaultConfig;
+use crate::client_builder::{recreate_config, ClientBuilder, MakeConfig};
 use crate::client_config::ClientConfig;
+pub use crate::client_config::Registries;
 use crate::group::framing::MlsMessage;
 
+use crate::group::proposal::INVALID_PROPOSAL_DISCRIMINANT;
 use crate::group::{cipher_suite_provider, validate_group_info_joiner, GroupInfo};
 use crate::group::{
     framing::MlsMessagePayload, snapshot::Snapshot, ExportedTree, Group, NewMemberInfo,
@@ -18,9 +24,10 @@ use crate::group::{
     proposal::{AddProposal, Proposal},
 };
 use crate::identity::SigningIdentity;
-use crate::key_package::{KeyPackageGeneration, KeyPackageGenerator};
+use crate::key_package::{KeyPackageGeneration, KeyPackageGenerator, KeyPackageRef};
 use crate::protocol_version::ProtocolVersion;
 use crate::tree_kem::node::NodeIndex;
+use crate::tree_kem::node::RATCHET_TREE_TOO_LARGE;
 use alloc::vec::Vec;
 use mls_rs_codec::MlsDecode;
 use mls_rs_core::crypto::{CryptoProvider, SignatureSecretKey};
@@ -30,6 +37,9 @@ use mls_rs_core::group::{GroupStateStorage, ProposalType};
 use mls_rs_core::identity::{CredentialType, IdentityProvider};
 use mls_rs_core::key_package::KeyPackageStorage;
 
+#[cfg(feature = "psk")]
+use mls_rs_core::psk::ExternalPskId;
+
 use crate::group::external_commit::ExternalCommitBuilder;
 
 #[cfg(feature = "by_ref_proposal")]
@@ -62,6 +72,36 @@ pub enum MlsError {
     CommitMissingPath,
     #[cfg_attr(feature = "std", error("plaintext message for incorrect epoch"))]
     InvalidEpoch,
+    #[cfg_attr(
+        feature = "std",
+        error(
+            "handshake message for past epoch {message_epoch}, current epoch is {current_epoch}"
+        )
+    )]
+    StaleEpoch {
+        message_epoch: u64,
+        current_epoch: u64,
+    },
+    #[cfg_attr(
+        feature = "std",
+        error(
+            "handshake message for future epoch {message_epoch}, current epoch is {current_epoch}"
+        )
+    )]
+    FutureEpoch {
+        message_epoch: u64,
+        current_epoch: u64,
+    },
+    #[cfg_attr(
+        feature = "std",
+        error("commit for a future epoch not buffered, buffer of {0} commits is full")
+    )]
+    CommitBufferFull(usize),
+    #[cfg_attr(
+        feature = "std",
+        error("proposal not cached, limit of {0} pending proposals reached")
+    )]
+    TooManyPendingProposals(usize),
     #[cfg_attr(feature = "std", error("invalid signature found"))]
     InvalidSignature,
     #[cfg_attr(feature = "std", error("invalid confirmation tag"))]
@@ -86,14 +126,36 @@ pub enum MlsError {
         error("ratchet tree not provided or discovered in GroupInfo")
     )]
     RatchetTreeNotFound,
+    #[cfg_attr(feature = "std", error("ratchet tree has more nodes than allowed"))]
+    RatchetTreeTooLarge,
     #[cfg_attr(feature = "std", error("External sender cannot commit"))]
     ExternalSenderCannotCommit,
     #[cfg_attr(feature = "std", error("Unsupported protocol version {0:?}"))]
     UnsupportedProtocolVersion(ProtocolVersion),
     #[cfg_attr(feature = "std", error("Protocol version mismatch"))]
     ProtocolVersionMismatch,
+    #[cfg_attr(
+        feature = "std",
+        error("Protocol version {version:?} is older than the minimum {minimum:?}")
+    )]
+    ProtocolVersionTooOld {
+        version: ProtocolVersion,
+        minimum: ProtocolVersion,
+    },
     #[cfg_attr(feature = "std", error("Unsupported cipher suite {0:?}"))]
     UnsupportedCipherSuite(CipherSuite),
+    #[cfg_attr(
+        feature = "std",
+        error(
+            "Welcome uses unsupported cipher suite {cipher_suite:?} or protocol version {protocol_version:?}, supported cipher suites are {supported_cipher_suites:?} and protocol versions {supported_protocol_versions:?}"
+        )
+    )]
+    UnsupportedWelcome {
+        cipher_suite: CipherSuite,
+        protocol_version: ProtocolVersion,
+        supported_cipher_suites: Vec<CipherSuite>,
+        supported_protocol_versions: Vec<ProtocolVersion>,
+    },
     #[cfg_attr(feature = "std", error("Signing key of external sender is unknown"))]
     UnknownSigningIdentityForExternalSender,
     #[cfg_attr(
@@ -127,6 +189,11 @@ pub enum MlsError {
         error("External commit missing ExternalInit proposal")
     )]
     ExternalCommitMissingExternalInit,
+    #[cfg_attr(
+        feature = "std",
+        error("External commit missing required PSK proposal")
+    )]
+    ExternalCommitMissingPsk,
     #[cfg_attr(
         feature = "std",
         error(
@@ -134,6 +201,8 @@ pub enum MlsError {
         )
     )]
     GroupUsedAfterReInit,
+    #[cfg_attr(feature = "std", error("the local member was removed from the group"))]
+    RemovedFromGroup,
     #[cfg_attr(feature = "std", error("Pending ReIinit not found."))]
     PendingReInitNotFound,
     #[cfg_attr(
@@ -163,19 +232,24 @@ pub enum MlsError {
     #[cfg_attr(feature = "std", error("invalid sender for content type"))]
     InvalidSender,
     #[cfg_attr(feature = "std", error("GroupID mismatch"))]
-    GroupIdMismatch,
+    GroupIdMismatch { expected: Vec<u8>, found: Vec<u8> },
+    #[cfg_attr(feature = "std", error("group id of length {0} is too long"))]
+    GroupIdTooLong(usize),
     #[cfg_attr(feature = "std", error("storage retention can not be zero"))]
     NonZeroRetentionRequired,
     #[cfg_attr(feature = "std", error("Too many PSK IDs to compute PSK secret"))]
     TooManyPskIds,
-    #[cfg_attr(feature = "std", error("Missing required Psk"))]
-    MissingRequiredPsk,
+    #[cfg(feature = "psk")]
+    #[cfg_attr(feature = "std", error("external PSK {0:?} not found in PSK storage"))]
+    MissingPsk(ExternalPskId),
     #[cfg_attr(feature = "std", error("Old group state not found"))]
     OldGroupStateNotFound,
     #[cfg_attr(feature = "std", error("leaf secret already consumed"))]
     InvalidLeafConsumption,
     #[cfg_attr(feature = "std", error("key not available, invalid generation {0}"))]
     KeyMissing(u32),
+    #[cfg_attr(feature = "std", error("application message was already received"))]
+    DuplicateMessage,
     #[cfg_attr(
         feature = "std",
         error("requested generation {0} is too far ahead of current generation")
@@ -265,8 +339,11 @@ pub enum MlsError {
     InvalidCommitSelfUpdate,
     #[cfg_attr(feature = "std", error("A PreSharedKey proposal must have a PSK of type External or type Resumption and usage Application"))]
     InvalidTypeOrUsageInPreSharedKeyProposal,
-    #[cfg_attr(feature = "std", error("psk nonce length does not match cipher suite"))]
-    InvalidPskNonceLength,
+    #[cfg_attr(
+        feature = "std",
+        error("psk nonce length {found} does not match cipher suite, expected {expected}")
+    )]
+    InvalidPskNonceLength { expected: usize, found: usize },
     #[cfg_attr(
         feature = "std",
         error("ReInit proposal protocol version is less than the version of the original group")
@@ -318,6 +395,13 @@ pub enum MlsError {
     UnsupportedGroupExtension(ExtensionType),
     #[cfg_attr(feature = "std", error("Unsupported custom proposal type {0:?}"))]
     UnsupportedCustomProposal(ProposalType),
+    #[cfg_attr(feature = "std", error("Incorrect custom proposal type {0:?}"))]
+    IncorrectCustomProposalType(ProposalType),
+    #[cfg_attr(
+        feature = "std",
+        error("Custom proposal {0:?} expanded into another custom proposal")
+    )]
+    RecursiveCustomProposalExpansion(ProposalType),
     #[cfg_attr(feature = "std", error("by-ref proposal not found"))]
     ProposalNotFound,
     #[cfg_attr(
@@ -338,6 +422,21 @@ pub enum MlsError {
     InvalidGroupInfo,
     #[cfg_attr(feature = "std", error("Invalid welcome message"))]
     InvalidWelcomeMessage,
+    #[cfg_attr(
+        feature = "std",
+        error("Importing epoch secrets is not enabled for this external client")
+    )]
+    EpochSecretImportDisabled,
+    #[cfg_attr(
+        feature = "std",
+        error("Custom cipher suite {0:?} has KDF extract size {1} smaller than AEAD key size {2}")
+    )]
+    CustomCipherSuiteKdfAeadMismatch(CipherSuite, usize, usize),
+    #[cfg_attr(
+        feature = "std",
+        error("Invalid proposal or proposal reference discriminant")
+    )]
+    InvalidProposalDiscriminant,
 }
 
 impl IntoAnyError for MlsError {
@@ -350,7 +449,13 @@ impl IntoAnyError for MlsError {
 impl From<mls_rs_codec::Error> for MlsError {
     #[inline]
     fn from(e: mls_rs_codec::Error) -> Self {
-        MlsError::SerializationError(e.into_any_error())
+        match e {
+            mls_rs_codec::Error::Custom(INVALID_PROPOSAL_DISCRIMINANT) => {
+                MlsError::InvalidProposalDiscriminant
+            }
+            mls_rs_codec::Error::Custom(RATCHET_TREE_TOO_LARGE) => MlsError::RatchetTreeTooLarge,
+            e => MlsError::SerializationError(e.into_any_error()),
+        }
     }
 }
 
@@ -378,12 +483,28 @@ pub struct Client<C> {
     pub(crate) version: ProtocolVersion,
 }
 
+/// Maximum length in bytes of a group id provided to
+/// [`Client::create_group_with_id`].
+pub const MAX_GROUP_ID_LEN: usize = 255;
+
 impl Client<()> {
     /// Returns a [`ClientBuilder`]
     /// used to configure client preferences and providers.
+    #[cfg(not(all(feature = "wasm", target_arch = "wasm32")))]
     pub fn builder() -> ClientBuilder<BaseConfig> {
         ClientBuilder::new()
     }
+
+    /// Returns a [`ClientBuilder`]
+    /// used to configure client preferences and providers.
+    ///
+    /// The builder comes with the
+    /// [`RustCryptoProvider`](crate::crypto::RustCryptoProvider) already
+    /// selected. See [`DefaultConfig`].
+    #[cfg(all(feature = "wasm", target_arch = "wasm32"))]
+    pub fn builder() -> ClientBuilder<DefaultConfig> {
+        ClientBuilder::new().crypto_provider(crate::crypto::RustCryptoProvider::new())
+    }
 }
 
 #[cfg_attr(all(feature = "ffi", not(test)), safer_ffi_gen::safer_ffi_gen)]
@@ -481,12 +602,18 @@ where
     /// It is recommended to use [create_group](Client::create_group)
     /// instead of this function because it guarantees that group_id values
     /// are globally unique.
+    ///
+    /// `group_id` must not be longer than [`MAX_GROUP_ID_LEN`] bytes.
     #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
     pub async fn create_group_with_id(
         &self,
         group_id: Vec<u8>,
         group_context_extensions: ExtensionList,
     ) -> Result<Group<C>, MlsError> {
+        if group_id.len() > MAX_GROUP_ID_LEN {
+            return Err(MlsError::GroupIdTooLong(group_id.len()));
+        }
+
         let (signing_identity, cipher_suite) = self.signing_identity()?;
 
         Group::new(
@@ -506,6 +633,15 @@ where
     /// The `cipher_suite` provided must be supported by the
     /// [CipherSuiteProvider](crate::CipherSuiteProvider)
     /// that was used to build the client.
+    ///
+    /// `group_context_extensions` become the initial extensions of the group
+    /// context, for instance a
+    /// [`RequiredCapabilitiesExt`](crate::extension::built_in::RequiredCapabilitiesExt)
+    /// or an `ExternalSendersExt`.
+    /// The capabilities of this client must satisfy them: every non-default
+    /// extension type and every required capability must be supported, or
+    /// an error such as [`MlsError::RequiredExtensionNotFound`] or
+    /// [`MlsError::UnsupportedGroupExtension`] is returned.
     #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
     pub async fn create_group(
         &self,
@@ -559,6 +695,49 @@ where
         Group::decrypt_group_info(welcome_message, &self.config).await
     }
 
+    /// Select the Welcome message that lets this client join at the highest
+    /// epoch, e.g. among several Welcome messages received for the same group
+    /// because of retries.
+    ///
+    /// Welcome messages this client can not decrypt are skipped. If several
+    /// messages lead to the same epoch, the first one is returned.
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    #[cfg_attr(all(feature = "ffi", not(test)), safer_ffi_gen::safer_ffi_gen_ignore)]
+    pub async fn select_latest_welcome(&self, welcomes: Vec<MlsMessage>) -> Option<MlsMessage> {
+        let mut latest: Option<(u64, MlsMessage)> = None;
+
+        for welcome in welcomes {
+            let Ok(group_info) = self.examine_welcome_message(&welcome).await else {
+                continue;
+            };
+
+            let epoch = group_info.group_context().epoch;
+
+            if latest.as_ref().map_or(true, |(latest, _)| epoch > *latest) {
+                latest = Some((epoch, welcome));
+            }
+        }
+
+        latest.map(|(_, welcome)| welcome)
+    }
+
+    /// Derive the welcome secret of a Welcome message for the key package
+    /// referenced by `key_package_ref`, without joining the group.
+    ///
+    /// This is a diagnostic tool meant to pinpoint mismatches with other
+    /// implementations when a join fails. The returned value is secret and
+    /// must not be used for anything else.
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    pub async fn debug_welcome_secret(
+        &self,
+        welcome_message: &MlsMessage,
+        key_package_ref: &KeyPackageRef,
+    ) -> Result<Vec<u8>, MlsError> {
+        Group::welcome_secret(welcome_message, &self.config, key_package_ref)
+            .await
+            .map(|secret| secret.to_vec())
+    }
+
     /// Validate GroupInfo message. This does NOT validate the ratchet tree in case
     /// it is provided in the extension. It validates the signature, identity of the
     /// signer, identities of external senders and cipher suite.
@@ -697,6 +876,7 @@ where
             protocol_version,
             group_info,
             tree_data,
+            self.config.max_ratchet_tree_size(),
             &self.config.identity_provider(),
             &cipher_suite_provider,
         )
@@ -774,6 +954,13 @@ where
     pub fn identity_provider(&self) -> <C as ClientConfig>::IdentityProvider {
         self.config.identity_provider()
     }
+
+    /// Extension, proposal and credential types that this client was
+    /// configured to support.
+    #[cfg_attr(all(feature = "ffi", not(test)), safer_ffi_gen::safer_ffi_gen_ignore)]
+    pub fn registries(&self) -> Registries {
+        self.config.registries()
+    }
 }
 
 #[cfg(test)]
@@ -828,7 +1015,8 @@ mod tests {
 
     use super::*;
     use crate::{
-        crypto::test_utils::TestCryptoProvider,
+        crypto::test_utils::{test_cipher_suite_provider, TestCryptoProvider},
+        group::key_schedule::WelcomeSecret,
         identity::test_utils::{get_test_basic_credential, get_test_signing_identity},
         tree_kem::leaf_node::LeafNodeSource,
     };
@@ -886,6 +1074,27 @@ mod tests {
         }
     }
 
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn key_package_reference_matches_storage_id() {
+        let (client, key_package) =
+            test_client_with_key_pkg(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE, "alice").await;
+
+        let key_package = MlsMessage::from_bytes(&key_package.to_bytes().unwrap())
+            .unwrap()
+            .into_key_package()
+            .unwrap();
+
+        let reference = key_package
+            .to_reference(&test_cipher_suite_provider(TEST_CIPHER_SUITE))
+            .await
+            .unwrap();
+
+        let stored = client.key_package_store().key_packages();
+
+        assert_eq!(stored.len(), 1);
+        assert_eq!(stored[0].0, reference.to_vec());
+    }
+
     #[cfg(feature = "by_ref_proposal")]
     #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
     async fn new_member_add_proposal_adds_to_group() {
@@ -1038,6 +1247,57 @@ mod tests {
         join_via_external_commit(true, true).await.unwrap();
     }
 
+    #[cfg(feature = "psk")]
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn external_commit_without_required_psk_is_rejected() {
+        let psk = PreSharedKey::from(b"psk".to_vec());
+        let psk_id = ExternalPskId::new(b"psk id".to_vec());
+
+        let mut alice_group =
+            test_group_custom_config(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE, |c| {
+                c.psk(psk_id.clone(), psk.clone())
+                    .require_external_commit_psk()
+            })
+            .await;
+
+        // An external commit cannot be the first commit in a group.
+        alice_group.join("bob").await;
+
+        for with_psk in [false, true] {
+            let group_info_msg = alice_group
+                .group_info_message_allowing_ext_commit(true)
+                .await
+                .unwrap();
+
+            let charlie = TestClientBuilder::new_for_test()
+                .psk(psk_id.clone(), psk.clone())
+                .with_random_signing_identity("charlie", TEST_CIPHER_SUITE)
+                .await
+                .build();
+
+            let mut builder = charlie.external_commit_builder().unwrap();
+
+            if with_psk {
+                builder = builder.with_external_psk(psk_id.clone());
+            }
+
+            let (charlie_group, external_commit) = builder.build(group_info_msg).await.unwrap();
+
+            let res = alice_group.process_incoming_message(external_commit).await;
+
+            if with_psk {
+                res.unwrap();
+
+                assert_eq!(
+                    alice_group.epoch_authenticator().unwrap(),
+                    charlie_group.epoch_authenticator().unwrap()
+                );
+            } else {
+                assert_matches!(res, Err(MlsError::ExternalCommitMissingPsk));
+            }
+        }
+    }
+
     #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
     async fn creating_an_external_commit_requires_a_group_info_message() {
         let (alice_identity, secret_key) =
@@ -1094,6 +1354,24 @@ mod tests {
         assert_eq!(bob.config.supported_extensions(), [33, 34].map(Into::into));
     }
 
+    #[test]
+    fn registries_reflect_builder_configuration() {
+        let client = TestClientBuilder::new_for_test()
+            .extension_type(33.into())
+            .custom_proposal_type(ProposalType::new(65002))
+            .build();
+
+        let registries = client.registries();
+
+        assert_eq!(registries.extensions, [ExtensionType::new(33)]);
+        assert_eq!(registries.proposals, [ProposalType::new(65002)]);
+
+        assert_eq!(
+            registries.credentials,
+            client.identity_provider().supported_types()
+        );
+    }
+
     #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
     async fn examine_welcome_message() {
         let mut alice = test_group(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE)
@@ -1135,6 +1413,66 @@ mod tests {
         assert_eq!(expected_group_info, group_info);
     }
 
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn latest_welcome_is_selected() {
+        let mut alice = test_group(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE)
+            .await
+            .group;
+
+        let (bob, kp) =
+            test_client_with_key_pkg(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE, "bob").await;
+
+        let first = alice
+            .commit_builder()
+            .add_member(kp)
+            .unwrap()
+            .build()
+            .await
+            .unwrap()
+            .welcome_messages
+            .remove(0);
+
+        alice.apply_pending_commit().await.unwrap();
+
+        // Bob is removed and added again with a new key package, as if he rejoined
+        alice
+            .commit_builder()
+            .remove_member(1)
+            .unwrap()
+            .build()
+            .await
+            .unwrap();
+
+        alice.apply_pending_commit().await.unwrap();
+
+        let kp = bob.generate_key_package_message().await.unwrap();
+
+        let second = alice
+            .commit_builder()
+            .add_member(kp)
+            .unwrap()
+            .build()
+            .await
+            .unwrap()
+            .welcome_messages
+            .remove(0);
+
+        alice.apply_pending_commit().await.unwrap();
+
+        let latest = bob
+            .select_latest_welcome(vec![first.clone(), second.clone()])
+            .await;
+
+        assert_eq!(latest.as_ref(), Some(&second));
+
+        let latest = bob.select_latest_welcome(vec![second.clone(), first]).await;
+
+        assert_eq!(latest, Some(second));
+
+        let latest = bob.select_latest_welcome(vec![]).await;
+        assert!(latest.is_none());
+    }
+
     #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
     async fn validate_group_info() {
         let alice = test_group(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE)
@@ -1159,4 +1497,204 @@ mod tests {
         let res = bob.validate_group_info(&group_info, &other_signer).await;
         assert_matches!(res, Err(MlsError::InvalidSignature));
     }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn debug_welcome_secret_decrypts_group_info() {
+        let mut alice = test_group(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE).await;
+
+        let (bob, key_package) =
+            test_client_with_key_pkg(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE, "bob").await;
+
+        let cs = test_cipher_suite_provider(TEST_CIPHER_SUITE);
+
+        let key_package_ref = key_package
+            .as_key_package()
+            .unwrap()
+            .to_reference(&cs)
+            .await
+            .unwrap();
+
+        let commit = alice
+            .commit_builder()
+            .add_member(key_package)
+            .unwrap()
+            .build()
+            .await
+            .unwrap();
+
+        let welcome = &commit.welcome_messages[0];
+
+        let secret = bob
+            .debug_welcome_secret(welcome, &key_package_ref)
+            .await
+            .unwrap();
+
+        // The secret derived by the joiner decrypts the group info encrypted
+        // by the committer.
+        let MlsMessagePayload::Welcome(encrypted) = &welcome.payload else {
+            panic!("expected welcome message");
+        };
+
+        let group_info = WelcomeSecret::new(&cs, &secret)
+            .await
+            .unwrap()
+            .decrypt(&encrypted.encrypted_group_info)
+            .await
+            .unwrap();
+
+        let group_info = GroupInfo::mls_decode(&mut &**group_info).unwrap();
+
+        let examined = bob.examine_welcome_message(welcome).await.unwrap();
+        assert_eq!(group_info, examined);
+
+        let res = bob
+            .debug_welcome_secret(welcome, &KeyPackageRef::from(vec![0; 16]))
+            .await;
+
+        assert_matches!(res, Err(MlsError::WelcomeKeyPackageNotFound));
+    }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn group_can_be_created_with_id() {
+        let client = TestClientBuilder::new_for_test()
+            .with_random_signing_identity("alice", TEST_CIPHER_SUITE)
+            .await
+            .build();
+
+        let group = client
+            .create_group_with_id(b"group id".to_vec(), ExtensionList::new())
+            .await
+            .unwrap();
+
+        assert_eq!(group.group_id(), b"group id");
+
+        #[cfg(feature = "external_client")]
+        {
+            use crate::external_client::tests_utils::TestExternalClientBuilder;
+
+            let group_info = group.group_info_message(true).await.unwrap();
+
+            let observed = TestExternalClientBuilder::new_for_test()
+                .build()
+                .observe_group(group_info, None)
+                .await
+                .unwrap();
+
+            assert_eq!(observed.group_context().group_id, b"group id");
+        }
+    }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn group_id_over_max_length_is_rejected() {
+        let client = TestClientBuilder::new_for_test()
+            .with_random_signing_identity("alice", TEST_CIPHER_SUITE)
+            .await
+            .build();
+
+        client
+            .create_group_with_id(vec![0u8; MAX_GROUP_ID_LEN], ExtensionList::new())
+            .await
+            .unwrap();
+
+        let res = client
+            .create_group_with_id(vec![0u8; MAX_GROUP_ID_LEN + 1], ExtensionList::new())
+            .await
+            .map(|_| ());
+
+        assert_matches!(res, Err(MlsError::GroupIdTooLong(len)) if len == MAX_GROUP_ID_LEN + 1);
+    }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn group_can_be_created_with_initial_extensions() {
+        use crate::extension::{built_in::RequiredCapabilitiesExt, MlsExtension};
+
+        const EXTENSION_TYPE: ExtensionType = ExtensionType::new(33);
+
+        let required_capabilities = RequiredCapabilitiesExt {
+            extensions: vec![EXTENSION_TYPE],
+            ..Default::default()
+        };
+
+        let extensions: ExtensionList =
+            vec![required_capabilities.clone().into_extension().unwrap()].into();
+
+        let client = TestClientBuilder::new_for_test()
+            .with_random_signing_identity("alice", TEST_CIPHER_SUITE)
+            .await
+            .extension_type(EXTENSION_TYPE)
+            .build();
+
+        let group = client.create_group(extensions.clone()).await.unwrap();
+
+        assert_eq!(group.context().extensions, extensions);
+
+        assert_eq!(
+            group
+                .context()
+                .extensions
+                .get_as::<RequiredCapabilitiesExt>()
+                .unwrap(),
+            Some(required_capabilities)
+        );
+
+        // The creator must support the required extension
+        let client = TestClientBuilder::new_for_test()
+            .with_random_signing_identity("bob", TEST_CIPHER_SUITE)
+            .await
+            .build();
+
+        let res = client.create_group(extensions).await.map(|_| ());
+
+        assert_matches!(res, Err(MlsError::RequiredExtensionNotFound(ext)) if ext == EXTENSION_TYPE);
+    }
+
+    #[cfg(all(feature = "wasm", target_arch = "wasm32"))]
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    async fn default_wasm_client(name: &[u8]) -> Client<impl crate::client_builder::MlsConfig> {
+        use crate::{crypto::RustCryptoProvider, identity::basic::BasicIdentityProvider};
+        use mls_rs_core::crypto::CipherSuiteProvider;
+
+        let cipher_suite = CipherSuite::CURVE25519_AES128;
+
+        let (secret_key, public_key) = RustCryptoProvider::new()
+            .cipher_suite_provider(cipher_suite)
+            .unwrap()
+            .signature_key_generate()
+            .await
+            .unwrap();
+
+        let identity = SigningIdentity::new(get_test_basic_credential(name.to_vec()), public_key);
+
+        Client::builder()
+            .identity_provider(BasicIdentityProvider::new())
+            .signing_identity(identity, secret_key, cipher_suite)
+            .build()
+    }
+
+    #[cfg(all(feature = "wasm", target_arch = "wasm32"))]
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn group_can_be_created_with_default_wasm_crypto() {
+        let alice = default_wasm_client(b"alice").await;
+        let bob = default_wasm_client(b"bob").await;
+
+        let mut alice_group = alice.create_group(ExtensionList::new()).await.unwrap();
+        let key_package = bob.generate_key_package_message().await.unwrap();
+
+        let commit = alice_group
+            .commit_builder()
+            .add_member(key_package)
+            .unwrap()
+            .build()
+            .await
+            .unwrap();
+
+        alice_group.apply_pending_commit().await.unwrap();
+
+        let (bob_group, _) = bob
+            .join_group(None, &commit.welcome_messages[0])
+            .await
+            .unwrap();
+
+        assert_eq!(alice_group.context(), bob_group.context());
+    }
 }
@@ -65,6 +65,14 @@ pub type BaseInMemoryConfig = Config<
 
 pub type EmptyConfig = Config<Missing, Missing, Missing, Missing, Missing, Missing>;
 
+/// Client configuration type returned by [`Client::builder`].
+///
+/// When the `wasm` feature is enabled and the target is `wasm32`, the
+/// [`RustCryptoProvider`](crate::crypto::RustCryptoProvider) is selected as
+/// crypto provider. Otherwise this is [`BaseConfig`] and a crypto provider
+/// must be set with [`ClientBuilder::crypto_provider`].
+pub type DefaultConfig = private::DefaultConfig;
+
 /// Base client configuration that is backed by SQLite storage.
 #[cfg(feature = "sqlite")]
 pub type BaseSqlConfig = Config<
@@ -354,6 +362,97 @@ impl<C: IntoConfig> ClientBuilder<C> {
         ClientBuilder(c)
     }
 
+    /// Reject application messages that were already received in the current
+    /// epoch with [`MlsError::DuplicateMessage`](crate::client::MlsError::DuplicateMessage).
+    ///
+    /// The sender and generation of up to `capacity` received application
+    /// messages are remembered in each epoch. This is disabled by default,
+    /// in which case processing a message twice fails because its key was
+    /// already consumed.
+    pub fn message_dedup_capacity(self, capacity: usize) -> ClientBuilder<IntoConfigOutput<C>> {
+        let mut c = self.0.into_config();
+        c.0.settings.message_dedup_capacity = Some(capacity);
+        ClientBuilder(c)
+    }
+
+    /// Buffer up to `capacity` commits received for a future epoch instead of
+    /// rejecting them with
+    /// [`MlsError::FutureEpoch`](crate::client::MlsError::FutureEpoch).
+    ///
+    /// Only commits at most `capacity` epochs ahead of the group are buffered,
+    /// and the same commit is buffered once. A buffered commit is reported as
+    /// [`ReceivedMessage::BufferedCommit`](crate::group::ReceivedMessage::BufferedCommit)
+    /// and applied automatically once the group reaches its epoch, see
+    /// [`Group::take_applied_buffered_commits`](crate::group::Group::take_applied_buffered_commits).
+    /// When the buffer is full, the commit for the farthest epoch is evicted, or
+    /// buffering fails with
+    /// [`MlsError::CommitBufferFull`](crate::client::MlsError::CommitBufferFull)
+    /// if that is the new commit. The buffer is not persisted with the group
+    /// state. This is disabled by default.
+    pub fn future_commit_buffer_size(self, capacity: usize) -> ClientBuilder<IntoConfigOutput<C>> {
+        let mut c = self.0.into_config();
+        c.0.settings.future_commit_buffer_size = Some(capacity);
+        ClientBuilder(c)
+    }
+
+    /// Cache at most `max` proposals received by reference in each epoch.
+    ///
+    /// Received proposals are cached until they are committed. Once `max`
+    /// proposals are cached, processing another proposal fails with
+    /// [`MlsError::TooManyPendingProposals`](crate::client::MlsError::TooManyPendingProposals)
+    /// and the proposal is dropped. The number is not limited by default.
+    pub fn max_pending_proposals(self, max: usize) -> ClientBuilder<IntoConfigOutput<C>> {
+        let mut c = self.0.into_config();
+        c.0.settings.max_pending_proposals = Some(max);
+        ClientBuilder(c)
+    }
+
+    /// Accept ratchet trees of at most `max_nodes` nodes when joining a group.
+    ///
+    /// The tree is provided in the
+    /// [`RatchetTreeExt`](crate::extension::built_in::RatchetTreeExt)
+    /// of a GroupInfo or passed as an
+    /// [`ExportedTree`](crate::group::ExportedTree). A larger tree is rejected
+    /// with
+    /// [`MlsError::RatchetTreeTooLarge`](crate::client::MlsError::RatchetTreeTooLarge)
+    /// before it is validated. The size is only limited by the range of node
+    /// indices by default.
+    pub fn max_ratchet_tree_size(self, max_nodes: usize) -> ClientBuilder<IntoConfigOutput<C>> {
+        let mut c = self.0.into_config();
+        c.0.settings.max_ratchet_tree_size = Some(max_nodes);
+        ClientBuilder(c)
+    }
+
+    /// Require external commits received by this client to include a
+    /// pre-shared key proposal.
+    ///
+    /// This gates external joins by a PSK shared with the joiner, which adds
+    /// it with
+    /// [`ExternalCommitBuilder::with_external_psk`](crate::group::external_commit::ExternalCommitBuilder::with_external_psk).
+    /// Processing an external commit without a PSK fails with
+    /// [`MlsError::ExternalCommitMissingPsk`](crate::client::MlsError::ExternalCommitMissingPsk).
+    /// As for any PSK proposal, the PSK must be found in the
+    /// [`PreSharedKeyStorage`] of the members.
+    #[cfg(feature = "psk")]
+    pub fn require_external_commit_psk(self) -> ClientBuilder<IntoConfigOutput<C>> {
+        let mut c = self.0.into_config();
+        c.0.settings.external_commit_psk_required = true;
+        ClientBuilder(c)
+    }
+
+    /// Bound the number of message keys kept for out of order decryption.
+    ///
+    /// The secret tree derives the keys of each sender on demand. Keys
+    /// skipped over while receiving messages out of order are kept so that
+    /// the late messages can still be decrypted. This option keeps at most
+    /// `cache_size` such keys per sender and key type, evicting the oldest
+    /// generations first. It has no effect without the `out_of_order` feature.
+    pub fn secret_tree_cache_size(self, cache_size: usize) -> ClientBuilder<IntoConfigOutput<C>> {
+        let mut c = self.0.into_config();
+        c.0.settings.secret_tree_cache_size = Some(cache_size);
+        ClientBuilder(c)
+    }
+
     /// Set the key package repository to be used by the client.
     ///
     /// By default, an in-memory repository is used.
@@ -501,6 +600,21 @@ impl<C: IntoConfig> ClientBuilder<C> {
         }))
     }
 
+    /// Refuse to join groups using a protocol version older than `version`
+    /// with [`MlsError::ProtocolVersionTooOld`](crate::client::MlsError::ProtocolVersionTooOld).
+    ///
+    /// This protects against being tricked into joining a group with a
+    /// downgraded protocol version. By default, any supported version is
+    /// accepted.
+    pub fn min_protocol_version(
+        self,
+        version: ProtocolVersion,
+    ) -> ClientBuilder<IntoConfigOutput<C>> {
+        let mut c = self.0.into_config();
+        c.0.settings.min_protocol_version = Some(version);
+        ClientBuilder(c)
+    }
+
     /// Set the protocol version used by the client. By default, the client uses version MLS 1.0
     pub fn used_protocol_version(
         self,
@@ -763,6 +877,34 @@ where
     fn supported_custom_proposals(&self) -> Vec<crate::group::proposal::ProposalType> {
         self.settings.custom_proposal_types.clone()
     }
+
+    fn min_protocol_version(&self) -> Option<ProtocolVersion> {
+        self.settings.min_protocol_version
+    }
+
+    fn message_dedup_capacity(&self) -> Option<usize> {
+        self.settings.message_dedup_capacity
+    }
+
+    fn future_commit_buffer_size(&self) -> Option<usize> {
+        self.settings.future_commit_buffer_size
+    }
+
+    fn max_pending_proposals(&self) -> Option<usize> {
+        self.settings.max_pending_proposals
+    }
+
+    fn max_ratchet_tree_size(&self) -> Option<usize> {
+        self.settings.max_ratchet_tree_size
+    }
+
+    fn external_commit_psk_required(&self) -> bool {
+        self.settings.external_commit_psk_required
+    }
+
+    fn secret_tree_cache_size(&self) -> Option<usize> {
+        self.settings.secret_tree_cache_size
+    }
 }
 
 impl<Kpr, Ps, Gss, Ip, Pr, Cp> Sealed for Config<Kpr, Ps, Gss, Ip, Pr, Cp> {}
@@ -852,6 +994,34 @@ impl<T: MlsConfig> ClientConfig for T {
         self.get().lifetime()
     }
 
+    fn min_protocol_version(&self) -> Option<ProtocolVersion> {
+        self.get().min_protocol_version()
+    }
+
+    fn message_dedup_capacity(&self) -> Option<usize> {
+        self.get().message_dedup_capacity()
+    }
+
+    fn future_commit_buffer_size(&self) -> Option<usize> {
+        self.get().future_commit_buffer_size()
+    }
+
+    fn max_pending_proposals(&self) -> Option<usize> {
+        self.get().max_pending_proposals()
+    }
+
+    fn max_ratchet_tree_size(&self) -> Option<usize> {
+        self.get().max_ratchet_tree_size()
+    }
+
+    fn external_commit_psk_required(&self) -> bool {
+        self.get().external_commit_psk_required()
+    }
+
+    fn secret_tree_cache_size(&self) -> Option<usize> {
+        self.get().secret_tree_cache_size()
+    }
+
     fn capabilities(&self) -> Capabilities {
         self.get().capabilities()
     }
@@ -873,6 +1043,13 @@ pub(crate) struct Settings {
     pub(crate) key_package_extensions: ExtensionList,
     pub(crate) leaf_node_extensions: ExtensionList,
     pub(crate) lifetime_in_s: u64,
+    pub(crate) min_protocol_version: Option<ProtocolVersion>,
+    pub(crate) message_dedup_capacity: Option<usize>,
+    pub(crate) future_commit_buffer_size: Option<usize>,
+    pub(crate) max_pending_proposals: Option<usize>,
+    pub(crate) max_ratchet_tree_size: Option<usize>,
+    pub(crate) external_commit_psk_required: bool,
+    pub(crate) secret_tree_cache_size: Option<usize>,
     #[cfg(any(test, feature = "test_util"))]
     pub(crate) key_package_not_before: Option<u64>,
 }
@@ -885,7 +1062,14 @@ impl Default for Settings {
             key_package_extensions: Default::default(),
             leaf_node_extensions: Default::default(),
             lifetime_in_s: 365 * 24 * 3600,
+            min_protocol_version: None,
             custom_proposal_types: Default::default(),
+            message_dedup_capacity: None,
+            future_commit_buffer_size: None,
+            max_pending_proposals: None,
+            max_ratchet_tree_size: None,
+            external_commit_psk_required: false,
+            secret_tree_cache_size: None,
             #[cfg(any(test, feature = "test_util"))]
             key_package_not_before: None,
         }
@@ -909,6 +1093,13 @@ pub(crate) fn recreate_config<T: ClientConfig>(
                 let l = c.lifetime();
                 l.not_after - l.not_before
             },
+            min_protocol_version: c.min_protocol_version(),
+            message_dedup_capacity: c.message_dedup_capacity(),
+            future_commit_buffer_size: c.future_commit_buffer_size(),
+            max_pending_proposals: c.max_pending_proposals(),
+            max_ratchet_tree_size: c.max_ratchet_tree_size(),
+            external_commit_psk_required: c.external_commit_psk_required(),
+            secret_tree_cache_size: c.secret_tree_cache_size(),
             #[cfg(any(test, feature = "test_util"))]
             key_package_not_before: None,
         },
@@ -975,6 +1166,13 @@ mod private {
             self
         }
     }
+
+    #[cfg(not(all(feature = "wasm", target_arch = "wasm32")))]
+    pub type DefaultConfig = super::BaseConfig;
+
+    #[cfg(all(feature = "wasm", target_arch = "wasm32"))]
+    pub type DefaultConfig =
+        super::WithCryptoProvider<crate::crypto::RustCryptoProvider, super::BaseConfig>;
 }
 
 use mls_rs_core::{
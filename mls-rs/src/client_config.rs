@@ -16,6 +16,20 @@ use mls_rs_core::{
     key_package::KeyPackageStorage, psk::PreSharedKeyStorage,
 };
 
+/// Extension, proposal and credential types supported by a client.
+///
+/// See [`Client::registries`](crate::Client::registries).
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Registries {
+    /// Extension types supported in addition to the default ones.
+    pub extensions: Vec<ExtensionType>,
+    /// Custom proposal types supported in addition to the default ones.
+    pub proposals: Vec<ProposalType>,
+    /// Credential types supported by the identity provider.
+    pub credentials: Vec<CredentialType>,
+}
+
 pub trait ClientConfig: Send + Sync + Clone {
     type KeyPackageRepository: KeyPackageStorage + Clone;
     type PskStore: PreSharedKeyStorage + Clone;
@@ -41,6 +55,48 @@ pub trait ClientConfig: Send + Sync + Clone {
     fn leaf_node_extensions(&self) -> ExtensionList;
     fn lifetime(&self) -> Lifetime;
 
+    /// Lowest protocol version of groups that the client accepts to join, or
+    /// `None` to accept any supported version.
+    fn min_protocol_version(&self) -> Option<ProtocolVersion> {
+        None
+    }
+
+    /// Number of received application messages remembered per epoch in order
+    /// to detect duplicates, or `None` if duplicates are not tracked.
+    fn message_dedup_capacity(&self) -> Option<usize> {
+        None
+    }
+
+    /// Maximum number of commits for future epochs held until the group
+    /// reaches their epoch, or `None` if such commits are rejected.
+    fn future_commit_buffer_size(&self) -> Option<usize> {
+        None
+    }
+
+    /// Maximum number of received proposals cached until the next commit, or
+    /// `None` if the number is not limited.
+    fn max_pending_proposals(&self) -> Option<usize> {
+        None
+    }
+
+    /// Maximum number of nodes of a ratchet tree accepted when joining a
+    /// group, or `None` if only the range of node indices limits it.
+    fn max_ratchet_tree_size(&self) -> Option<usize> {
+        None
+    }
+
+    /// Whether external commits must include a pre-shared key proposal.
+    fn external_commit_psk_required(&self) -> bool {
+        false
+    }
+
+    /// Maximum number of skipped message keys kept for out of order
+    /// decryption, per sender and key type, or `None` to only apply the
+    /// default bound on how far a sender may skip ahead.
+    fn secret_tree_cache_size(&self) -> Option<usize> {
+        None
+    }
+
     fn capabilities(&self) -> Capabilities {
         Capabilities {
             protocol_versions: self.supported_protocol_versions(),
@@ -59,6 +115,14 @@ pub trait ClientConfig: Send + Sync + Clone {
         self.identity_provider().supported_types()
     }
 
+    fn registries(&self) -> Registries {
+        Registries {
+            extensions: self.supported_extensions(),
+            proposals: self.supported_custom_proposals(),
+            credentials: self.supported_credential_types(),
+        }
+    }
+
     fn leaf_properties(&self) -> ConfigProperties {
         ConfigProperties {
             capabilities: self.capabilities(),
@@ -11,6 +11,15 @@ pub use mls_rs_core::crypto::{
 
 pub use mls_rs_core::secret::Secret;
 
+mod custom;
+mod dyn_provider;
+
+pub use custom::{CustomCipherSuite, CustomCipherSuiteProvider, CustomCryptoProvider};
+pub use dyn_provider::DynCryptoProvider;
+
+#[cfg(all(feature = "wasm", target_arch = "wasm32"))]
+pub use mls_rs_crypto_rustcrypto::RustCryptoProvider;
+
 #[cfg(test)]
 pub(crate) mod test_utils {
     use cfg_if::cfg_if;
@@ -40,4 +49,387 @@ pub(crate) mod test_utils {
     ) -> Option<<TestCryptoProvider as CryptoProvider>::CipherSuiteProvider> {
         TestCryptoProvider::new().cipher_suite_provider(CipherSuite::from(cipher_suite))
     }
+
+    pub use hooked::{CipherSuiteHooks, HookedCryptoProvider};
+    pub use remote_signer::RemoteSignerCryptoProvider;
+
+    /// Wrapper of the test crypto provider letting tests override a few of
+    /// its operations without implementing [`CipherSuiteProvider`] again.
+    mod hooked {
+        use alloc::vec::Vec;
+        use mls_rs_core::crypto::{
+            CipherSuite, CipherSuiteProvider, CryptoProvider, HpkeCiphertext, HpkePublicKey,
+            HpkeSecretKey, SignaturePublicKey, SignatureSecretKey,
+        };
+        use zeroize::Zeroizing;
+
+        use crate::client::MlsError;
+
+        use super::TestCryptoProvider;
+
+        type TestCipherSuiteProvider = <TestCryptoProvider as CryptoProvider>::CipherSuiteProvider;
+
+        /// Operations of a [`HookedCryptoProvider`] that differ from the test
+        /// crypto provider. The defaults leave the test provider unchanged.
+        pub trait CipherSuiteHooks: Clone + Send + Sync {
+            fn kdf_extract_size(&self, size: usize) -> usize {
+                size
+            }
+
+            /// Turn a newly generated signature secret key into the value
+            /// handed out to the group.
+            fn store_signature_key(&self, secret_key: SignatureSecretKey) -> SignatureSecretKey {
+                secret_key
+            }
+
+            /// Recover a signature secret key from the value handed out by
+            /// [`Self::store_signature_key`].
+            fn load_signature_key(
+                &self,
+                secret_key: &SignatureSecretKey,
+            ) -> Result<SignatureSecretKey, MlsError> {
+                Ok(secret_key.clone())
+            }
+
+            /// Same as [`Self::load_signature_key`], called before signing.
+            fn signing_key(
+                &self,
+                secret_key: &SignatureSecretKey,
+            ) -> Result<SignatureSecretKey, MlsError> {
+                self.load_signature_key(secret_key)
+            }
+        }
+
+        #[derive(Clone, Debug, Default)]
+        pub struct HookedCryptoProvider<H> {
+            hooks: H,
+        }
+
+        impl<H: CipherSuiteHooks + Default> HookedCryptoProvider<H> {
+            pub fn new() -> Self {
+                Default::default()
+            }
+        }
+
+        impl<H> HookedCryptoProvider<H> {
+            pub fn hooks(&self) -> &H {
+                &self.hooks
+            }
+        }
+
+        impl<H: CipherSuiteHooks> CryptoProvider for HookedCryptoProvider<H> {
+            type CipherSuiteProvider = HookedCipherSuiteProvider<H>;
+
+            fn supported_cipher_suites(&self) -> Vec<CipherSuite> {
+                TestCryptoProvider::new().supported_cipher_suites()
+            }
+
+            fn cipher_suite_provider(
+                &self,
+                cipher_suite: CipherSuite,
+            ) -> Option<Self::CipherSuiteProvider> {
+                Some(HookedCipherSuiteProvider {
+                    inner: TestCryptoProvider::new().cipher_suite_provider(cipher_suite)?,
+                    hooks: self.hooks.clone(),
+                })
+            }
+        }
+
+        #[derive(Clone)]
+        pub struct HookedCipherSuiteProvider<H> {
+            inner: TestCipherSuiteProvider,
+            hooks: H,
+        }
+
+        fn crypto_error<E: mls_rs_core::error::IntoAnyError>(e: E) -> MlsError {
+            MlsError::CryptoProviderError(e.into_any_error())
+        }
+
+        #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+        #[cfg_attr(all(target_arch = "wasm32", mls_build_async), maybe_async::must_be_async(?Send))]
+        #[cfg_attr(
+            all(not(target_arch = "wasm32"), mls_build_async),
+            maybe_async::must_be_async
+        )]
+        impl<H: CipherSuiteHooks> CipherSuiteProvider for HookedCipherSuiteProvider<H> {
+            type Error = MlsError;
+            type HpkeContextS = <TestCipherSuiteProvider as CipherSuiteProvider>::HpkeContextS;
+            type HpkeContextR = <TestCipherSuiteProvider as CipherSuiteProvider>::HpkeContextR;
+
+            fn cipher_suite(&self) -> CipherSuite {
+                self.inner.cipher_suite()
+            }
+
+            async fn hash(&self, data: &[u8]) -> Result<Vec<u8>, Self::Error> {
+                self.inner.hash(data).await.map_err(crypto_error)
+            }
+
+            async fn mac(&self, key: &[u8], data: &[u8]) -> Result<Vec<u8>, Self::Error> {
+                self.inner.mac(key, data).await.map_err(crypto_error)
+            }
+
+            async fn aead_seal(
+                &self,
+                key: &[u8],
+                data: &[u8],
+                aad: Option<&[u8]>,
+                nonce: &[u8],
+            ) -> Result<Vec<u8>, Self::Error> {
+                self.inner
+                    .aead_seal(key, data, aad, nonce)
+                    .await
+                    .map_err(crypto_error)
+            }
+
+            async fn aead_open(
+                &self,
+                key: &[u8],
+                ciphertext: &[u8],
+                aad: Option<&[u8]>,
+                nonce: &[u8],
+            ) -> Result<Zeroizing<Vec<u8>>, Self::Error> {
+                self.inner
+                    .aead_open(key, ciphertext, aad, nonce)
+                    .await
+                    .map_err(crypto_error)
+            }
+
+            fn aead_key_size(&self) -> usize {
+                self.inner.aead_key_size()
+            }
+
+            fn aead_nonce_size(&self) -> usize {
+                self.inner.aead_nonce_size()
+            }
+
+            async fn kdf_extract(
+                &self,
+                salt: &[u8],
+                ikm: &[u8],
+            ) -> Result<Zeroizing<Vec<u8>>, Self::Error> {
+                self.inner
+                    .kdf_extract(salt, ikm)
+                    .await
+                    .map_err(crypto_error)
+            }
+
+            async fn kdf_expand(
+                &self,
+                prk: &[u8],
+                info: &[u8],
+                len: usize,
+            ) -> Result<Zeroizing<Vec<u8>>, Self::Error> {
+                self.inner
+                    .kdf_expand(prk, info, len)
+                    .await
+                    .map_err(crypto_error)
+            }
+
+            async fn kdf_expand_batch(
+                &self,
+                prk: &[u8],
+                infos: &[&[u8]],
+                len: usize,
+            ) -> Result<Vec<Zeroizing<Vec<u8>>>, Self::Error> {
+                self.inner
+                    .kdf_expand_batch(prk, infos, len)
+                    .await
+                    .map_err(crypto_error)
+            }
+
+            fn kdf_extract_size(&self) -> usize {
+                self.hooks.kdf_extract_size(self.inner.kdf_extract_size())
+            }
+
+            async fn hpke_seal(
+                &self,
+                remote_key: &HpkePublicKey,
+                info: &[u8],
+                aad: Option<&[u8]>,
+                pt: &[u8],
+            ) -> Result<HpkeCiphertext, Self::Error> {
+                self.inner
+                    .hpke_seal(remote_key, info, aad, pt)
+                    .await
+                    .map_err(crypto_error)
+            }
+
+            async fn hpke_open(
+                &self,
+                ciphertext: &HpkeCiphertext,
+                local_secret: &HpkeSecretKey,
+                local_public: &HpkePublicKey,
+                info: &[u8],
+                aad: Option<&[u8]>,
+            ) -> Result<Vec<u8>, Self::Error> {
+                self.inner
+                    .hpke_open(ciphertext, local_secret, local_public, info, aad)
+                    .await
+                    .map_err(crypto_error)
+            }
+
+            async fn hpke_setup_s(
+                &self,
+                remote_key: &HpkePublicKey,
+                info: &[u8],
+            ) -> Result<(Vec<u8>, Self::HpkeContextS), Self::Error> {
+                self.inner
+                    .hpke_setup_s(remote_key, info)
+                    .await
+                    .map_err(crypto_error)
+            }
+
+            async fn hpke_setup_r(
+                &self,
+                kem_output: &[u8],
+                local_secret: &HpkeSecretKey,
+                local_public: &HpkePublicKey,
+                info: &[u8],
+            ) -> Result<Self::HpkeContextR, Self::Error> {
+                self.inner
+                    .hpke_setup_r(kem_output, local_secret, local_public, info)
+                    .await
+                    .map_err(crypto_error)
+            }
+
+            async fn kem_derive(
+                &self,
+                ikm: &[u8],
+            ) -> Result<(HpkeSecretKey, HpkePublicKey), Self::Error> {
+                self.inner.kem_derive(ikm).await.map_err(crypto_error)
+            }
+
+            async fn kem_generate(&self) -> Result<(HpkeSecretKey, HpkePublicKey), Self::Error> {
+                self.inner.kem_generate().await.map_err(crypto_error)
+            }
+
+            fn kem_public_key_validate(&self, key: &HpkePublicKey) -> Result<(), Self::Error> {
+                self.inner
+                    .kem_public_key_validate(key)
+                    .map_err(crypto_error)
+            }
+
+            fn random_bytes(&self, out: &mut [u8]) -> Result<(), Self::Error> {
+                self.inner.random_bytes(out).map_err(crypto_error)
+            }
+
+            async fn signature_key_generate(
+                &self,
+            ) -> Result<(SignatureSecretKey, SignaturePublicKey), Self::Error> {
+                let (secret_key, public_key) = self
+                    .inner
+                    .signature_key_generate()
+                    .await
+                    .map_err(crypto_error)?;
+
+                Ok((self.hooks.store_signature_key(secret_key), public_key))
+            }
+
+            async fn signature_key_derive_public(
+                &self,
+                secret_key: &SignatureSecretKey,
+            ) -> Result<SignaturePublicKey, Self::Error> {
+                let secret_key = self.hooks.load_signature_key(secret_key)?;
+
+                self.inner
+                    .signature_key_derive_public(&secret_key)
+                    .await
+                    .map_err(crypto_error)
+            }
+
+            async fn sign(
+                &self,
+                secret_key: &SignatureSecretKey,
+                data: &[u8],
+            ) -> Result<Vec<u8>, Self::Error> {
+                let secret_key = self.hooks.signing_key(secret_key)?;
+
+                self.inner
+                    .sign(&secret_key, data)
+                    .await
+                    .map_err(crypto_error)
+            }
+
+            async fn verify(
+                &self,
+                public_key: &SignaturePublicKey,
+                signature: &[u8],
+                data: &[u8],
+            ) -> Result<(), Self::Error> {
+                self.inner
+                    .verify(public_key, signature, data)
+                    .await
+                    .map_err(crypto_error)
+            }
+        }
+    }
+
+    /// Mock of a crypto provider whose signature keys live in a remote key
+    /// service (HSM, TPM...). The group only holds opaque key handles and every
+    /// signature operation is a request to the service.
+    mod remote_signer {
+        use alloc::sync::Arc;
+        use alloc::vec::Vec;
+        use mls_rs_core::crypto::SignatureSecretKey;
+        use spin::Mutex;
+
+        use crate::client::MlsError;
+
+        use super::{CipherSuiteHooks, HookedCryptoProvider};
+
+        pub type RemoteSignerCryptoProvider = HookedCryptoProvider<KeyService>;
+
+        #[derive(Default)]
+        struct KeyServiceState {
+            keys: Vec<SignatureSecretKey>,
+            sign_requests: usize,
+        }
+
+        #[derive(Clone, Default)]
+        pub struct KeyService(Arc<Mutex<KeyServiceState>>);
+
+        impl CipherSuiteHooks for KeyService {
+            /// Keep the generated key in the key service and return its handle.
+            fn store_signature_key(&self, secret_key: SignatureSecretKey) -> SignatureSecretKey {
+                let mut state = self.0.lock();
+                let handle = (state.keys.len() as u32).to_be_bytes().to_vec();
+                state.keys.push(secret_key);
+
+                handle.into()
+            }
+
+            fn load_signature_key(
+                &self,
+                handle: &SignatureSecretKey,
+            ) -> Result<SignatureSecretKey, MlsError> {
+                let index = u32::from_be_bytes(
+                    handle
+                        .as_bytes()
+                        .try_into()
+                        .map_err(|_| MlsError::InvalidSignature)?,
+                );
+
+                self.0
+                    .lock()
+                    .keys
+                    .get(index as usize)
+                    .cloned()
+                    .ok_or(MlsError::InvalidSignature)
+            }
+
+            fn signing_key(
+                &self,
+                handle: &SignatureSecretKey,
+            ) -> Result<SignatureSecretKey, MlsError> {
+                self.0.lock().sign_requests += 1;
+                self.load_signature_key(handle)
+            }
+        }
+
+        impl RemoteSignerCryptoProvider {
+            pub fn sign_requests(&self) -> usize {
+                self.hooks().0.lock().sign_requests
+            }
+        }
+    }
 }
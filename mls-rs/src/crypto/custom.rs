@@ -0,0 +1,511 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// Copyright by contributors to this project.
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+use alloc::vec::Vec;
+use mls_rs_core::crypto::{
+    CipherSuite, CipherSuiteProvider, CryptoProvider, HpkeCiphertext, HpkePublicKey, HpkeSecretKey,
+    SignaturePublicKey, SignatureSecretKey,
+};
+use zeroize::Zeroizing;
+
+use crate::client::MlsError;
+
+/// Description of a custom cipher suite built out of the components of
+/// cipher suites supported by an existing [`CryptoProvider`].
+///
+/// Each component is selected by the code point of a cipher suite supported
+/// by the inner provider of [`CustomCryptoProvider`]. The inner provider may
+/// expose experimental primitives (such as a post-quantum or hybrid KEM) under
+/// private use code points that are then combined here.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CustomCipherSuite {
+    /// Code point of the custom cipher suite.
+    pub cipher_suite: CipherSuite,
+    /// Cipher suite providing the KEM and HPKE operations.
+    pub kem: CipherSuite,
+    /// Cipher suite providing the hash, MAC and KDF operations.
+    pub kdf: CipherSuite,
+    /// Cipher suite providing the AEAD operations.
+    pub aead: CipherSuite,
+    /// Cipher suite providing the signature operations.
+    pub signature: CipherSuite,
+}
+
+impl CustomCipherSuite {
+    /// Create a custom cipher suite that takes all its components from
+    /// `components`.
+    pub fn new(cipher_suite: CipherSuite, components: CipherSuite) -> Self {
+        Self {
+            cipher_suite,
+            kem: components,
+            kdf: components,
+            aead: components,
+            signature: components,
+        }
+    }
+
+    /// Use the KEM and HPKE operations of `kem`.
+    pub fn with_kem(self, kem: CipherSuite) -> Self {
+        Self { kem, ..self }
+    }
+
+    /// Use the hash, MAC and KDF operations of `kdf`.
+    pub fn with_kdf(self, kdf: CipherSuite) -> Self {
+        Self { kdf, ..self }
+    }
+
+    /// Use the AEAD operations of `aead`.
+    pub fn with_aead(self, aead: CipherSuite) -> Self {
+        Self { aead, ..self }
+    }
+
+    /// Use the signature operations of `signature`.
+    pub fn with_signature(self, signature: CipherSuite) -> Self {
+        Self { signature, ..self }
+    }
+}
+
+/// [`CryptoProvider`] adding [custom cipher suites](CustomCipherSuite) on top
+/// of the cipher suites supported by an inner provider.
+///
+/// Cipher suites that are not registered as custom are forwarded to the
+/// inner provider unchanged.
+///
+/// Components are selected by code point rather than supplied as separate
+/// trait objects so that a custom suite keeps the error and HPKE context
+/// types of the inner provider. A new primitive is plugged in by implementing
+/// [`CryptoProvider`] for it under a private use code point, wrapping an
+/// existing provider for the cipher suites it does not handle.
+#[derive(Clone, Debug)]
+pub struct CustomCryptoProvider<C> {
+    inner: C,
+    custom_cipher_suites: Vec<CustomCipherSuite>,
+}
+
+impl<C: CryptoProvider> CustomCryptoProvider<C> {
+    pub fn new(inner: C) -> Self {
+        Self {
+            inner,
+            custom_cipher_suites: Vec::new(),
+        }
+    }
+
+    /// Register a custom cipher suite. A previously registered custom cipher
+    /// suite with the same code point is replaced.
+    ///
+    /// Registration fails if a component is not supported by the inner
+    /// provider, or if the KDF extract size is smaller than the AEAD key size
+    /// as AEAD keys are derived from KDF outputs by the key schedule.
+    pub fn with_custom_cipher_suite(mut self, custom: CustomCipherSuite) -> Result<Self, MlsError> {
+        let component = |cipher_suite| {
+            self.inner
+                .cipher_suite_provider(cipher_suite)
+                .ok_or(MlsError::UnsupportedCipherSuite(cipher_suite))
+        };
+
+        let kdf_extract_size = component(custom.kdf)?.kdf_extract_size();
+        let aead_key_size = component(custom.aead)?.aead_key_size();
+
+        [custom.kem, custom.signature]
+            .into_iter()
+            .try_for_each(|cs| component(cs).map(|_| ()))?;
+
+        if kdf_extract_size < aead_key_size {
+            return Err(MlsError::CustomCipherSuiteKdfAeadMismatch(
+                custom.cipher_suite,
+                kdf_extract_size,
+                aead_key_size,
+            ));
+        }
+
+        self.custom_cipher_suites
+            .retain(|c| c.cipher_suite != custom.cipher_suite);
+
+        self.custom_cipher_suites.push(custom);
+
+        Ok(self)
+    }
+
+    fn custom_cipher_suite(&self, cipher_suite: CipherSuite) -> Option<&CustomCipherSuite> {
+        self.custom_cipher_suites
+            .iter()
+            .find(|c| c.cipher_suite == cipher_suite)
+    }
+}
+
+impl<C: CryptoProvider> CryptoProvider for CustomCryptoProvider<C> {
+    type CipherSuiteProvider = CustomCipherSuiteProvider<C::CipherSuiteProvider>;
+
+    fn supported_cipher_suites(&self) -> Vec<CipherSuite> {
+        self.inner
+            .supported_cipher_suites()
+            .into_iter()
+            .filter(|cs| self.custom_cipher_suite(*cs).is_none())
+            .chain(self.custom_cipher_suites.iter().map(|c| c.cipher_suite))
+            .collect()
+    }
+
+    fn cipher_suite_provider(
+        &self,
+        cipher_suite: CipherSuite,
+    ) -> Option<Self::CipherSuiteProvider> {
+        match self.custom_cipher_suite(cipher_suite) {
+            Some(custom) => Some(CustomCipherSuiteProvider {
+                cipher_suite,
+                kem: self.inner.cipher_suite_provider(custom.kem)?,
+                kdf: self.inner.cipher_suite_provider(custom.kdf)?,
+                aead: self.inner.cipher_suite_provider(custom.aead)?,
+                signature: self.inner.cipher_suite_provider(custom.signature)?,
+            }),
+            None => self
+                .inner
+                .cipher_suite_provider(cipher_suite)
+                .map(CustomCipherSuiteProvider::from_inner),
+        }
+    }
+}
+
+/// [`CipherSuiteProvider`] dispatching each operation to the provider of the
+/// corresponding component of a [`CustomCipherSuite`].
+#[derive(Clone)]
+pub struct CustomCipherSuiteProvider<P> {
+    cipher_suite: CipherSuite,
+    kem: P,
+    kdf: P,
+    aead: P,
+    signature: P,
+}
+
+impl<P: CipherSuiteProvider + Clone> CustomCipherSuiteProvider<P> {
+    fn from_inner(inner: P) -> Self {
+        Self {
+            cipher_suite: inner.cipher_suite(),
+            kem: inner.clone(),
+            kdf: inner.clone(),
+            aead: inner.clone(),
+            signature: inner,
+        }
+    }
+}
+
+#[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+#[cfg_attr(all(target_arch = "wasm32", mls_build_async), maybe_async::must_be_async(?Send))]
+#[cfg_attr(
+    all(not(target_arch = "wasm32"), mls_build_async),
+    maybe_async::must_be_async
+)]
+impl<P: CipherSuiteProvider> CipherSuiteProvider for CustomCipherSuiteProvider<P> {
+    type Error = P::Error;
+    type HpkeContextS = P::HpkeContextS;
+    type HpkeContextR = P::HpkeContextR;
+
+    fn cipher_suite(&self) -> CipherSuite {
+        self.cipher_suite
+    }
+
+    async fn hash(&self, data: &[u8]) -> Result<Vec<u8>, Self::Error> {
+        self.kdf.hash(data).await
+    }
+
+    async fn mac(&self, key: &[u8], data: &[u8]) -> Result<Vec<u8>, Self::Error> {
+        self.kdf.mac(key, data).await
+    }
+
+    async fn aead_seal(
+        &self,
+        key: &[u8],
+        data: &[u8],
+        aad: Option<&[u8]>,
+        nonce: &[u8],
+    ) -> Result<Vec<u8>, Self::Error> {
+        self.aead.aead_seal(key, data, aad, nonce).await
+    }
+
+    async fn aead_open(
+        &self,
+        key: &[u8],
+        ciphertext: &[u8],
+        aad: Option<&[u8]>,
+        nonce: &[u8],
+    ) -> Result<Zeroizing<Vec<u8>>, Self::Error> {
+        self.aead.aead_open(key, ciphertext, aad, nonce).await
+    }
+
+    fn aead_key_size(&self) -> usize {
+        self.aead.aead_key_size()
+    }
+
+    fn aead_nonce_size(&self) -> usize {
+        self.aead.aead_nonce_size()
+    }
+
+    async fn kdf_extract(
+        &self,
+        salt: &[u8],
+        ikm: &[u8],
+    ) -> Result<Zeroizing<Vec<u8>>, Self::Error> {
+        self.kdf.kdf_extract(salt, ikm).await
+    }
+
+    async fn kdf_expand(
+        &self,
+        prk: &[u8],
+        info: &[u8],
+        len: usize,
+    ) -> Result<Zeroizing<Vec<u8>>, Self::Error> {
+        self.kdf.kdf_expand(prk, info, len).await
+    }
+
+    async fn kdf_expand_batch(
+        &self,
+        prk: &[u8],
+        infos: &[&[u8]],
+        len: usize,
+    ) -> Result<Vec<Zeroizing<Vec<u8>>>, Self::Error> {
+        self.kdf.kdf_expand_batch(prk, infos, len).await
+    }
+
+    fn kdf_extract_size(&self) -> usize {
+        self.kdf.kdf_extract_size()
+    }
+
+    async fn hpke_seal(
+        &self,
+        remote_key: &HpkePublicKey,
+        info: &[u8],
+        aad: Option<&[u8]>,
+        pt: &[u8],
+    ) -> Result<HpkeCiphertext, Self::Error> {
+        self.kem.hpke_seal(remote_key, info, aad, pt).await
+    }
+
+    async fn hpke_open(
+        &self,
+        ciphertext: &HpkeCiphertext,
+        local_secret: &HpkeSecretKey,
+        local_public: &HpkePublicKey,
+        info: &[u8],
+        aad: Option<&[u8]>,
+    ) -> Result<Vec<u8>, Self::Error> {
+        self.kem
+            .hpke_open(ciphertext, local_secret, local_public, info, aad)
+            .await
+    }
+
+    async fn hpke_setup_s(
+        &self,
+        remote_key: &HpkePublicKey,
+        info: &[u8],
+    ) -> Result<(Vec<u8>, Self::HpkeContextS), Self::Error> {
+        self.kem.hpke_setup_s(remote_key, info).await
+    }
+
+    async fn hpke_setup_r(
+        &self,
+        kem_output: &[u8],
+        local_secret: &HpkeSecretKey,
+        local_public: &HpkePublicKey,
+        info: &[u8],
+    ) -> Result<Self::HpkeContextR, Self::Error> {
+        self.kem
+            .hpke_setup_r(kem_output, local_secret, local_public, info)
+            .await
+    }
+
+    async fn kem_derive(&self, ikm: &[u8]) -> Result<(HpkeSecretKey, HpkePublicKey), Self::Error> {
+        self.kem.kem_derive(ikm).await
+    }
+
+    async fn kem_generate(&self) -> Result<(HpkeSecretKey, HpkePublicKey), Self::Error> {
+        self.kem.kem_generate().await
+    }
+
+    fn kem_public_key_validate(&self, key: &HpkePublicKey) -> Result<(), Self::Error> {
+        self.kem.kem_public_key_validate(key)
+    }
+
+    fn random_bytes(&self, out: &mut [u8]) -> Result<(), Self::Error> {
+        self.kdf.random_bytes(out)
+    }
+
+    async fn signature_key_generate(
+        &self,
+    ) -> Result<(SignatureSecretKey, SignaturePublicKey), Self::Error> {
+        self.signature.signature_key_generate().await
+    }
+
+    async fn signature_key_derive_public(
+        &self,
+        secret_key: &SignatureSecretKey,
+    ) -> Result<SignaturePublicKey, Self::Error> {
+        self.signature.signature_key_derive_public(secret_key).await
+    }
+
+    async fn sign(
+        &self,
+        secret_key: &SignatureSecretKey,
+        data: &[u8],
+    ) -> Result<Vec<u8>, Self::Error> {
+        self.signature.sign(secret_key, data).await
+    }
+
+    async fn verify(
+        &self,
+        public_key: &SignaturePublicKey,
+        signature: &[u8],
+        data: &[u8],
+    ) -> Result<(), Self::Error> {
+        self.signature.verify(public_key, signature, data).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        client::test_utils::{TEST_CIPHER_SUITE, TEST_PROTOCOL_VERSION},
+        client_builder::{ClientBuilder, MlsConfig},
+        crypto::test_utils::{CipherSuiteHooks, HookedCryptoProvider, TestCryptoProvider},
+        identity::{basic::BasicIdentityProvider, test_utils::get_test_signing_identity},
+        Client, ExtensionList,
+    };
+
+    #[cfg(feature = "private_message")]
+    use crate::group::ReceivedMessage;
+    #[cfg(feature = "private_message")]
+    use alloc::vec;
+
+    use super::*;
+    use assert_matches::assert_matches;
+
+    const CUSTOM_CIPHER_SUITE: CipherSuite = CipherSuite::new(0xF001);
+
+    fn custom_crypto_provider() -> CustomCryptoProvider<TestCryptoProvider> {
+        CustomCryptoProvider::new(TestCryptoProvider::new())
+            .with_custom_cipher_suite(CustomCipherSuite::new(
+                CUSTOM_CIPHER_SUITE,
+                TEST_CIPHER_SUITE,
+            ))
+            .unwrap()
+    }
+
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    async fn custom_client(identity: &str) -> Client<impl MlsConfig> {
+        // Signature keys of the custom suite are those of its signature component
+        let (signing_identity, secret_key) =
+            get_test_signing_identity(TEST_CIPHER_SUITE, identity.as_bytes()).await;
+
+        ClientBuilder::new()
+            .crypto_provider(custom_crypto_provider())
+            .identity_provider(BasicIdentityProvider::new())
+            .used_protocol_version(TEST_PROTOCOL_VERSION)
+            .signing_identity(signing_identity, secret_key, CUSTOM_CIPHER_SUITE)
+            .build()
+    }
+
+    #[test]
+    fn custom_cipher_suite_is_supported() {
+        let provider = custom_crypto_provider();
+
+        assert!(provider
+            .supported_cipher_suites()
+            .contains(&CUSTOM_CIPHER_SUITE));
+
+        let cs = provider.cipher_suite_provider(CUSTOM_CIPHER_SUITE).unwrap();
+        assert_eq!(cs.cipher_suite(), CUSTOM_CIPHER_SUITE);
+
+        let cs = provider.cipher_suite_provider(TEST_CIPHER_SUITE).unwrap();
+        assert_eq!(cs.cipher_suite(), TEST_CIPHER_SUITE);
+    }
+
+    #[test]
+    fn custom_cipher_suite_with_unsupported_component_is_rejected() {
+        let res = CustomCryptoProvider::new(TestCryptoProvider::new()).with_custom_cipher_suite(
+            CustomCipherSuite::new(CUSTOM_CIPHER_SUITE, TEST_CIPHER_SUITE)
+                .with_kem(CipherSuite::new(0xF002)),
+        );
+
+        assert_matches!(
+            res,
+            Err(MlsError::UnsupportedCipherSuite(cs)) if cs == CipherSuite::new(0xF002)
+        );
+    }
+
+    #[test]
+    fn custom_cipher_suite_with_kdf_aead_mismatch_is_rejected() {
+        let res = CustomCryptoProvider::new(HookedCryptoProvider::<ShortKdf>::new())
+            .with_custom_cipher_suite(CustomCipherSuite::new(
+                CUSTOM_CIPHER_SUITE,
+                TEST_CIPHER_SUITE,
+            ));
+
+        assert_matches!(
+            res,
+            Err(MlsError::CustomCipherSuiteKdfAeadMismatch(
+                CUSTOM_CIPHER_SUITE,
+                8,
+                16
+            ))
+        );
+    }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn group_can_use_custom_cipher_suite() {
+        let alice = custom_client("alice").await;
+        let bob = custom_client("bob").await;
+
+        let mut alice_group = alice.create_group(ExtensionList::new()).await.unwrap();
+        assert_eq!(alice_group.cipher_suite(), CUSTOM_CIPHER_SUITE);
+
+        let key_package = bob.generate_key_package_message().await.unwrap();
+
+        let commit = alice_group
+            .commit_builder()
+            .add_member(key_package)
+            .unwrap()
+            .build()
+            .await
+            .unwrap();
+
+        alice_group.apply_pending_commit().await.unwrap();
+
+        let (bob_group, _) = bob
+            .join_group(None, &commit.welcome_messages[0])
+            .await
+            .unwrap();
+
+        assert_eq!(bob_group.cipher_suite(), CUSTOM_CIPHER_SUITE);
+
+        assert_eq!(
+            bob_group.epoch_authenticator().unwrap(),
+            alice_group.epoch_authenticator().unwrap()
+        );
+
+        #[cfg(feature = "private_message")]
+        {
+            let mut bob_group = bob_group;
+
+            let message = alice_group
+                .encrypt_application_message(b"hello", vec![])
+                .await
+                .unwrap();
+
+            let received = bob_group.process_incoming_message(message).await.unwrap();
+
+            assert!(matches!(
+                received,
+                ReceivedMessage::ApplicationMessage(m) if m.data() == b"hello"
+            ));
+        }
+    }
+
+    /// Hooks making the KDF extract size too small for the AEAD key size.
+    #[derive(Clone, Debug, Default)]
+    struct ShortKdf;
+
+    impl CipherSuiteHooks for ShortKdf {
+        fn kdf_extract_size(&self, _size: usize) -> usize {
+            8
+        }
+    }
+}
@@ -0,0 +1,659 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// Copyright by contributors to this project.
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use mls_rs_core::{
+    crypto::{
+        CipherSuite, CipherSuiteProvider, CryptoProvider, HpkeCiphertext, HpkeContextR,
+        HpkeContextS, HpkePublicKey, HpkeSecretKey, SignaturePublicKey, SignatureSecretKey,
+    },
+    error::IntoAnyError,
+};
+use zeroize::Zeroizing;
+
+use crate::client::MlsError;
+
+/// Object safe version of [`CryptoProvider`], implemented for every
+/// [`CryptoProvider`] that can be stored in a [`DynCryptoProvider`].
+trait ErasedCryptoProvider: Send + Sync {
+    fn supported_cipher_suites(&self) -> Vec<CipherSuite>;
+    fn cipher_suite_provider(&self, cipher_suite: CipherSuite) -> Option<DynCipherSuiteProvider>;
+    fn clone_box(&self) -> Box<dyn ErasedCryptoProvider>;
+}
+
+impl<C> ErasedCryptoProvider for C
+where
+    C: CryptoProvider + Clone + 'static,
+    C::CipherSuiteProvider: 'static,
+    <C::CipherSuiteProvider as CipherSuiteProvider>::HpkeContextS: 'static,
+    <C::CipherSuiteProvider as CipherSuiteProvider>::HpkeContextR: 'static,
+{
+    fn supported_cipher_suites(&self) -> Vec<CipherSuite> {
+        CryptoProvider::supported_cipher_suites(self)
+    }
+
+    fn cipher_suite_provider(&self, cipher_suite: CipherSuite) -> Option<DynCipherSuiteProvider> {
+        CryptoProvider::cipher_suite_provider(self, cipher_suite)
+            .map(|cs| DynCipherSuiteProvider(Box::new(Erased(cs))))
+    }
+
+    fn clone_box(&self) -> Box<dyn ErasedCryptoProvider> {
+        Box::new(self.clone())
+    }
+}
+
+/// Type erased [`CryptoProvider`].
+///
+/// This allows choosing or combining crypto providers at runtime without
+/// making the client generic over their types. All errors of the wrapped
+/// provider are reported as [`MlsError::CryptoProviderError`].
+pub struct DynCryptoProvider(Box<dyn ErasedCryptoProvider>);
+
+impl DynCryptoProvider {
+    pub fn new<C>(crypto_provider: C) -> Self
+    where
+        C: CryptoProvider + Clone + 'static,
+        C::CipherSuiteProvider: 'static,
+        <C::CipherSuiteProvider as CipherSuiteProvider>::HpkeContextS: 'static,
+        <C::CipherSuiteProvider as CipherSuiteProvider>::HpkeContextR: 'static,
+    {
+        Self(Box::new(crypto_provider))
+    }
+}
+
+impl Clone for DynCryptoProvider {
+    fn clone(&self) -> Self {
+        Self(self.0.clone_box())
+    }
+}
+
+impl core::fmt::Debug for DynCryptoProvider {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("DynCryptoProvider")
+            .field("supported_cipher_suites", &self.0.supported_cipher_suites())
+            .finish()
+    }
+}
+
+impl CryptoProvider for DynCryptoProvider {
+    type CipherSuiteProvider = DynCipherSuiteProvider;
+
+    fn supported_cipher_suites(&self) -> Vec<CipherSuite> {
+        self.0.supported_cipher_suites()
+    }
+
+    fn cipher_suite_provider(
+        &self,
+        cipher_suite: CipherSuite,
+    ) -> Option<Self::CipherSuiteProvider> {
+        self.0.cipher_suite_provider(cipher_suite)
+    }
+}
+
+trait ErasedCipherSuiteProvider:
+    CipherSuiteProvider<
+    Error = MlsError,
+    HpkeContextS = DynHpkeContextS,
+    HpkeContextR = DynHpkeContextR,
+>
+{
+    fn clone_box(&self) -> Box<dyn ErasedCipherSuiteProvider>;
+}
+
+impl<P> ErasedCipherSuiteProvider for Erased<P>
+where
+    P: CipherSuiteProvider + Clone + 'static,
+    P::HpkeContextS: 'static,
+    P::HpkeContextR: 'static,
+{
+    fn clone_box(&self) -> Box<dyn ErasedCipherSuiteProvider> {
+        Box::new(self.clone())
+    }
+}
+
+// The types below appear in the `CryptoProvider` implementation of
+// `DynCryptoProvider` and therefore must be `pub`, but they are not exported
+// from the crate.
+
+/// Type erased [`CipherSuiteProvider`] returned by [`DynCryptoProvider`].
+pub struct DynCipherSuiteProvider(Box<dyn ErasedCipherSuiteProvider>);
+
+impl Clone for DynCipherSuiteProvider {
+    fn clone(&self) -> Self {
+        Self(self.0.clone_box())
+    }
+}
+
+/// Type erased [`HpkeContextS`] of a [`DynCipherSuiteProvider`].
+pub struct DynHpkeContextS(Box<dyn HpkeContextS<Error = MlsError> + Send + Sync>);
+
+/// Type erased [`HpkeContextR`] of a [`DynCipherSuiteProvider`].
+pub struct DynHpkeContextR(Box<dyn HpkeContextR<Error = MlsError> + Send + Sync>);
+
+/// Adapter mapping the errors and HPKE contexts of a provider to their type
+/// erased counterparts.
+#[derive(Clone)]
+struct Erased<T>(T);
+
+fn erase_error<E: IntoAnyError>(e: E) -> MlsError {
+    MlsError::CryptoProviderError(e.into_any_error())
+}
+
+#[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+#[cfg_attr(all(target_arch = "wasm32", mls_build_async), maybe_async::must_be_async(?Send))]
+#[cfg_attr(
+    all(not(target_arch = "wasm32"), mls_build_async),
+    maybe_async::must_be_async
+)]
+impl<T: HpkeContextS + Send + Sync> HpkeContextS for Erased<T> {
+    type Error = MlsError;
+
+    async fn seal(&mut self, aad: Option<&[u8]>, data: &[u8]) -> Result<Vec<u8>, Self::Error> {
+        self.0.seal(aad, data).await.map_err(erase_error)
+    }
+
+    async fn export(&self, exporter_context: &[u8], len: usize) -> Result<Vec<u8>, Self::Error> {
+        self.0
+            .export(exporter_context, len)
+            .await
+            .map_err(erase_error)
+    }
+}
+
+#[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+#[cfg_attr(all(target_arch = "wasm32", mls_build_async), maybe_async::must_be_async(?Send))]
+#[cfg_attr(
+    all(not(target_arch = "wasm32"), mls_build_async),
+    maybe_async::must_be_async
+)]
+impl<T: HpkeContextR + Send + Sync> HpkeContextR for Erased<T> {
+    type Error = MlsError;
+
+    async fn open(
+        &mut self,
+        aad: Option<&[u8]>,
+        ciphertext: &[u8],
+    ) -> Result<Vec<u8>, Self::Error> {
+        self.0.open(aad, ciphertext).await.map_err(erase_error)
+    }
+
+    async fn export(&self, exporter_context: &[u8], len: usize) -> Result<Vec<u8>, Self::Error> {
+        self.0
+            .export(exporter_context, len)
+            .await
+            .map_err(erase_error)
+    }
+}
+
+#[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+#[cfg_attr(all(target_arch = "wasm32", mls_build_async), maybe_async::must_be_async(?Send))]
+#[cfg_attr(
+    all(not(target_arch = "wasm32"), mls_build_async),
+    maybe_async::must_be_async
+)]
+impl HpkeContextS for DynHpkeContextS {
+    type Error = MlsError;
+
+    async fn seal(&mut self, aad: Option<&[u8]>, data: &[u8]) -> Result<Vec<u8>, Self::Error> {
+        self.0.seal(aad, data).await
+    }
+
+    async fn export(&self, exporter_context: &[u8], len: usize) -> Result<Vec<u8>, Self::Error> {
+        self.0.export(exporter_context, len).await
+    }
+}
+
+#[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+#[cfg_attr(all(target_arch = "wasm32", mls_build_async), maybe_async::must_be_async(?Send))]
+#[cfg_attr(
+    all(not(target_arch = "wasm32"), mls_build_async),
+    maybe_async::must_be_async
+)]
+impl HpkeContextR for DynHpkeContextR {
+    type Error = MlsError;
+
+    async fn open(
+        &mut self,
+        aad: Option<&[u8]>,
+        ciphertext: &[u8],
+    ) -> Result<Vec<u8>, Self::Error> {
+        self.0.open(aad, ciphertext).await
+    }
+
+    async fn export(&self, exporter_context: &[u8], len: usize) -> Result<Vec<u8>, Self::Error> {
+        self.0.export(exporter_context, len).await
+    }
+}
+
+#[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+#[cfg_attr(all(target_arch = "wasm32", mls_build_async), maybe_async::must_be_async(?Send))]
+#[cfg_attr(
+    all(not(target_arch = "wasm32"), mls_build_async),
+    maybe_async::must_be_async
+)]
+impl<P> CipherSuiteProvider for Erased<P>
+where
+    P: CipherSuiteProvider,
+    P::HpkeContextS: 'static,
+    P::HpkeContextR: 'static,
+{
+    type Error = MlsError;
+    type HpkeContextS = DynHpkeContextS;
+    type HpkeContextR = DynHpkeContextR;
+
+    fn cipher_suite(&self) -> CipherSuite {
+        self.0.cipher_suite()
+    }
+
+    async fn hash(&self, data: &[u8]) -> Result<Vec<u8>, Self::Error> {
+        self.0.hash(data).await.map_err(erase_error)
+    }
+
+    async fn mac(&self, key: &[u8], data: &[u8]) -> Result<Vec<u8>, Self::Error> {
+        self.0.mac(key, data).await.map_err(erase_error)
+    }
+
+    async fn aead_seal(
+        &self,
+        key: &[u8],
+        data: &[u8],
+        aad: Option<&[u8]>,
+        nonce: &[u8],
+    ) -> Result<Vec<u8>, Self::Error> {
+        self.0
+            .aead_seal(key, data, aad, nonce)
+            .await
+            .map_err(erase_error)
+    }
+
+    async fn aead_open(
+        &self,
+        key: &[u8],
+        ciphertext: &[u8],
+        aad: Option<&[u8]>,
+        nonce: &[u8],
+    ) -> Result<Zeroizing<Vec<u8>>, Self::Error> {
+        self.0
+            .aead_open(key, ciphertext, aad, nonce)
+            .await
+            .map_err(erase_error)
+    }
+
+    fn aead_key_size(&self) -> usize {
+        self.0.aead_key_size()
+    }
+
+    fn aead_nonce_size(&self) -> usize {
+        self.0.aead_nonce_size()
+    }
+
+    async fn kdf_extract(
+        &self,
+        salt: &[u8],
+        ikm: &[u8],
+    ) -> Result<Zeroizing<Vec<u8>>, Self::Error> {
+        self.0.kdf_extract(salt, ikm).await.map_err(erase_error)
+    }
+
+    async fn kdf_expand(
+        &self,
+        prk: &[u8],
+        info: &[u8],
+        len: usize,
+    ) -> Result<Zeroizing<Vec<u8>>, Self::Error> {
+        self.0.kdf_expand(prk, info, len).await.map_err(erase_error)
+    }
+
+    async fn kdf_expand_batch(
+        &self,
+        prk: &[u8],
+        infos: &[&[u8]],
+        len: usize,
+    ) -> Result<Vec<Zeroizing<Vec<u8>>>, Self::Error> {
+        self.0
+            .kdf_expand_batch(prk, infos, len)
+            .await
+            .map_err(erase_error)
+    }
+
+    fn kdf_extract_size(&self) -> usize {
+        self.0.kdf_extract_size()
+    }
+
+    async fn hpke_seal(
+        &self,
+        remote_key: &HpkePublicKey,
+        info: &[u8],
+        aad: Option<&[u8]>,
+        pt: &[u8],
+    ) -> Result<HpkeCiphertext, Self::Error> {
+        self.0
+            .hpke_seal(remote_key, info, aad, pt)
+            .await
+            .map_err(erase_error)
+    }
+
+    async fn hpke_open(
+        &self,
+        ciphertext: &HpkeCiphertext,
+        local_secret: &HpkeSecretKey,
+        local_public: &HpkePublicKey,
+        info: &[u8],
+        aad: Option<&[u8]>,
+    ) -> Result<Vec<u8>, Self::Error> {
+        self.0
+            .hpke_open(ciphertext, local_secret, local_public, info, aad)
+            .await
+            .map_err(erase_error)
+    }
+
+    async fn hpke_setup_s(
+        &self,
+        remote_key: &HpkePublicKey,
+        info: &[u8],
+    ) -> Result<(Vec<u8>, Self::HpkeContextS), Self::Error> {
+        let (kem_output, context) = self
+            .0
+            .hpke_setup_s(remote_key, info)
+            .await
+            .map_err(erase_error)?;
+
+        Ok((kem_output, DynHpkeContextS(Box::new(Erased(context)))))
+    }
+
+    async fn hpke_setup_r(
+        &self,
+        kem_output: &[u8],
+        local_secret: &HpkeSecretKey,
+        local_public: &HpkePublicKey,
+        info: &[u8],
+    ) -> Result<Self::HpkeContextR, Self::Error> {
+        self.0
+            .hpke_setup_r(kem_output, local_secret, local_public, info)
+            .await
+            .map(|context| DynHpkeContextR(Box::new(Erased(context))))
+            .map_err(erase_error)
+    }
+
+    async fn kem_derive(&self, ikm: &[u8]) -> Result<(HpkeSecretKey, HpkePublicKey), Self::Error> {
+        self.0.kem_derive(ikm).await.map_err(erase_error)
+    }
+
+    async fn kem_generate(&self) -> Result<(HpkeSecretKey, HpkePublicKey), Self::Error> {
+        self.0.kem_generate().await.map_err(erase_error)
+    }
+
+    fn kem_public_key_validate(&self, key: &HpkePublicKey) -> Result<(), Self::Error> {
+        self.0.kem_public_key_validate(key).map_err(erase_error)
+    }
+
+    fn random_bytes(&self, out: &mut [u8]) -> Result<(), Self::Error> {
+        self.0.random_bytes(out).map_err(erase_error)
+    }
+
+    async fn signature_key_generate(
+        &self,
+    ) -> Result<(SignatureSecretKey, SignaturePublicKey), Self::Error> {
+        self.0.signature_key_generate().await.map_err(erase_error)
+    }
+
+    async fn signature_key_derive_public(
+        &self,
+        secret_key: &SignatureSecretKey,
+    ) -> Result<SignaturePublicKey, Self::Error> {
+        self.0
+            .signature_key_derive_public(secret_key)
+            .await
+            .map_err(erase_error)
+    }
+
+    async fn sign(
+        &self,
+        secret_key: &SignatureSecretKey,
+        data: &[u8],
+    ) -> Result<Vec<u8>, Self::Error> {
+        self.0.sign(secret_key, data).await.map_err(erase_error)
+    }
+
+    async fn verify(
+        &self,
+        public_key: &SignaturePublicKey,
+        signature: &[u8],
+        data: &[u8],
+    ) -> Result<(), Self::Error> {
+        self.0
+            .verify(public_key, signature, data)
+            .await
+            .map_err(erase_error)
+    }
+}
+
+#[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+#[cfg_attr(all(target_arch = "wasm32", mls_build_async), maybe_async::must_be_async(?Send))]
+#[cfg_attr(
+    all(not(target_arch = "wasm32"), mls_build_async),
+    maybe_async::must_be_async
+)]
+impl CipherSuiteProvider for DynCipherSuiteProvider {
+    type Error = MlsError;
+    type HpkeContextS = DynHpkeContextS;
+    type HpkeContextR = DynHpkeContextR;
+
+    fn cipher_suite(&self) -> CipherSuite {
+        self.0.cipher_suite()
+    }
+
+    async fn hash(&self, data: &[u8]) -> Result<Vec<u8>, Self::Error> {
+        self.0.hash(data).await
+    }
+
+    async fn mac(&self, key: &[u8], data: &[u8]) -> Result<Vec<u8>, Self::Error> {
+        self.0.mac(key, data).await
+    }
+
+    async fn aead_seal(
+        &self,
+        key: &[u8],
+        data: &[u8],
+        aad: Option<&[u8]>,
+        nonce: &[u8],
+    ) -> Result<Vec<u8>, Self::Error> {
+        self.0.aead_seal(key, data, aad, nonce).await
+    }
+
+    async fn aead_open(
+        &self,
+        key: &[u8],
+        ciphertext: &[u8],
+        aad: Option<&[u8]>,
+        nonce: &[u8],
+    ) -> Result<Zeroizing<Vec<u8>>, Self::Error> {
+        self.0.aead_open(key, ciphertext, aad, nonce).await
+    }
+
+    fn aead_key_size(&self) -> usize {
+        self.0.aead_key_size()
+    }
+
+    fn aead_nonce_size(&self) -> usize {
+        self.0.aead_nonce_size()
+    }
+
+    async fn kdf_extract(
+        &self,
+        salt: &[u8],
+        ikm: &[u8],
+    ) -> Result<Zeroizing<Vec<u8>>, Self::Error> {
+        self.0.kdf_extract(salt, ikm).await
+    }
+
+    async fn kdf_expand(
+        &self,
+        prk: &[u8],
+        info: &[u8],
+        len: usize,
+    ) -> Result<Zeroizing<Vec<u8>>, Self::Error> {
+        self.0.kdf_expand(prk, info, len).await
+    }
+
+    async fn kdf_expand_batch(
+        &self,
+        prk: &[u8],
+        infos: &[&[u8]],
+        len: usize,
+    ) -> Result<Vec<Zeroizing<Vec<u8>>>, Self::Error> {
+        self.0.kdf_expand_batch(prk, infos, len).await
+    }
+
+    fn kdf_extract_size(&self) -> usize {
+        self.0.kdf_extract_size()
+    }
+
+    async fn hpke_seal(
+        &self,
+        remote_key: &HpkePublicKey,
+        info: &[u8],
+        aad: Option<&[u8]>,
+        pt: &[u8],
+    ) -> Result<HpkeCiphertext, Self::Error> {
+        self.0.hpke_seal(remote_key, info, aad, pt).await
+    }
+
+    async fn hpke_open(
+        &self,
+        ciphertext: &HpkeCiphertext,
+        local_secret: &HpkeSecretKey,
+        local_public: &HpkePublicKey,
+        info: &[u8],
+        aad: Option<&[u8]>,
+    ) -> Result<Vec<u8>, Self::Error> {
+        self.0
+            .hpke_open(ciphertext, local_secret, local_public, info, aad)
+            .await
+    }
+
+    async fn hpke_setup_s(
+        &self,
+        remote_key: &HpkePublicKey,
+        info: &[u8],
+    ) -> Result<(Vec<u8>, Self::HpkeContextS), Self::Error> {
+        self.0.hpke_setup_s(remote_key, info).await
+    }
+
+    async fn hpke_setup_r(
+        &self,
+        kem_output: &[u8],
+        local_secret: &HpkeSecretKey,
+        local_public: &HpkePublicKey,
+        info: &[u8],
+    ) -> Result<Self::HpkeContextR, Self::Error> {
+        self.0
+            .hpke_setup_r(kem_output, local_secret, local_public, info)
+            .await
+    }
+
+    async fn kem_derive(&self, ikm: &[u8]) -> Result<(HpkeSecretKey, HpkePublicKey), Self::Error> {
+        self.0.kem_derive(ikm).await
+    }
+
+    async fn kem_generate(&self) -> Result<(HpkeSecretKey, HpkePublicKey), Self::Error> {
+        self.0.kem_generate().await
+    }
+
+    fn kem_public_key_validate(&self, key: &HpkePublicKey) -> Result<(), Self::Error> {
+        self.0.kem_public_key_validate(key)
+    }
+
+    fn random_bytes(&self, out: &mut [u8]) -> Result<(), Self::Error> {
+        self.0.random_bytes(out)
+    }
+
+    async fn signature_key_generate(
+        &self,
+    ) -> Result<(SignatureSecretKey, SignaturePublicKey), Self::Error> {
+        self.0.signature_key_generate().await
+    }
+
+    async fn signature_key_derive_public(
+        &self,
+        secret_key: &SignatureSecretKey,
+    ) -> Result<SignaturePublicKey, Self::Error> {
+        self.0.signature_key_derive_public(secret_key).await
+    }
+
+    async fn sign(
+        &self,
+        secret_key: &SignatureSecretKey,
+        data: &[u8],
+    ) -> Result<Vec<u8>, Self::Error> {
+        self.0.sign(secret_key, data).await
+    }
+
+    async fn verify(
+        &self,
+        public_key: &SignaturePublicKey,
+        signature: &[u8],
+        data: &[u8],
+    ) -> Result<(), Self::Error> {
+        self.0.verify(public_key, signature, data).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        client::test_utils::{TEST_CIPHER_SUITE, TEST_PROTOCOL_VERSION},
+        client_builder::{ClientBuilder, MlsConfig},
+        crypto::test_utils::TestCryptoProvider,
+        identity::{basic::BasicIdentityProvider, test_utils::get_test_signing_identity},
+        Client, ExtensionList,
+    };
+
+    use super::*;
+
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    async fn dyn_client(
+        crypto_provider: DynCryptoProvider,
+        identity: &str,
+    ) -> Client<impl MlsConfig> {
+        let (signing_identity, secret_key) =
+            get_test_signing_identity(TEST_CIPHER_SUITE, identity.as_bytes()).await;
+
+        ClientBuilder::new()
+            .crypto_provider(crypto_provider)
+            .identity_provider(BasicIdentityProvider::new())
+            .used_protocol_version(TEST_PROTOCOL_VERSION)
+            .signing_identity(signing_identity, secret_key, TEST_CIPHER_SUITE)
+            .build()
+    }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn client_can_use_dyn_crypto_provider() {
+        let alice = dyn_client(DynCryptoProvider::new(TestCryptoProvider::new()), "alice").await;
+        let bob = dyn_client(DynCryptoProvider::new(TestCryptoProvider::new()), "bob").await;
+
+        let mut alice_group = alice.create_group(ExtensionList::new()).await.unwrap();
+
+        let commit = alice_group
+            .commit_builder()
+            .add_member(bob.generate_key_package_message().await.unwrap())
+            .unwrap()
+            .build()
+            .await
+            .unwrap();
+
+        alice_group.apply_pending_commit().await.unwrap();
+
+        let (bob_group, _) = bob
+            .join_group(None, &commit.welcome_messages[0])
+            .await
+            .unwrap();
+
+        assert_eq!(
+            bob_group.epoch_authenticator().unwrap(),
+            alice_group.epoch_authenticator().unwrap()
+        );
+    }
+}
@@ -11,7 +11,7 @@ use mls_rs_core::{group::ProposalType, identity::CredentialType};
 
 #[cfg(feature = "by_ref_proposal")]
 use mls_rs_core::{
-    extension::ExtensionList,
+    extension::{ExtensionError, ExtensionList},
     identity::{IdentityProvider, SigningIdentity},
     time::MlsTime,
 };
@@ -211,6 +211,26 @@ impl ExternalSendersExt {
         &self.allowed_senders
     }
 
+    /// Check that no two allowed senders have identical credentials.
+    ///
+    /// External senders are referenced by index, so duplicate senders make
+    /// these references ambiguous. Credentials are compared as encoded, which
+    /// does not detect distinct credentials that an
+    /// [IdentityProvider](crate::IdentityProvider) maps to the same identity,
+    /// e.g. two X.509 chains for the same subject.
+    #[cfg_attr(all(feature = "ffi", not(test)), safer_ffi_gen::safer_ffi_gen_ignore)]
+    pub fn ensure_unique(&self) -> Result<(), ExtensionError> {
+        self.allowed_senders
+            .iter()
+            .enumerate()
+            .find(|(i, id)| {
+                self.allowed_senders[..*i]
+                    .iter()
+                    .any(|other| other.credential == id.credential)
+            })
+            .map_or(Ok(()), |(i, _)| Err(ExtensionError::DuplicateEntry(i)))
+    }
+
     #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
     pub(crate) async fn verify_all<I: IdentityProvider>(
         &self,
@@ -226,6 +246,66 @@ impl ExternalSendersExt {
 
         Ok(())
     }
+
+    /// Validate every allowed sender with `provider`, returning the result
+    /// for each sender along with its index.
+    ///
+    /// Unlike the validation performed when processing the extension, which
+    /// stops at the first invalid sender, this reports all failures at once.
+    #[cfg_attr(all(feature = "ffi", not(test)), safer_ffi_gen::safer_ffi_gen_ignore)]
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    pub async fn verify_all_detailed<I: IdentityProvider>(
+        &self,
+        provider: &I,
+        timestamp: Option<MlsTime>,
+        group_context_extensions: &ExtensionList,
+    ) -> Vec<(usize, Result<(), I::Error>)> {
+        let mut results = Vec::with_capacity(self.allowed_senders.len());
+
+        for (i, id) in self.allowed_senders.iter().enumerate() {
+            let res = provider
+                .validate_external_sender(id, timestamp, Some(group_context_extensions))
+                .await;
+
+            results.push((i, res));
+        }
+
+        results
+    }
+}
+
+/// Builder for an [`ExternalSendersExt`] that skips senders with duplicate
+/// credentials, following the same rules as
+/// [`ExternalSendersExt::ensure_unique`].
+#[cfg(feature = "by_ref_proposal")]
+#[derive(Clone, Debug, Default)]
+pub struct ExternalSendersExtBuilder {
+    allowed_senders: Vec<SigningIdentity>,
+}
+
+#[cfg(feature = "by_ref_proposal")]
+impl ExternalSendersExtBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add an allowed sender, unless a sender with an identical credential
+    /// was already added.
+    pub fn sender(mut self, sender: SigningIdentity) -> Self {
+        if !self
+            .allowed_senders
+            .iter()
+            .any(|id| id.credential == sender.credential)
+        {
+            self.allowed_senders.push(sender);
+        }
+
+        self
+    }
+
+    pub fn build(self) -> ExternalSendersExt {
+        ExternalSendersExt::new(self.allowed_senders)
+    }
 }
 
 #[cfg(feature = "by_ref_proposal")]
@@ -242,9 +322,13 @@ mod tests {
     use crate::tree_kem::node::NodeVec;
     #[cfg(feature = "by_ref_proposal")]
     use crate::{
-        client::test_utils::TEST_CIPHER_SUITE, identity::test_utils::get_test_signing_identity,
+        client::test_utils::TEST_CIPHER_SUITE,
+        identity::{basic::BasicIdentityProvider, test_utils::get_test_signing_identity},
     };
 
+    #[cfg(feature = "by_ref_proposal")]
+    use mls_rs_core::identity::{Credential, CustomCredential};
+
     use mls_rs_core::extension::MlsExtension;
 
     use mls_rs_core::identity::BasicCredential;
@@ -315,6 +399,69 @@ mod tests {
         assert_eq!(ext, restored)
     }
 
+    #[cfg(feature = "by_ref_proposal")]
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn detailed_verification_reports_every_invalid_sender() {
+        let valid = get_test_signing_identity(TEST_CIPHER_SUITE, b"alice")
+            .await
+            .0;
+
+        let invalid = SigningIdentity::new(
+            Credential::Custom(CustomCredential::new(CredentialType::new(65000), vec![])),
+            valid.signature_key.clone(),
+        );
+
+        let ext = ExternalSendersExt::new(vec![invalid.clone(), valid.clone(), invalid]);
+
+        let results = ext
+            .verify_all_detailed(&BasicIdentityProvider, None, &ExtensionList::new())
+            .await;
+
+        let failed = results
+            .iter()
+            .filter_map(|(i, res)| res.is_err().then_some(*i))
+            .collect::<Vec<_>>();
+
+        assert_eq!(results.len(), 3);
+        assert_eq!(failed, vec![0, 2]);
+
+        let res = ext
+            .verify_all(&BasicIdentityProvider, None, &ExtensionList::new())
+            .await;
+
+        assert!(res.is_err());
+    }
+
+    #[cfg(feature = "by_ref_proposal")]
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn external_senders_with_duplicate_credential_are_rejected() {
+        let alice = get_test_signing_identity(TEST_CIPHER_SUITE, b"alice")
+            .await
+            .0;
+        let bob = get_test_signing_identity(TEST_CIPHER_SUITE, b"bob").await.0;
+
+        // Same credential under a different signature key.
+        let alice_again = get_test_signing_identity(TEST_CIPHER_SUITE, b"alice")
+            .await
+            .0;
+
+        let ext = ExternalSendersExt::new(vec![alice.clone(), bob.clone(), alice_again.clone()]);
+
+        assert_matches::assert_matches!(
+            ext.ensure_unique(),
+            Err(ExtensionError::DuplicateEntry(2))
+        );
+
+        let ext = ExternalSendersExtBuilder::new()
+            .sender(alice.clone())
+            .sender(bob.clone())
+            .sender(alice_again)
+            .build();
+
+        assert_eq!(ext.allowed_senders, vec![alice, bob]);
+        ext.ensure_unique().unwrap();
+    }
+
     #[test]
     fn test_external_pub() {
         let ext = ExternalPubExt {
@@ -4,14 +4,22 @@
 
 use crate::{
     client::MlsError,
-    group::{framing::MlsMessage, message_processor::validate_key_package, ExportedTree},
-    KeyPackage,
+    group::{
+        framing::{MlsMessage, MlsMessagePayload},
+        message_processor::{key_package_validation_time, validate_key_package_at},
+        validate_group_info_joiner, validate_tree_and_info_joiner, validate_tree_data,
+        ExportedTree, GroupInfo,
+    },
+    time::MlsTime,
+    tree_kem::{node::NodeVec, TreeKemPublic},
+    CipherSuite, KeyPackage, ProtocolVersion,
 };
 
 pub mod builder;
 mod config;
 mod group;
 
+use alloc::vec::Vec;
 pub(crate) use config::ExternalClientConfig;
 use mls_rs_core::{
     crypto::{CryptoProvider, SignatureSecretKey},
@@ -46,6 +54,27 @@ impl ExternalClient<()> {
     pub fn builder() -> ExternalClientBuilder<ExternalBaseConfig> {
         ExternalClientBuilder::new()
     }
+
+    /// Export the group id and member identities of each observed group in
+    /// `snapshots`.
+    ///
+    /// The rosters are read from the snapshots directly, without loading
+    /// the groups. Snapshots can be obtained with
+    /// [ExternalGroup::snapshot](self::ExternalGroup::snapshot).
+    pub fn export_rosters<'a, I>(snapshots: I) -> Vec<(Vec<u8>, Vec<SigningIdentity>)>
+    where
+        I: IntoIterator<Item = &'a ExternalSnapshot>,
+    {
+        snapshots
+            .into_iter()
+            .map(|snapshot| {
+                (
+                    snapshot.context().group_id.clone(),
+                    snapshot.member_identities(),
+                )
+            })
+            .collect()
+    }
 }
 
 impl<C> ExternalClient<C>
@@ -86,6 +115,61 @@ where
         .await
     }
 
+    /// Begin observing a group based on a GroupInfo message and a serialized
+    /// ratchet tree delivered separately, for instance exported with
+    /// [Group::export_tree_with_hash](crate::group::Group::export_tree_with_hash).
+    ///
+    /// The tree is validated as in [ExternalClient::validate_ratchet_tree]
+    /// and must match the tree hash in the GroupInfo, even if the GroupInfo
+    /// also contains a ratchet tree extension.
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    pub async fn observe_group_with_tree(
+        &self,
+        group_info: MlsMessage,
+        tree_data: &[u8],
+    ) -> Result<ExternalGroup<C>, MlsError> {
+        let protocol_version = group_info.version;
+
+        if !self.config.version_supported(protocol_version) {
+            return Err(MlsError::UnsupportedProtocolVersion(protocol_version));
+        }
+
+        let group_info = group_info
+            .into_group_info()
+            .ok_or(MlsError::UnexpectedMessageType)?;
+
+        let cs = self.cipher_suite_provider(group_info.group_context.cipher_suite)?;
+
+        let public_tree = self
+            .validate_separate_tree(tree_data, &group_info, protocol_version, &cs)
+            .await?;
+
+        ExternalGroup::from_validated_tree(
+            self.config.clone(),
+            self.signing_data.clone(),
+            group_info,
+            public_tree,
+            cs,
+        )
+        .await
+    }
+
+    /// Begin observing a group based on public group state serialized by
+    /// [Group::public_group_state_bytes](crate::group::Group::public_group_state_bytes).
+    ///
+    /// The serialized state includes the ratchet tree, so no tree needs to be
+    /// provided out of band.
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    pub async fn observe_group_bytes(&self, bytes: &[u8]) -> Result<ExternalGroup<C>, MlsError> {
+        let group_info = MlsMessage::from_bytes(bytes)?;
+
+        if group_info.as_group_info().is_none() {
+            return Err(MlsError::UnexpectedMessageType);
+        }
+
+        self.observe_group(group_info, None).await
+    }
+
     /// Load an existing observed group by loading a snapshot that was
     /// generated by
     /// [ExternalGroup::snapshot](self::ExternalGroup::snapshot).
@@ -101,6 +185,24 @@ where
     pub async fn validate_key_package(
         &self,
         key_package: MlsMessage,
+    ) -> Result<KeyPackage, MlsError> {
+        self.validate_key_package_at(key_package, key_package_validation_time())
+            .await
+    }
+
+    /// Validate a key package as in
+    /// [`validate_key_package`](Self::validate_key_package), checking its
+    /// lifetime against `time` instead of the current time.
+    ///
+    /// The lifetime is not checked if `time` is `None`. Key packages are only
+    /// ever validated for adding a member: their leaf node always has the key
+    /// package source, which is rejected in the context of an update or a
+    /// commit.
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    pub async fn validate_key_package_at(
+        &self,
+        key_package: MlsMessage,
+        time: Option<MlsTime>,
     ) -> Result<KeyPackage, MlsError> {
         let version = key_package.version;
 
@@ -116,11 +218,139 @@ where
 
         let id = self.config.identity_provider();
 
-        validate_key_package(&key_package, version, &cs, &id).await?;
+        validate_key_package_at(&key_package, version, &cs, &id, time).await?;
 
         Ok(key_package)
     }
 
+    /// Deserialize an [`MlsMessage`] containing a key package from `bytes` and
+    /// validate it as in [`validate_key_package`](Self::validate_key_package).
+    ///
+    /// The key package is additionally required to use the given
+    /// `protocol_version` and `cipher_suite`.
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    pub async fn validate_key_package_bytes(
+        &self,
+        bytes: &[u8],
+        protocol_version: ProtocolVersion,
+        cipher_suite: CipherSuite,
+    ) -> Result<KeyPackage, MlsError> {
+        let key_package = MlsMessage::from_bytes(bytes)?;
+
+        let key_package_cipher_suite = key_package
+            .as_key_package()
+            .ok_or(MlsError::UnexpectedMessageType)?
+            .cipher_suite;
+
+        if key_package.version != protocol_version {
+            return Err(MlsError::ProtocolVersionMismatch);
+        }
+
+        if key_package_cipher_suite != cipher_suite {
+            return Err(MlsError::CipherSuiteMismatch);
+        }
+
+        self.validate_key_package(key_package).await
+    }
+
+    /// Verify the signature of a GroupInfo message and return the identity
+    /// of the member that signed it.
+    ///
+    /// The signer is looked up in the ratchet tree, which is validated along
+    /// with the GroupInfo. `tree_data` must be provided following the same
+    /// rules as [`observe_group`](Self::observe_group).
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    pub async fn group_info_signer(
+        &self,
+        group_info_message: &MlsMessage,
+        tree_data: Option<ExportedTree<'_>>,
+    ) -> Result<SigningIdentity, MlsError> {
+        let MlsMessagePayload::GroupInfo(group_info) = &group_info_message.payload else {
+            return Err(MlsError::UnexpectedMessageType);
+        };
+
+        let cs = self.cipher_suite_provider(group_info.group_context.cipher_suite)?;
+
+        let tree = validate_tree_and_info_joiner(
+            group_info_message.version,
+            group_info,
+            tree_data,
+            self.config.max_ratchet_tree_size(),
+            &self.config.identity_provider(),
+            &cs,
+        )
+        .await?;
+
+        Ok(tree
+            .get_leaf_node(group_info.signer)?
+            .signing_identity
+            .clone())
+    }
+
+    /// Validate a serialized ratchet tree against a GroupInfo message.
+    ///
+    /// The tree is checked for structural integrity and valid parent hash
+    /// links, and its tree hash must match the one in the GroupInfo. The
+    /// signature of the GroupInfo is verified using the signer's leaf in the
+    /// tree. `tree_data` is validated even if the GroupInfo also contains a
+    /// ratchet tree extension.
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    pub async fn validate_ratchet_tree(
+        &self,
+        tree_data: &[u8],
+        group_info_message: &MlsMessage,
+    ) -> Result<(), MlsError> {
+        let MlsMessagePayload::GroupInfo(group_info) = &group_info_message.payload else {
+            return Err(MlsError::UnexpectedMessageType);
+        };
+
+        let cs = self.cipher_suite_provider(group_info.group_context.cipher_suite)?;
+
+        self.validate_separate_tree(tree_data, group_info, group_info_message.version, &cs)
+            .await
+            .map(|_| ())
+    }
+
+    fn cipher_suite_provider(
+        &self,
+        cipher_suite: CipherSuite,
+    ) -> Result<<C::CryptoProvider as CryptoProvider>::CipherSuiteProvider, MlsError> {
+        self.config
+            .crypto_provider()
+            .cipher_suite_provider(cipher_suite)
+            .ok_or(MlsError::UnsupportedCipherSuite(cipher_suite))
+    }
+
+    // Validate `tree_data` and `group_info` against each other, ignoring any
+    // ratchet tree extension in `group_info`.
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    async fn validate_separate_tree(
+        &self,
+        tree_data: &[u8],
+        group_info: &GroupInfo,
+        protocol_version: ProtocolVersion,
+        cs: &<C::CryptoProvider as CryptoProvider>::CipherSuiteProvider,
+    ) -> Result<TreeKemPublic, MlsError> {
+        let id_provider = self.config.identity_provider();
+
+        let tree_data =
+            NodeVec::mls_decode_bounded(&mut &*tree_data, self.config.max_ratchet_tree_size())?;
+
+        let tree = validate_tree_data(
+            ExportedTree::new(tree_data),
+            &group_info.group_context,
+            &id_provider,
+            cs,
+        )
+        .await?;
+
+        let signer = &tree.get_leaf_node(group_info.signer)?.signing_identity;
+
+        validate_group_info_joiner(protocol_version, group_info, signer, &id_provider, cs).await?;
+
+        Ok(tree)
+    }
+
     /// The [IdentityProvider](crate::IdentityProvider) that this client was configured to use.
     pub fn identity_provider(&self) -> <C as ExternalClientConfig>::IdentityProvider {
         self.config.identity_provider()
@@ -129,11 +359,25 @@ where
 
 #[cfg(test)]
 pub(crate) mod tests_utils {
+    use alloc::{vec, vec::Vec};
+    use assert_matches::assert_matches;
+
     use crate::{
-        client::test_utils::{TEST_CIPHER_SUITE, TEST_PROTOCOL_VERSION},
+        client::{
+            test_utils::{TestClientBuilder, TEST_CIPHER_SUITE, TEST_PROTOCOL_VERSION},
+            MlsError,
+        },
+        crypto::test_utils::test_cipher_suite_provider,
+        group::{framing::MlsMessagePayload, test_utils::test_group, ExportedTree},
+        identity::basic::BasicIdentityProvider,
         key_package::test_utils::test_key_package_message,
+        signer::Signable,
+        tree_kem::{node::NodeVec, parent_hash::ParentHash, TreeKemPublic},
+        CipherSuite,
     };
 
+    use super::ExternalClient;
+
     pub use super::builder::test_utils::*;
 
     #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
@@ -144,4 +388,319 @@ pub(crate) mod tests_utils {
 
         assert_eq!(kp.into_key_package().unwrap(), validated_kp);
     }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn external_client_validates_key_package_lifetime_at_given_time() {
+        let kp = TestClientBuilder::new_for_test()
+            .with_random_signing_identity("john", TEST_CIPHER_SUITE)
+            .await
+            .key_package_lifetime(100)
+            .key_package_not_before(1000)
+            .build()
+            .generate_key_package_message()
+            .await
+            .unwrap();
+
+        let server = TestExternalClientBuilder::new_for_test().build();
+
+        server
+            .validate_key_package_at(kp.clone(), Some(1050.into()))
+            .await
+            .unwrap();
+
+        server
+            .validate_key_package_at(kp.clone(), None)
+            .await
+            .unwrap();
+
+        let res = server
+            .validate_key_package_at(kp.clone(), Some(2000.into()))
+            .await;
+
+        assert_matches!(res, Err(MlsError::InvalidLifetime));
+
+        let res = server.validate_key_package(kp).await;
+
+        assert_matches!(res, Err(MlsError::InvalidLifetime));
+    }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn external_client_can_validate_key_package_bytes() {
+        let kp = test_key_package_message(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE, "john").await;
+        let server = TestExternalClientBuilder::new_for_test().build();
+
+        let validated_kp = server
+            .validate_key_package_bytes(
+                &kp.to_bytes().unwrap(),
+                TEST_PROTOCOL_VERSION,
+                TEST_CIPHER_SUITE,
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(kp.into_key_package().unwrap(), validated_kp);
+    }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn external_client_rejects_non_key_package_bytes() {
+        let group = test_group(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE).await;
+        let group_info = group.group_info_message(true).await.unwrap();
+        let server = TestExternalClientBuilder::new_for_test().build();
+
+        let res = server
+            .validate_key_package_bytes(
+                &group_info.to_bytes().unwrap(),
+                TEST_PROTOCOL_VERSION,
+                TEST_CIPHER_SUITE,
+            )
+            .await;
+
+        assert_matches!(res, Err(MlsError::UnexpectedMessageType));
+    }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn external_client_rejects_truncated_key_package_bytes() {
+        let kp = test_key_package_message(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE, "john").await;
+        let server = TestExternalClientBuilder::new_for_test().build();
+        let bytes = kp.to_bytes().unwrap();
+
+        let res = server
+            .validate_key_package_bytes(
+                &bytes[..bytes.len() - 1],
+                TEST_PROTOCOL_VERSION,
+                TEST_CIPHER_SUITE,
+            )
+            .await;
+
+        assert_matches!(res, Err(MlsError::SerializationError(_)));
+    }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn external_client_rejects_key_package_bytes_with_other_cipher_suite() {
+        let kp = test_key_package_message(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE, "john").await;
+        let server = TestExternalClientBuilder::new_for_test().build();
+
+        let res = server
+            .validate_key_package_bytes(
+                &kp.to_bytes().unwrap(),
+                TEST_PROTOCOL_VERSION,
+                CipherSuite::CURVE25519_AES128,
+            )
+            .await;
+
+        assert_matches!(res, Err(MlsError::CipherSuiteMismatch));
+    }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn external_client_can_get_group_info_signer() {
+        let group = test_group(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE).await;
+        let group_info = group.group_info_message(true).await.unwrap();
+        let server = TestExternalClientBuilder::new_for_test().build();
+
+        let signer = server.group_info_signer(&group_info, None).await.unwrap();
+
+        assert_eq!(
+            &signer,
+            group.group.current_member_signing_identity().unwrap()
+        );
+    }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn external_client_rejects_tampered_group_info() {
+        let group = test_group(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE).await;
+        let mut group_info = group.group_info_message(true).await.unwrap();
+        let server = TestExternalClientBuilder::new_for_test().build();
+
+        let MlsMessagePayload::GroupInfo(ref mut info) = group_info.payload else {
+            panic!("expected group info");
+        };
+
+        info.signature[0] ^= 1;
+
+        let res = server.group_info_signer(&group_info, None).await;
+
+        assert_matches!(res, Err(MlsError::InvalidSignature));
+    }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn external_client_can_validate_ratchet_tree() {
+        let mut group = test_group(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE).await;
+        group.join("bob").await;
+
+        let group_info = group.group_info_message(false).await.unwrap();
+        let tree_data = group.export_tree().to_bytes().unwrap();
+        let server = TestExternalClientBuilder::new_for_test().build();
+
+        server
+            .validate_ratchet_tree(&tree_data, &group_info)
+            .await
+            .unwrap();
+    }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn external_client_rejects_ratchet_tree_with_broken_parent_hash() {
+        let mut group = test_group(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE).await;
+        group.join("bob").await;
+
+        // An empty commit includes a path, which sets the parent node above both leaves.
+        group.commit(Vec::new()).await.unwrap();
+        group.apply_pending_commit().await.unwrap();
+
+        let mut group_info = group.group_info_message(false).await.unwrap();
+        let mut nodes = NodeVec::from(group.export_tree().into_owned());
+
+        nodes.borrow_as_parent_mut(1).unwrap().parent_hash = ParentHash::from(vec![0u8; 32]);
+
+        // The tree hash covers parent hashes, so update it in the GroupInfo and
+        // re-sign to make sure only the parent hash check fails.
+        let cs = test_cipher_suite_provider(TEST_CIPHER_SUITE);
+
+        let mut tree = TreeKemPublic::import_node_data(
+            nodes.clone(),
+            &BasicIdentityProvider,
+            &Default::default(),
+        )
+        .await
+        .unwrap();
+
+        let tree_hash = tree.tree_hash(&cs).await.unwrap();
+
+        let MlsMessagePayload::GroupInfo(info) = &mut group_info.payload else {
+            panic!("expected group info");
+        };
+
+        info.group_context.tree_hash = tree_hash;
+        info.sign(&cs, &group.signer, &()).await.unwrap();
+
+        let tree_data = ExportedTree::new(nodes).to_bytes().unwrap();
+        let server = TestExternalClientBuilder::new_for_test().build();
+
+        let res = server.validate_ratchet_tree(&tree_data, &group_info).await;
+
+        assert_matches!(res, Err(MlsError::ParentHashMismatch));
+    }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn external_client_rejects_ratchet_tree_above_max_size() {
+        let mut group = test_group(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE).await;
+        group.join("bob").await;
+
+        let group_info = group.group_info_message(true).await.unwrap();
+        let tree_data = group.export_tree().to_bytes().unwrap();
+
+        let server = TestExternalClientBuilder::new_for_test()
+            .max_ratchet_tree_size(2)
+            .build();
+
+        let res = server
+            .observe_group(group_info.clone(), None)
+            .await
+            .map(|_| ());
+
+        assert_matches!(res, Err(MlsError::RatchetTreeTooLarge));
+
+        let res = server.validate_ratchet_tree(&tree_data, &group_info).await;
+        assert_matches!(res, Err(MlsError::RatchetTreeTooLarge));
+
+        let server = TestExternalClientBuilder::new_for_test()
+            .max_ratchet_tree_size(3)
+            .build();
+
+        server.observe_group(group_info, None).await.unwrap();
+    }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn external_client_can_observe_group_with_separate_tree() {
+        let mut group = test_group(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE).await;
+        let (old_tree, _) = group.export_tree_with_hash().unwrap();
+        group.join("bob").await;
+
+        let group_info = group.group_info_message(false).await.unwrap();
+        let (tree_data, tree_hash) = group.export_tree_with_hash().unwrap();
+
+        assert_eq!(
+            group_info
+                .as_group_info()
+                .unwrap()
+                .group_context()
+                .tree_hash(),
+            tree_hash
+        );
+
+        let server = TestExternalClientBuilder::new_for_test().build();
+
+        let observer = server
+            .observe_group_with_tree(group_info.clone(), &tree_data)
+            .await
+            .unwrap();
+
+        assert_eq!(observer.roster().members(), group.group.roster().members());
+
+        let res = server
+            .observe_group_with_tree(group_info, &old_tree)
+            .await
+            .map(|_| ());
+
+        assert_matches!(res, Err(MlsError::TreeHashMismatch));
+    }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn external_client_can_observe_public_group_state_bytes() {
+        let group = test_group(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE).await;
+        let bytes = group.group.public_group_state_bytes().await.unwrap();
+        let server = TestExternalClientBuilder::new_for_test().build();
+
+        let observer = server.observe_group_bytes(&bytes).await.unwrap();
+
+        assert_eq!(observer.group_context(), group.group.context());
+        assert_eq!(observer.roster().members(), group.group.roster().members());
+    }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn external_client_can_export_rosters() {
+        let alice_group = test_group(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE).await;
+        let mut bob_group = test_group(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE).await;
+        bob_group.join("carol").await;
+
+        let server = TestExternalClientBuilder::new_for_test().build();
+        let mut snapshots = Vec::new();
+
+        for group in [&alice_group, &bob_group] {
+            let bytes = group.group.public_group_state_bytes().await.unwrap();
+            let observer = server.observe_group_bytes(&bytes).await.unwrap();
+            snapshots.push(observer.snapshot());
+        }
+
+        let rosters = ExternalClient::export_rosters(&snapshots);
+
+        let expected = [&alice_group, &bob_group]
+            .into_iter()
+            .map(|group| {
+                let identities = group
+                    .group
+                    .roster()
+                    .members_iter()
+                    .map(|member| member.signing_identity)
+                    .collect::<Vec<_>>();
+
+                (group.group.group_id().to_vec(), identities)
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(rosters, expected);
+        assert_eq!(rosters[1].1.len(), 2);
+    }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn external_client_rejects_non_group_info_bytes() {
+        let kp = test_key_package_message(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE, "john").await;
+        let server = TestExternalClientBuilder::new_for_test().build();
+
+        let res = server
+            .observe_group_bytes(&kp.to_bytes().unwrap())
+            .await
+            .map(|_| ());
+
+        assert_matches!(res, Err(MlsError::UnexpectedMessageType));
+    }
 }
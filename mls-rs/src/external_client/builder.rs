@@ -198,6 +198,22 @@ impl<C: IntoConfig> ExternalClientBuilder<C> {
         ExternalClientBuilder(c)
     }
 
+    /// Accept ratchet trees of at most `max_nodes` nodes when observing a
+    /// group.
+    ///
+    /// A larger tree is rejected with
+    /// [`MlsError::RatchetTreeTooLarge`](crate::client::MlsError::RatchetTreeTooLarge)
+    /// before it is validated. The size is only limited by the range of node
+    /// indices by default.
+    pub fn max_ratchet_tree_size(
+        self,
+        max_nodes: usize,
+    ) -> ExternalClientBuilder<IntoConfigOutput<C>> {
+        let mut c = self.0.into_config();
+        c.0.settings.max_ratchet_tree_size = Some(max_nodes);
+        ExternalClientBuilder(c)
+    }
+
     /// Specify whether processed proposals should be cached by the external group. In case they
     /// are not cached by the group, they should be cached externally and inserted using
     /// `ExternalGroup::insert_proposal` before processing the next commit.
@@ -210,6 +226,21 @@ impl<C: IntoConfig> ExternalClientBuilder<C> {
         ExternalClientBuilder(c)
     }
 
+    /// Allow groups observed by this client to import epoch secrets using
+    /// [`ExternalGroup::import_epoch_secret`](crate::external_client::ExternalGroup::import_epoch_secret).
+    ///
+    /// This is a privileged mode meant for auditors that are given epoch
+    /// secrets out of band. It is disabled by default.
+    #[cfg(feature = "private_message")]
+    pub fn allow_epoch_secret_import(
+        self,
+        allow: bool,
+    ) -> ExternalClientBuilder<IntoConfigOutput<C>> {
+        let mut c = self.0.into_config();
+        c.0.settings.allow_epoch_secret_import = allow;
+        ExternalClientBuilder(c)
+    }
+
     /// Set the identity validator to be used by the client.
     pub fn identity_provider<I>(
         self,
@@ -378,10 +409,18 @@ where
         self.settings.max_epoch_jitter
     }
 
+    fn max_ratchet_tree_size(&self) -> Option<usize> {
+        self.settings.max_ratchet_tree_size
+    }
+
     fn cache_proposals(&self) -> bool {
         self.settings.cache_proposals
     }
 
+    fn allow_epoch_secret_import(&self) -> bool {
+        self.settings.allow_epoch_secret_import
+    }
+
     fn supported_custom_proposals(&self) -> Vec<ProposalType> {
         self.settings.custom_proposal_types.clone()
     }
@@ -457,6 +496,14 @@ impl<T: MlsConfig> ExternalClientConfig for T {
         self.get().max_epoch_jitter()
     }
 
+    fn max_ratchet_tree_size(&self) -> Option<usize> {
+        self.get().max_ratchet_tree_size()
+    }
+
+    fn allow_epoch_secret_import(&self) -> bool {
+        self.get().allow_epoch_secret_import()
+    }
+
     fn capabilities(&self) -> Capabilities {
         self.get().capabilities()
     }
@@ -477,7 +524,9 @@ pub(crate) struct Settings {
     pub(crate) protocol_versions: Vec<ProtocolVersion>,
     pub(crate) external_signing_keys: HashMap<Vec<u8>, SignaturePublicKey>,
     pub(crate) max_epoch_jitter: Option<u64>,
+    pub(crate) max_ratchet_tree_size: Option<usize>,
     pub(crate) cache_proposals: bool,
+    pub(crate) allow_epoch_secret_import: bool,
 }
 
 impl Debug for Settings {
@@ -499,7 +548,9 @@ impl Debug for Settings {
                 }),
             )
             .field("max_epoch_jitter", &self.max_epoch_jitter)
+            .field("max_ratchet_tree_size", &self.max_ratchet_tree_size)
             .field("cache_proposals", &self.cache_proposals)
+            .field("allow_epoch_secret_import", &self.allow_epoch_secret_import)
             .finish()
     }
 }
@@ -512,7 +563,9 @@ impl Default for Settings {
             protocol_versions: vec![],
             external_signing_keys: Default::default(),
             max_epoch_jitter: None,
+            max_ratchet_tree_size: None,
             custom_proposal_types: vec![],
+            allow_epoch_secret_import: false,
         }
     }
 }
@@ -34,6 +34,14 @@ pub trait ExternalClientConfig: Send + Sync + Clone {
         None
     }
 
+    fn max_ratchet_tree_size(&self) -> Option<usize> {
+        None
+    }
+
+    fn allow_epoch_secret_import(&self) -> bool {
+        false
+    }
+
     fn capabilities(&self) -> Capabilities {
         Capabilities {
             protocol_versions: self.supported_protocol_versions(),
@@ -4,8 +4,11 @@
 
 use mls_rs_codec::{MlsDecode, MlsEncode, MlsSize};
 use mls_rs_core::{
-    crypto::SignatureSecretKey, error::IntoAnyError, extension::ExtensionList, group::Member,
-    identity::IdentityProvider,
+    crypto::SignatureSecretKey,
+    error::IntoAnyError,
+    extension::ExtensionList,
+    group::Member,
+    identity::{Credential, IdentityProvider},
 };
 
 use crate::{
@@ -32,19 +35,18 @@ use crate::{
     identity::SigningIdentity,
     protocol_version::ProtocolVersion,
     psk::AlwaysFoundPskStorage,
-    tree_kem::{node::LeafIndex, path_secret::PathSecret, TreeKemPrivate},
+    tree_kem::{node::LeafIndex, path_secret::PathSecret, TreeKemPrivate, TreeKemPublic},
     CryptoProvider, KeyPackage, MlsMessage,
 };
 
+#[cfg(any(feature = "by_ref_proposal", feature = "private_message"))]
+use crate::group::{framing::Content, message_signature::AuthenticatedContent};
+
 #[cfg(feature = "by_ref_proposal")]
 use crate::{
     group::{
-        framing::{Content, MlsMessagePayload},
-        message_processor::CachedProposal,
-        message_signature::AuthenticatedContent,
-        proposal::Proposal,
-        proposal_ref::ProposalRef,
-        Sender,
+        framing::MlsMessagePayload, message_processor::CachedProposal, proposal::Proposal,
+        proposal_ref::ProposalRef, Sender,
     },
     WireFormat,
 };
@@ -70,9 +72,19 @@ use crate::{
 };
 
 #[cfg(feature = "private_message")]
-use crate::group::framing::PrivateMessage;
+use crate::group::{
+    ciphertext_processor::{CiphertextProcessor, GroupStateProvider},
+    epoch::EpochSecrets,
+    framing::PrivateMessage,
+    key_schedule::KeySchedule,
+    message_verifier::{verify_auth_content_signature, SignaturePublicKeysContainer},
+};
+
+#[cfg(feature = "private_message")]
+use zeroize::Zeroizing;
 
 use alloc::boxed::Box;
+use alloc::{format, string::String};
 
 /// The result of processing an [ExternalGroup](ExternalGroup) message using
 /// [process_incoming_message](ExternalGroup::process_incoming_message)
@@ -85,6 +97,9 @@ pub enum ExternalReceivedMessage {
     Proposal(ProposalMessageDescription),
     /// Encrypted message that can not be processed.
     Ciphertext(ContentType),
+    /// Application message decrypted using an imported epoch secret.
+    #[cfg(feature = "private_message")]
+    ApplicationMessage(ApplicationMessageDescription),
     /// Validated GroupInfo object
     GroupInfo(GroupInfo),
     /// Validated welcome message
@@ -95,6 +110,13 @@ pub enum ExternalReceivedMessage {
 
 /// A handle to an observed group that can track plaintext control messages
 /// and the resulting group state.
+///
+/// An external group can not produce or re-sign a [`GroupInfo`], even with
+/// the signing identity of an external sender. The signer of a `GroupInfo`
+/// is identified by its leaf index and joiners verify the signature with the
+/// key of that leaf. A cached `GroupInfo` must therefore be re-issued by a
+/// current member, for instance with
+/// [`Group::group_info_message`](crate::group::Group::group_info_message).
 #[derive(Clone)]
 pub struct ExternalGroup<C>
 where
@@ -104,6 +126,47 @@ where
     pub(crate) cipher_suite_provider: <C::CryptoProvider as CryptoProvider>::CipherSuiteProvider,
     pub(crate) state: GroupState,
     pub(crate) signing_data: Option<(SignatureSecretKey, SigningIdentity)>,
+    #[cfg(feature = "private_message")]
+    pub(crate) imported_epochs: Vec<ImportedEpoch>,
+}
+
+/// Epoch secret given to an [ExternalGroup](ExternalGroup) out of band.
+///
+/// Secrets derived from `epoch_secret` are computed lazily when the group
+/// reaches `epoch`, since the secret tree depends on the ratchet tree of that
+/// epoch.
+#[cfg(feature = "private_message")]
+#[derive(Clone)]
+pub(crate) struct ImportedEpoch {
+    epoch: u64,
+    epoch_secret: Zeroizing<Vec<u8>>,
+    secrets: Option<EpochSecrets>,
+}
+
+#[cfg(feature = "private_message")]
+struct ImportedEpochState<'a> {
+    context: &'a GroupContext,
+    secrets: &'a mut EpochSecrets,
+}
+
+#[cfg(feature = "private_message")]
+impl GroupStateProvider for ImportedEpochState<'_> {
+    fn group_context(&self) -> &GroupContext {
+        self.context
+    }
+
+    fn self_index(&self) -> LeafIndex {
+        // An observer is not a member, use an index that no sender can have.
+        LeafIndex(u32::MAX)
+    }
+
+    fn epoch_secrets_mut(&mut self) -> &mut EpochSecrets {
+        self.secrets
+    }
+
+    fn epoch_secrets(&self) -> &EpochSecrets {
+        self.secrets
+    }
 }
 
 impl<C: ExternalClientConfig + Clone> ExternalGroup<C> {
@@ -133,11 +196,32 @@ impl<C: ExternalClientConfig + Clone> ExternalGroup<C> {
             protocol_version,
             &group_info,
             tree_data,
+            config.max_ratchet_tree_size(),
             &config.identity_provider(),
             &cipher_suite_provider,
         )
         .await?;
 
+        Self::from_validated_tree(
+            config,
+            signing_data,
+            group_info,
+            public_tree,
+            cipher_suite_provider,
+        )
+        .await
+    }
+
+    /// Create a group from a GroupInfo and a ratchet tree that were already
+    /// validated against each other.
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    pub(crate) async fn from_validated_tree(
+        config: C,
+        signing_data: Option<(SignatureSecretKey, SigningIdentity)>,
+        group_info: GroupInfo,
+        public_tree: TreeKemPublic,
+        cipher_suite_provider: <C::CryptoProvider as CryptoProvider>::CipherSuiteProvider,
+    ) -> Result<Self, MlsError> {
         let interim_transcript_hash = InterimTranscriptHash::create(
             &cipher_suite_provider,
             &group_info.group_context.confirmed_transcript_hash,
@@ -155,6 +239,8 @@ impl<C: ExternalClientConfig + Clone> ExternalGroup<C> {
                 group_info.confirmation_tag,
             ),
             cipher_suite_provider,
+            #[cfg(feature = "private_message")]
+            imported_epochs: Vec::new(),
         })
     }
 
@@ -169,7 +255,11 @@ impl<C: ExternalClientConfig + Clone> ExternalGroup<C> {
     /// secrets required to do a complete check.
     ///
     /// * Application messages are always encrypted so they result in a no-op
-    /// that returns [ExternalReceivedMessage::Ciphertext]
+    /// that returns [ExternalReceivedMessage::Ciphertext], unless the secret
+    /// of the current epoch was provided using
+    /// [import_epoch_secret](ExternalGroup::import_epoch_secret). In that case
+    /// they are decrypted and returned as
+    /// [ExternalReceivedMessage::ApplicationMessage].
     ///
     /// # Warning
     ///
@@ -191,6 +281,94 @@ impl<C: ExternalClientConfig + Clone> ExternalGroup<C> {
         .await
     }
 
+    /// Import the epoch secret of `epoch` so that encrypted application
+    /// messages sent in that epoch can be decrypted by
+    /// [process_incoming_message](ExternalGroup::process_incoming_message).
+    ///
+    /// `epoch` may be the current epoch or a future one, in which case the
+    /// secret is used once the group reaches it. Imported secrets of past
+    /// epochs are discarded when a commit is processed and are not included
+    /// in [snapshots](ExternalGroup::snapshot).
+    ///
+    /// This is a privileged mode that must be enabled using
+    /// [ExternalClientBuilder::allow_epoch_secret_import](crate::external_client::builder::ExternalClientBuilder::allow_epoch_secret_import).
+    #[cfg(feature = "private_message")]
+    pub fn import_epoch_secret(&mut self, epoch: u64, secret: Vec<u8>) -> Result<(), MlsError> {
+        if !self.config.allow_epoch_secret_import() {
+            return Err(MlsError::EpochSecretImportDisabled);
+        }
+
+        if epoch < self.state.context.epoch {
+            return Err(MlsError::InvalidEpoch);
+        }
+
+        self.imported_epochs
+            .retain(|imported| imported.epoch != epoch);
+
+        self.imported_epochs.push(ImportedEpoch {
+            epoch,
+            epoch_secret: Zeroizing::new(secret),
+            secrets: None,
+        });
+
+        Ok(())
+    }
+
+    #[cfg(feature = "private_message")]
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    async fn decrypt_with_imported_secret(
+        &mut self,
+        message: &PrivateMessage,
+    ) -> Result<Option<AuthenticatedContent>, MlsError> {
+        let context = &self.state.context;
+
+        if message.content_type != ContentType::Application || message.epoch != context.epoch {
+            return Ok(None);
+        }
+
+        let Some(imported) = self
+            .imported_epochs
+            .iter_mut()
+            .find(|imported| imported.epoch == message.epoch)
+        else {
+            return Ok(None);
+        };
+
+        if imported.secrets.is_none() {
+            let derived = KeySchedule::from_epoch_secret(
+                &self.cipher_suite_provider,
+                &imported.epoch_secret,
+                self.state.public_tree.total_leaf_count(),
+            )
+            .await?;
+
+            imported.secrets = Some(derived.epoch_secrets);
+        }
+
+        let Some(secrets) = imported.secrets.as_mut() else {
+            return Ok(None);
+        };
+
+        let mut epoch_state = ImportedEpochState { context, secrets };
+
+        let content =
+            CiphertextProcessor::new(&mut epoch_state, self.cipher_suite_provider.clone())
+                .open(message)
+                .await?;
+
+        verify_auth_content_signature(
+            &self.cipher_suite_provider,
+            SignaturePublicKeysContainer::RatchetTree(&self.state.public_tree),
+            context,
+            &content,
+            #[cfg(feature = "by_ref_proposal")]
+            &[],
+        )
+        .await?;
+
+        Ok(Some(content))
+    }
+
     /// Replay a proposal message into the group skipping all validation steps.
     #[cfg(feature = "by_ref_proposal")]
     #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
@@ -508,6 +686,48 @@ impl<C: ExternalClientConfig + Clone> ExternalGroup<C> {
         self.group_state().public_tree.roster()
     }
 
+    /// Credential of the member at leaf `index`, or `None` if the leaf is
+    /// empty.
+    ///
+    /// The credential is returned as found in the member's leaf node, for
+    /// instance including the full certificate chain of an X.509 credential.
+    pub fn member_credential(&self, index: u32) -> Option<Credential> {
+        self.group_state()
+            .public_tree
+            .get_leaf_node(LeafIndex(index))
+            .ok()
+            .map(|leaf| leaf.signing_identity.credential.clone())
+    }
+
+    /// Human readable names of the current members along with their index.
+    ///
+    /// Names are derived from the identity returned by the configured
+    /// [`IdentityProvider`](crate::IdentityProvider), such as the content of
+    /// a basic credential. Identities that are not printable UTF-8 are
+    /// rendered as hex. If the identity provider fails, the member's
+    /// signature key is rendered as hex instead.
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    pub async fn roster_display(&self) -> Vec<(u32, String)> {
+        let identity_provider = self.config.identity_provider();
+        let extensions = &self.group_state().context.extensions;
+
+        let mut names = Vec::new();
+
+        for member in self.roster().members_iter() {
+            let name = match identity_provider
+                .identity(&member.signing_identity, extensions)
+                .await
+            {
+                Ok(identity) => display_name(&identity),
+                Err(_) => hex_name(&member.signing_identity.signature_key),
+            };
+
+            names.push((member.index, name));
+        }
+
+        names
+    }
+
     /// Get the
     /// [transcript hash](https://messaginglayersecurity.rocks/mls-protocol/draft-ietf-mls-protocol.html#name-transcript-hashes)
     /// for the current epoch that the group is in.
@@ -602,9 +822,25 @@ where
         &mut self,
         cipher_text: &PrivateMessage,
     ) -> Result<EventOrContent<Self::OutputType>, MlsError> {
-        Ok(EventOrContent::Event(ExternalReceivedMessage::Ciphertext(
-            cipher_text.content_type,
-        )))
+        let Some(auth_content) = self.decrypt_with_imported_secret(cipher_text).await? else {
+            return Ok(EventOrContent::Event(ExternalReceivedMessage::Ciphertext(
+                cipher_text.content_type,
+            )));
+        };
+
+        let Content::Application(data) = auth_content.content.content else {
+            return Err(MlsError::UnexpectedMessageType);
+        };
+
+        let description = self.process_application_message(
+            data,
+            auth_content.content.sender,
+            auth_content.content.authenticated_data,
+        )?;
+
+        Ok(EventOrContent::Event(
+            ExternalReceivedMessage::ApplicationMessage(description),
+        ))
     }
 
     async fn update_key_schedule(
@@ -621,6 +857,14 @@ where
         self.state.public_tree = provisional_public_state.public_tree;
         self.state.confirmation_tag = confirmation_tag.clone();
 
+        #[cfg(feature = "private_message")]
+        {
+            let epoch = self.state.context.epoch;
+
+            self.imported_epochs
+                .retain(|imported| imported.epoch >= epoch);
+        }
+
         Ok(())
     }
 
@@ -659,6 +903,22 @@ where
     }
 }
 
+fn display_name(identity: &[u8]) -> String {
+    match core::str::from_utf8(identity) {
+        Ok(name) if !name.is_empty() && !name.chars().any(char::is_control) => name.into(),
+        _ => hex_name(identity),
+    }
+}
+
+fn hex_name(bytes: &[u8]) -> String {
+    format!(
+        "{:?}",
+        mls_rs_core::debug::pretty_bytes(bytes)
+            .show_len(false)
+            .show_raw(true)
+    )
+}
+
 /// Serializable snapshot of an [ExternalGroup](ExternalGroup) state.
 #[derive(Debug, MlsEncode, MlsSize, MlsDecode, PartialEq, Clone)]
 pub struct ExternalSnapshot {
@@ -682,6 +942,16 @@ impl ExternalSnapshot {
     pub fn context(&self) -> &GroupContext {
         &self.state.context
     }
+
+    /// Signing identities of the group members encoded in the snapshot.
+    pub fn member_identities(&self) -> Vec<SigningIdentity> {
+        self.state
+            .public_tree
+            .roster()
+            .member_identities_iter()
+            .cloned()
+            .collect()
+    }
 }
 
 impl<C> ExternalGroup<C>
@@ -721,6 +991,8 @@ where
                 )
                 .await?,
             cipher_suite_provider,
+            #[cfg(feature = "private_message")]
+            imported_epochs: Vec::new(),
         })
     }
 }
@@ -804,7 +1076,7 @@ pub(crate) mod test_utils {
 
 #[cfg(test)]
 mod tests {
-    use super::test_utils::make_external_group;
+    use super::{display_name, test_utils::make_external_group};
     use crate::{
         cipher_suite::CipherSuite,
         client::{
@@ -824,7 +1096,7 @@ mod tests {
             proposal::{AddProposal, Proposal, ProposalOrRef},
             proposal_ref::ProposalRef,
             test_utils::{test_group, TestGroup},
-            CommitMessageDescription, ProposalMessageDescription,
+            CommitMessageDescription, ContentType, ProposalMessageDescription,
         },
         identity::{test_utils::get_test_signing_identity, SigningIdentity},
         key_package::test_utils::{test_key_package, test_key_package_message},
@@ -833,6 +1105,16 @@ mod tests {
     };
     use assert_matches::assert_matches;
     use mls_rs_codec::{MlsDecode, MlsEncode};
+    use mls_rs_core::crypto::CipherSuiteProvider;
+
+    #[cfg(feature = "x509")]
+    use crate::tree_kem::node::LeafIndex;
+
+    #[cfg(feature = "x509")]
+    use mls_rs_core::identity::{CertificateChain, Credential};
+
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::wasm_bindgen_test as test;
 
     #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
     async fn test_group_with_one_commit(v: ProtocolVersion, cs: CipherSuite) -> TestGroup {
@@ -884,6 +1166,138 @@ mod tests {
         }
     }
 
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn roster_display_uses_basic_credential_names() {
+        let group = test_group_two_members(
+            TEST_PROTOCOL_VERSION,
+            TEST_CIPHER_SUITE,
+            #[cfg(feature = "by_ref_proposal")]
+            None,
+        )
+        .await;
+
+        let server = make_external_group(&group).await;
+
+        let roster = server.roster_display().await;
+
+        assert_eq!(
+            roster,
+            vec![(0, "member".to_string()), (1, "bob".to_string())]
+        );
+    }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn member_credential_returns_basic_credential() {
+        let group = test_group_two_members(
+            TEST_PROTOCOL_VERSION,
+            TEST_CIPHER_SUITE,
+            #[cfg(feature = "by_ref_proposal")]
+            None,
+        )
+        .await;
+
+        let server = make_external_group(&group).await;
+
+        let credential = server.member_credential(1).unwrap();
+
+        assert_eq!(
+            credential.as_basic().map(|c| &c.identifier[..]),
+            Some(&b"bob"[..])
+        );
+
+        assert_eq!(server.member_credential(2), None);
+    }
+
+    #[cfg(feature = "x509")]
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn member_credential_returns_x509_chain() {
+        let group = test_group_two_members(
+            TEST_PROTOCOL_VERSION,
+            TEST_CIPHER_SUITE,
+            #[cfg(feature = "by_ref_proposal")]
+            None,
+        )
+        .await;
+
+        let mut server = make_external_group(&group).await;
+
+        let chain = CertificateChain::from(vec![vec![1, 2, 3], vec![4, 5, 6]]);
+
+        server
+            .state
+            .public_tree
+            .nodes
+            .borrow_as_leaf_mut(LeafIndex(1))
+            .unwrap()
+            .signing_identity
+            .credential = Credential::X509(chain.clone());
+
+        assert_eq!(server.member_credential(1), Some(Credential::X509(chain)));
+    }
+
+    #[cfg(all(feature = "x509", not(target_arch = "wasm32")))]
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn roster_display_renders_x509_subject() {
+        use crate::{
+            external_client::ExternalClientBuilder, identity::x509::X509CertificateReader,
+        };
+        use mls_rs_crypto_openssl::x509::{
+            identity_provider_from_certificate, signature_secret_key_from_bytes,
+            signing_identity_from_certificate, X509Reader,
+        };
+
+        let root = include_bytes!("../../../mls-rs-crypto-openssl/test_data/x509/root_ca/cert.der");
+        let leaf = include_bytes!("../../../mls-rs-crypto-openssl/test_data/x509/leaf/cert.der");
+
+        let secret_key = signature_secret_key_from_bytes(include_bytes!(
+            "../../../mls-rs-crypto-openssl/test_data/x509/leaf/key.pem"
+        ))
+        .unwrap();
+
+        let client = crate::Client::builder()
+            .crypto_provider(TestCryptoProvider::new())
+            .identity_provider(identity_provider_from_certificate(root).unwrap())
+            .signing_identity(
+                signing_identity_from_certificate(leaf).unwrap(),
+                secret_key,
+                CipherSuite::CURVE25519_AES128,
+            )
+            .build();
+
+        let group = client.create_group(ExtensionList::new()).await.unwrap();
+
+        let group_info = group
+            .group_info_message_allowing_ext_commit(true)
+            .await
+            .unwrap();
+
+        let config = ExternalClientBuilder::new()
+            .crypto_provider(TestCryptoProvider::new())
+            .identity_provider(identity_provider_from_certificate(root).unwrap())
+            .build_config();
+
+        let server = ExternalGroup::join(config, None, group_info, None)
+            .await
+            .unwrap();
+
+        // The subject identity extractor returns the DER encoded subject,
+        // which is not printable and therefore rendered as hex.
+        let subject = X509Reader::new()
+            .subject_bytes(&leaf.to_vec().into())
+            .unwrap();
+
+        let roster = server.roster_display().await;
+
+        assert_eq!(roster, vec![(0, hex::encode(subject))]);
+    }
+
+    #[test]
+    fn non_printable_identities_are_displayed_as_hex() {
+        assert_eq!(display_name(b"alice"), "alice");
+        assert_eq!(display_name(&[0xff, 0x01]), "ff01");
+        assert_eq!(display_name(&[0x00, 0x01, 0x61]), "000161");
+    }
+
     #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
     async fn external_group_can_process_commit() {
         let mut alice = test_group_with_one_commit(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE).await;
@@ -991,7 +1405,13 @@ mod tests {
             .process_incoming_message(commit_output.commit_message)
             .await;
 
-        assert_matches!(res, Err(MlsError::InvalidEpoch));
+        assert_matches!(
+            res,
+            Err(MlsError::StaleEpoch {
+                message_epoch: 0,
+                current_epoch: 1
+            })
+        );
     }
 
     #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
@@ -1032,6 +1452,65 @@ mod tests {
         assert_matches!(res, Err(MlsError::UnencryptedApplicationMessage));
     }
 
+    #[cfg(feature = "private_message")]
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn external_group_can_decrypt_with_imported_epoch_secret() {
+        let mut alice = test_group_with_one_commit(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE).await;
+        let (mut bob, _) = alice.join("bob").await;
+
+        let mut server = make_external_group_with_config(
+            &alice,
+            TestExternalClientBuilder::new_for_test()
+                .allow_epoch_secret_import(true)
+                .build_config(),
+        )
+        .await;
+
+        let epoch_secret = vec![42u8; server.cipher_suite_provider.kdf_extract_size()];
+        alice.set_epoch_secret(&epoch_secret).await;
+        bob.set_epoch_secret(&epoch_secret).await;
+
+        let message = alice
+            .encrypt_application_message(b"hello", b"aad".to_vec())
+            .await
+            .unwrap();
+
+        let res = server.process_incoming_message(message.clone()).await;
+
+        assert_matches!(
+            res,
+            Ok(ExternalReceivedMessage::Ciphertext(
+                ContentType::Application
+            ))
+        );
+
+        server
+            .import_epoch_secret(server.group_context().epoch, epoch_secret)
+            .unwrap();
+
+        let res = server.process_incoming_message(message.clone()).await;
+
+        assert_matches!(
+            res,
+            Ok(ExternalReceivedMessage::ApplicationMessage(desc))
+                if desc.data() == b"hello" && desc.authenticated_data == b"aad" && desc.sender_index == 0
+        );
+
+        // Members can still decrypt the message.
+        bob.process_message(message).await.unwrap();
+    }
+
+    #[cfg(feature = "private_message")]
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn external_group_rejects_epoch_secret_import_if_disabled() {
+        let alice = test_group_with_one_commit(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE).await;
+        let mut server = make_external_group(&alice).await;
+
+        let res = server.import_epoch_secret(server.group_context().epoch, vec![0u8; 32]);
+
+        assert_matches!(res, Err(MlsError::EpochSecretImportDisabled));
+    }
+
     #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
     async fn external_group_will_reject_unsupported_cipher_suites() {
         let alice = test_group_with_one_commit(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE).await;
@@ -0,0 +1,161 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// Copyright by contributors to this project.
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+use alloc::vec::Vec;
+
+use mls_rs_core::{
+    extension::ExtensionType,
+    group::{Capabilities, ProposalType},
+    identity::CredentialType,
+};
+
+use crate::{client::MlsError, client_config::ClientConfig, extension::RequiredCapabilitiesExt};
+
+use super::Group;
+
+/// Comparison between the capabilities required by a group and the
+/// capabilities supported by the local client.
+///
+/// See [`Group::capability_gap`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct CapabilityGap {
+    /// Capabilities required by the group's [`RequiredCapabilitiesExt`].
+    /// This is empty if the group does not have the extension.
+    pub required: RequiredCapabilitiesExt,
+    /// Capabilities supported by the local client configuration.
+    pub supported: Capabilities,
+    /// Required extension types that are not supported by the local client.
+    pub missing_extensions: Vec<ExtensionType>,
+    /// Required proposal types that are not supported by the local client.
+    pub missing_proposals: Vec<ProposalType>,
+    /// Required credential types that are not supported by the local client.
+    pub missing_credentials: Vec<CredentialType>,
+}
+
+impl CapabilityGap {
+    /// Returns `true` if the local client supports everything the group
+    /// requires.
+    pub fn is_empty(&self) -> bool {
+        self.missing_extensions.is_empty()
+            && self.missing_proposals.is_empty()
+            && self.missing_credentials.is_empty()
+    }
+}
+
+impl<C> Group<C>
+where
+    C: ClientConfig + Clone,
+{
+    /// Compare the capabilities required by this group with the capabilities
+    /// supported by the local client configuration.
+    ///
+    /// This is meant to help debugging capability mismatches, for instance
+    /// when the client is reconfigured after joining the group.
+    pub fn capability_gap(&self) -> Result<CapabilityGap, MlsError> {
+        let required = self.required_capabilities()?;
+        let supported = self.config.capabilities();
+
+        let missing_extensions = missing(&required.extensions, &supported.extensions);
+        let missing_proposals = missing(&required.proposals, &supported.proposals);
+        let missing_credentials = missing(&required.credentials, &supported.credentials);
+
+        Ok(CapabilityGap {
+            required,
+            supported,
+            missing_extensions,
+            missing_proposals,
+            missing_credentials,
+        })
+    }
+
+    /// Capabilities required by the group's [`RequiredCapabilitiesExt`], or
+    /// none if the group does not have the extension.
+    pub(super) fn required_capabilities(&self) -> Result<RequiredCapabilitiesExt, MlsError> {
+        Ok(self
+            .context()
+            .extensions
+            .get_as::<RequiredCapabilitiesExt>()?
+            .unwrap_or_default())
+    }
+}
+
+pub(super) fn missing<T: PartialEq + Copy>(required: &[T], supported: &[T]) -> Vec<T> {
+    required
+        .iter()
+        .filter(|item| !supported.contains(item))
+        .copied()
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+    use mls_rs_core::extension::{ExtensionList, ExtensionType, MlsExtension};
+
+    use crate::{
+        client::test_utils::{TestClientBuilder, TEST_CIPHER_SUITE, TEST_PROTOCOL_VERSION},
+        client_config::ClientConfig,
+        extension::RequiredCapabilitiesExt,
+        group::test_utils::test_group,
+    };
+
+    const EXTENSION_TYPE: ExtensionType = ExtensionType::new(33);
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn capability_gap_is_empty_without_required_capabilities() {
+        let group = test_group(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE).await;
+
+        let gap = group.group.capability_gap().unwrap();
+
+        assert!(gap.is_empty());
+        assert_eq!(gap.required, RequiredCapabilitiesExt::default());
+    }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn capability_gap_contains_unsupported_required_extension() {
+        let group_extensions = ExtensionList::from(vec![RequiredCapabilitiesExt {
+            extensions: vec![EXTENSION_TYPE],
+            ..Default::default()
+        }
+        .into_extension()
+        .unwrap()]);
+
+        let mut group = TestClientBuilder::new_for_test()
+            .with_random_signing_identity("alice", TEST_CIPHER_SUITE)
+            .await
+            .extension_type(EXTENSION_TYPE)
+            .build()
+            .create_group(group_extensions)
+            .await
+            .unwrap();
+
+        assert!(group.capability_gap().unwrap().is_empty());
+
+        group.write_to_storage().await.unwrap();
+
+        // Reload the group with a client that does not support the extension
+        let group = TestClientBuilder::new_for_test()
+            .signing_identity(
+                group.current_member_signing_identity().unwrap().clone(),
+                group.signer.clone(),
+                TEST_CIPHER_SUITE,
+            )
+            .key_package_repo(group.config.key_package_repo())
+            .group_state_storage(group.config.group_state_storage())
+            .build()
+            .load_group(group.group_id())
+            .await
+            .unwrap();
+
+        let gap = group.capability_gap().unwrap();
+
+        assert!(!gap.is_empty());
+        assert_eq!(gap.required.extensions, vec![EXTENSION_TYPE]);
+        assert!(!gap.supported.extensions.contains(&EXTENSION_TYPE));
+        assert_eq!(gap.missing_extensions, vec![EXTENSION_TYPE]);
+        assert!(gap.missing_proposals.is_empty());
+        assert!(gap.missing_credentials.is_empty());
+    }
+}
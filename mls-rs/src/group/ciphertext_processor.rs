@@ -39,6 +39,23 @@ pub(crate) trait GroupStateProvider {
     fn self_index(&self) -> LeafIndex;
     fn epoch_secrets_mut(&mut self) -> &mut EpochSecrets;
     fn epoch_secrets(&self) -> &EpochSecrets;
+
+    /// Called before decrypting an application message, allowing the group
+    /// to reject messages it already received. Nothing is recorded here as
+    /// the message is not authenticated yet.
+    fn check_application_message(
+        &self,
+        _sender: LeafIndex,
+        _generation: u32,
+    ) -> Result<(), MlsError> {
+        Ok(())
+    }
+
+    /// Maximum number of skipped message keys to keep per sender ratchet.
+    #[cfg(feature = "out_of_order")]
+    fn secret_tree_cache_size(&self) -> Option<usize> {
+        None
+    }
 }
 
 pub(crate) struct CiphertextProcessor<'a, GS, CP>
@@ -88,11 +105,22 @@ where
     ) -> Result<MessageKeyData, MlsError> {
         let sender = NodeIndex::from(sender);
 
-        self.group_state
+        let key = self
+            .group_state
             .epoch_secrets_mut()
             .secret_tree
             .message_key_generation(&self.cipher_suite_provider, sender, key_type, generation)
-            .await
+            .await?;
+
+        #[cfg(feature = "out_of_order")]
+        if let Some(cache_size) = self.group_state.secret_tree_cache_size() {
+            self.group_state
+                .epoch_secrets_mut()
+                .secret_tree
+                .trim_history(&sender, cache_size);
+        }
+
+        Ok(key)
     }
 
     #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
@@ -196,6 +224,18 @@ where
         &mut self,
         ciphertext: &PrivateMessage,
     ) -> Result<AuthenticatedContent, MlsError> {
+        self.open_with_generation(ciphertext)
+            .await
+            .map(|(content, _)| content)
+    }
+
+    /// Same as [`Self::open`] but also returns the generation of the
+    /// message key used to decrypt the message.
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    pub async fn open_with_generation(
+        &mut self,
+        ciphertext: &PrivateMessage,
+    ) -> Result<(AuthenticatedContent, u32), MlsError> {
         // Decrypt the sender data with the derived sender_key and sender_nonce from the message
         // epoch's key schedule
         let sender_data_aad = SenderDataAAD {
@@ -225,11 +265,20 @@ where
             _ => KeyType::Handshake,
         };
 
+        if matches!(key_type, KeyType::Application) {
+            self.group_state
+                .check_application_message(sender_data.sender, sender_data.generation)?;
+        }
+
         // Decrypt the content of the message using the grabbed key
         let key = self
             .decryption_key(sender_data.sender, key_type, sender_data.generation)
             .await?;
 
+        // Keep the key usable if the ciphertext turns out to be corrupted or forged
+        #[cfg(feature = "out_of_order")]
+        let unused_key = key.clone();
+
         let sender = Sender::Member(*sender_data.sender);
 
         let decrypted_content = MessageKey::new(key)
@@ -240,7 +289,17 @@ where
                 &sender_data.reuse_guard,
             )
             .await
-            .map_err(|e| MlsError::CryptoProviderError(e.into_any_error()))?;
+            .map_err(|e| MlsError::CryptoProviderError(e.into_any_error()));
+
+        #[cfg(feature = "out_of_order")]
+        if decrypted_content.is_err() {
+            self.group_state
+                .epoch_secrets_mut()
+                .secret_tree
+                .restore_message_key(&NodeIndex::from(sender_data.sender), key_type, unused_key);
+        }
+
+        let decrypted_content = decrypted_content?;
 
         let ciphertext_content =
             PrivateMessageContent::mls_decode(&mut &**decrypted_content, ciphertext.content_type)?;
@@ -258,7 +317,7 @@ where
             auth: ciphertext_content.auth,
         };
 
-        Ok(auth_content)
+        Ok((auth_content, sender_data.generation))
     }
 }
 
@@ -74,7 +74,8 @@ impl<'a, CP: CipherSuiteProvider> SenderDataKey<'a, CP> {
         let ciphertext_sample = ciphertext.get(0..extract_size).unwrap_or(ciphertext);
 
         // Generate a sender data key and nonce using the sender_data_secret from the current
-        // epoch's key schedule
+        // epoch's key schedule. Both depend on the ciphertext sample, so they are different for
+        // every message and cannot be cached per epoch (RFC 9420, Section 6.3.2).
         let key = kdf_expand_with_label(
             cipher_suite_provider,
             sender_data_secret,
@@ -176,8 +176,15 @@ where
     pub(super) proposals: Vec<Proposal>,
     authenticated_data: Vec<u8>,
     group_info_extensions: ExtensionList,
+    leaf_update: LeafUpdateOptions,
+}
+
+/// Changes to the committer's own leaf requested through a [`CommitBuilder`].
+#[derive(Default)]
+pub(super) struct LeafUpdateOptions {
     new_signer: Option<SignatureSecretKey>,
     new_signing_identity: Option<SigningIdentity>,
+    force_path_update: bool,
 }
 
 impl<'a, C> CommitBuilder<'a, C>
@@ -307,6 +314,15 @@ where
         }
     }
 
+    /// Always include an update path in this commit, regardless of the
+    /// proposals it contains and of the
+    /// `path_required` option returned by
+    /// [`MlsRules::commit_options`](`crate::MlsRules::commit_options`).
+    pub fn force_path_update(mut self) -> Self {
+        self.leaf_update.force_path_update = true;
+        self
+    }
+
     /// Change the committer's signing identity as part of making this commit.
     /// This will only succeed if the [`IdentityProvider`](crate::IdentityProvider)
     /// in use by the group considers the credential inside this signing_identity
@@ -315,15 +331,13 @@ where
     /// [identity](crate::IdentityProvider::identity)
     /// being used.
     pub fn set_new_signing_identity(
-        self,
+        mut self,
         signer: SignatureSecretKey,
         signing_identity: SigningIdentity,
     ) -> Self {
-        Self {
-            new_signer: Some(signer),
-            new_signing_identity: Some(signing_identity),
-            ..self
-        }
+        self.leaf_update.new_signer = Some(signer);
+        self.leaf_update.new_signing_identity = Some(signing_identity);
+        self
     }
 
     /// Finalize the commit to send.
@@ -343,8 +357,7 @@ where
                 None,
                 self.authenticated_data,
                 self.group_info_extensions,
-                self.new_signer,
-                self.new_signing_identity,
+                self.leaf_update,
             )
             .await?;
 
@@ -366,8 +379,7 @@ where
                 None,
                 self.authenticated_data,
                 self.group_info_extensions,
-                self.new_signer,
-                self.new_signing_identity,
+                self.leaf_update,
             )
             .await?;
 
@@ -427,6 +439,17 @@ where
             .await
     }
 
+    /// Perform a commit that refreshes this member's path secrets.
+    ///
+    /// The commit always contains an update path, even if pending proposals
+    /// that do not require one are committed with it. Committing a fresh
+    /// path on a regular basis provides forward secrecy and post-compromise
+    /// security when group membership does not change.
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    pub async fn commit_path_update(&mut self) -> Result<CommitOutput, MlsError> {
+        self.commit_builder().force_path_update().build().await
+    }
+
     /// The same function as `Group::commit` except the secrets generated
     /// for the commit are outputted instead of being cached internally.
     ///
@@ -450,8 +473,7 @@ where
             proposals: Default::default(),
             authenticated_data: Default::default(),
             group_info_extensions: Default::default(),
-            new_signer: Default::default(),
-            new_signing_identity: Default::default(),
+            leaf_update: Default::default(),
         }
     }
 
@@ -464,9 +486,14 @@ where
         external_leaf: Option<&LeafNode>,
         authenticated_data: Vec<u8>,
         mut welcome_group_info_extensions: ExtensionList,
-        new_signer: Option<SignatureSecretKey>,
-        new_signing_identity: Option<SigningIdentity>,
+        leaf_update: LeafUpdateOptions,
     ) -> Result<(CommitOutput, CommitGeneration), MlsError> {
+        let LeafUpdateOptions {
+            new_signer,
+            new_signing_identity,
+            force_path_update,
+        } = leaf_update;
+
         if self.pending_commit.is_some() {
             return Err(MlsError::ExistingPendingCommit);
         }
@@ -475,7 +502,11 @@ where
             return Err(MlsError::GroupUsedAfterReInit);
         }
 
-        let mls_rules = self.config.mls_rules();
+        if self.removed {
+            return Err(MlsError::RemovedFromGroup);
+        }
+
+        let mls_rules = self.current_mls_rules();
 
         let is_external = external_leaf.is_some();
 
@@ -542,7 +573,8 @@ where
             )
             .map_err(|e| MlsError::MlsRulesError(e.into_any_error()))?;
 
-        let perform_path_update = commit_options.path_required
+        let perform_path_update = force_path_update
+            || commit_options.path_required
             || path_update_required(&provisional_state.applied_proposals);
 
         let (update_path, path_secrets, commit_secret) = if perform_path_update {
@@ -881,6 +913,7 @@ pub(crate) mod test_utils {
 #[cfg(test)]
 mod tests {
     use mls_rs_core::{
+        crypto::{CipherSuiteProvider, CryptoProvider},
         error::IntoAnyError,
         extension::ExtensionType,
         identity::{CredentialType, IdentityProvider},
@@ -895,7 +928,7 @@ mod tests {
             WithIdentityProvider,
         },
         client_config::ClientConfig,
-        crypto::test_utils::TestCryptoProvider,
+        crypto::test_utils::{RemoteSignerCryptoProvider, TestCryptoProvider},
         extension::test_utils::{TestExtension, TEST_EXTENSION_TYPE},
         group::test_utils::{test_group, test_group_custom},
         group::{
@@ -1264,6 +1297,40 @@ mod tests {
         }
     }
 
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn welcome_exposes_joiner_metadata() {
+        let mut group = test_group(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE).await;
+        let bob_kp =
+            test_key_package_message(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE, "bob").await;
+        let carol_kp =
+            test_key_package_message(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE, "carol").await;
+
+        assert!(bob_kp.clone().into_welcome().is_none());
+
+        let output = group
+            .commit_builder()
+            .add_member(bob_kp.clone())
+            .unwrap()
+            .add_member(carol_kp.clone())
+            .unwrap()
+            .build()
+            .await
+            .unwrap();
+
+        assert!(output.commit_message.into_welcome().is_none());
+
+        let welcome = output.welcome_messages[0].clone().into_welcome().unwrap();
+        let cs = crate::crypto::test_utils::test_cipher_suite_provider(TEST_CIPHER_SUITE);
+
+        assert_eq!(welcome.cipher_suite(), TEST_CIPHER_SUITE);
+        assert_eq!(welcome.secret_count(), 2);
+
+        for kp in [bob_kp, carol_kp] {
+            let kp_ref = kp.key_package_reference(&cs).await.unwrap().unwrap();
+            assert!(welcome.key_package_references().contains(&&kp_ref));
+        }
+    }
+
     #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
     async fn commit_can_change_credential() {
         let cs = TEST_CIPHER_SUITE;
@@ -1616,4 +1683,110 @@ mod tests {
         group.apply_detached_commit(secrets).await.unwrap();
         assert_eq!(group.context().epoch, 1);
     }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn commit_can_be_signed_by_remote_signer() {
+        let crypto_provider = RemoteSignerCryptoProvider::new();
+
+        let (key_handle, public_key) = crypto_provider
+            .cipher_suite_provider(TEST_CIPHER_SUITE)
+            .unwrap()
+            .signature_key_generate()
+            .await
+            .unwrap();
+
+        let signing_identity =
+            SigningIdentity::new(get_test_basic_credential(b"alice".to_vec()), public_key);
+
+        let alice = ClientBuilder::new()
+            .crypto_provider(crypto_provider.clone())
+            .identity_provider(BasicIdentityProvider::new())
+            .signing_identity(signing_identity, key_handle, TEST_CIPHER_SUITE)
+            .build();
+
+        let mut alice_group = alice.create_group(ExtensionList::new()).await.unwrap();
+
+        let (bob, bob_key_package) =
+            test_client_with_key_pkg(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE, "bob").await;
+
+        let sign_requests = crypto_provider.sign_requests();
+
+        let commit = alice_group
+            .commit_builder()
+            .add_member(bob_key_package)
+            .unwrap()
+            .build()
+            .await
+            .unwrap();
+
+        assert!(crypto_provider.sign_requests() > sign_requests);
+
+        alice_group.apply_pending_commit().await.unwrap();
+
+        let (mut bob_group, _) = bob
+            .join_group(None, &commit.welcome_messages[0])
+            .await
+            .unwrap();
+
+        let commit = alice_group.commit(vec![]).await.unwrap();
+        alice_group.apply_pending_commit().await.unwrap();
+
+        bob_group
+            .process_incoming_message(commit.commit_message)
+            .await
+            .unwrap();
+
+        assert_eq!(bob_group.current_epoch(), alice_group.current_epoch());
+    }
+
+    fn commit_has_path(commit_output: CommitOutput) -> bool {
+        let plaintext = commit_output.commit_message.into_plaintext().unwrap();
+
+        match plaintext.content.content {
+            Content::Commit(commit) => commit.path.is_some(),
+            #[cfg(any(feature = "private_message", feature = "by_ref_proposal"))]
+            _ => panic!("Found non-commit data"),
+        }
+    }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn path_updates_refresh_epoch_secrets() {
+        let mut groups = test_n_member_group(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE, 2).await;
+        let mut authenticators = vec![groups[0].epoch_authenticator().unwrap()];
+
+        for _ in 0..2 {
+            let commit_output = groups[0].commit_path_update().await.unwrap();
+            let commit = commit_output.commit_message.clone();
+
+            assert!(commit_has_path(commit_output));
+
+            groups[0].apply_pending_commit().await.unwrap();
+            groups[1].process_incoming_message(commit).await.unwrap();
+
+            let authenticator = groups[0].epoch_authenticator().unwrap();
+
+            assert_eq!(authenticator, groups[1].epoch_authenticator().unwrap());
+            assert!(!authenticators.contains(&authenticator));
+
+            authenticators.push(authenticator);
+        }
+    }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn forced_path_update_is_included_with_add_proposals() {
+        let mut group = test_group(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE).await;
+
+        let commit_output = group
+            .commit_builder()
+            .add_member(
+                test_key_package_message(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE, "bob").await,
+            )
+            .unwrap()
+            .force_path_update()
+            .build()
+            .await
+            .unwrap();
+
+        assert!(commit_has_path(commit_output));
+    }
 }
@@ -92,6 +92,11 @@ impl GroupContext {
         self.epoch
     }
 
+    /// Get the hash of the group's ratchet tree in the current epoch.
+    pub fn tree_hash(&self) -> &[u8] {
+        &self.tree_hash
+    }
+
     pub fn extensions(&self) -> &ExtensionList {
         &self.extensions
     }
@@ -0,0 +1,142 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// Copyright by contributors to this project.
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+use alloc::vec::Vec;
+
+use mls_rs_core::extension::ExtensionType;
+
+use crate::{client::MlsError, client_config::ClientConfig};
+
+use super::{capability_gap::missing, Group};
+
+/// Extension types required or used by a group, along with the members
+/// that do not support them.
+///
+/// See [`Group::extension_compliance_report`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct ExtensionComplianceReport {
+    /// Extension types required by the group, as in
+    /// [`CapabilityGap::required`](super::CapabilityGap::required).
+    pub required: Vec<ExtensionType>,
+    /// Types of the extensions present in the group context.
+    pub group_context: Vec<ExtensionType>,
+    /// Index of each member lacking support for some required or group
+    /// context extension type, together with the unsupported types.
+    pub unsupported: Vec<(u32, Vec<ExtensionType>)>,
+}
+
+impl ExtensionComplianceReport {
+    /// Returns `true` if every member supports every required and group
+    /// context extension type.
+    pub fn is_compliant(&self) -> bool {
+        self.unsupported.is_empty()
+    }
+}
+
+impl<C> Group<C>
+where
+    C: ClientConfig + Clone,
+{
+    /// Compare the extension types required by and present in this group
+    /// with the extension types advertised by each member.
+    ///
+    /// Default extension types do not need to be advertised and are never
+    /// reported as unsupported.
+    pub fn extension_compliance_report(&self) -> Result<ExtensionComplianceReport, MlsError> {
+        let required = self.required_capabilities()?.extensions;
+
+        let group_context = self
+            .context()
+            .extensions
+            .iter()
+            .map(|ext| ext.extension_type)
+            .collect::<Vec<_>>();
+
+        let mut expected = Vec::new();
+
+        for &ext_type in required.iter().chain(&group_context) {
+            if !ext_type.is_default() && !expected.contains(&ext_type) {
+                expected.push(ext_type);
+            }
+        }
+
+        let unsupported = self
+            .roster()
+            .members_iter()
+            .filter_map(|member| {
+                let missing = missing(&expected, &member.capabilities.extensions);
+                (!missing.is_empty()).then_some((member.index, missing))
+            })
+            .collect();
+
+        Ok(ExtensionComplianceReport {
+            required,
+            group_context,
+            unsupported,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+    use mls_rs_core::extension::{ExtensionType, MlsExtension};
+
+    use crate::{
+        client::test_utils::{TEST_CIPHER_SUITE, TEST_PROTOCOL_VERSION},
+        extension::RequiredCapabilitiesExt,
+        group::test_utils::{test_group, test_group_custom},
+    };
+
+    const EXTENSION_TYPE: ExtensionType = ExtensionType::new(33);
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn test_group_is_compliant() {
+        let mut group = test_group(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE).await;
+        let (_bob, _) = group.join("bob").await;
+
+        let report = group.extension_compliance_report().unwrap();
+
+        assert!(report.is_compliant());
+        assert!(report.required.is_empty());
+
+        assert_eq!(
+            report.group_context,
+            vec![RequiredCapabilitiesExt::extension_type()]
+        );
+    }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn member_without_required_extension_is_flagged() {
+        let mut alice = test_group_custom(
+            TEST_PROTOCOL_VERSION,
+            TEST_CIPHER_SUITE,
+            vec![EXTENSION_TYPE],
+            None,
+            None,
+        )
+        .await;
+
+        // Bob does not support the extension. It becomes required only after he joined.
+        let (_bob, _) = alice.join("bob").await;
+
+        alice
+            .group
+            .state
+            .context
+            .extensions
+            .set_from(RequiredCapabilitiesExt {
+                extensions: vec![EXTENSION_TYPE],
+                ..Default::default()
+            })
+            .unwrap();
+
+        let report = alice.extension_compliance_report().unwrap();
+
+        assert!(!report.is_compliant());
+        assert_eq!(report.required, vec![EXTENSION_TYPE]);
+        assert_eq!(report.unsupported, vec![(1, vec![EXTENSION_TYPE])]);
+    }
+}
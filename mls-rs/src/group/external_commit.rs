@@ -7,12 +7,13 @@ use mls_rs_core::{crypto::SignatureSecretKey, identity::SigningIdentity};
 use crate::{
     client_config::ClientConfig,
     group::{
-        cipher_suite_provider,
+        check_min_protocol_version, cipher_suite_provider,
         epoch::SenderDataSecret,
         key_schedule::{InitSecret, KeySchedule},
         proposal::{ExternalInit, Proposal, RemoveProposal},
         EpochSecrets, ExternalPubExt, LeafIndex, LeafNode, MlsError, TreeKemPrivate,
     },
+    tree_kem::leaf_node_validator::{LeafNodeValidator, ValidationContext},
     Group, MlsMessage,
 };
 
@@ -145,6 +146,8 @@ impl<C: ClientConfig> ExternalCommitBuilder<C> {
     pub async fn build(self, group_info: MlsMessage) -> Result<(Group<C>, MlsMessage), MlsError> {
         let protocol_version = group_info.version;
 
+        check_min_protocol_version(protocol_version, self.config.min_protocol_version())?;
+
         if !self.config.version_supported(protocol_version) {
             return Err(MlsError::UnsupportedProtocolVersion(protocol_version));
         }
@@ -167,6 +170,7 @@ impl<C: ClientConfig> ExternalCommitBuilder<C> {
             protocol_version,
             &group_info,
             self.tree_data,
+            self.config.max_ratchet_tree_size(),
             &self.config.identity_provider(),
             &cipher_suite,
         )
@@ -181,6 +185,17 @@ impl<C: ClientConfig> ExternalCommitBuilder<C> {
         )
         .await?;
 
+        // The new leaf must support the group's required capabilities and
+        // extensions. Check it here so that the joiner gets a meaningful error
+        // instead of having its commit rejected by the members.
+        LeafNodeValidator::new(
+            &cipher_suite,
+            &self.config.identity_provider(),
+            Some(&group_info.group_context.extensions),
+        )
+        .check_if_valid(&leaf_node, ValidationContext::Add(None))
+        .await?;
+
         let (init_secret, kem_output) =
             InitSecret::encode_for_external(&cipher_suite, &external_pub_ext.external_pub).await?;
 
@@ -252,8 +267,7 @@ impl<C: ClientConfig> ExternalCommitBuilder<C> {
                 Some(&leaf_node),
                 self.authenticated_data,
                 Default::default(),
-                None,
-                None,
+                Default::default(),
             )
             .await?;
 
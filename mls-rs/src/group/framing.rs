@@ -369,6 +369,17 @@ impl MlsMessage {
         }
     }
 
+    /// The content type of a public or private message, readable without
+    /// verifying or decrypting the message.
+    pub(crate) fn content_type(&self) -> Option<ContentType> {
+        match &self.payload {
+            MlsMessagePayload::Plain(p) => Some(p.content.content_type()),
+            #[cfg(feature = "private_message")]
+            MlsMessagePayload::Cipher(c) => Some(c.content_type),
+            _ => None,
+        }
+    }
+
     #[cfg(feature = "private_message")]
     #[inline(always)]
     pub(crate) fn into_ciphertext(self) -> Option<PrivateMessage> {
@@ -379,7 +390,7 @@ impl MlsMessage {
     }
 
     #[inline(always)]
-    pub(crate) fn into_welcome(self) -> Option<Welcome> {
+    pub fn into_welcome(self) -> Option<Welcome> {
         match self.payload {
             MlsMessagePayload::Welcome(welcome) => Some(welcome),
             _ => None,
@@ -444,6 +455,23 @@ impl MlsMessage {
         }
     }
 
+    /// Whether this message is an encrypted handshake message.
+    ///
+    /// Returns `Some(false)` for a [`WireFormat::PublicMessage`], `Some(true)`
+    /// for a [`WireFormat::PrivateMessage`] containing a proposal or a commit
+    /// and `None` for any other message.
+    #[cfg_attr(all(feature = "ffi", not(test)), ::safer_ffi_gen::safer_ffi_gen_ignore)]
+    pub fn is_encrypted_handshake(&self) -> Option<bool> {
+        match &self.payload {
+            MlsMessagePayload::Plain(_) => Some(false),
+            #[cfg(feature = "private_message")]
+            MlsMessagePayload::Cipher(c) => {
+                (c.content_type != ContentType::Application).then_some(true)
+            }
+            _ => None,
+        }
+    }
+
     #[cfg_attr(all(feature = "ffi", not(test)), ::safer_ffi_gen::safer_ffi_gen_ignore)]
     pub fn cipher_suite(&self) -> Option<CipherSuite> {
         match &self.payload {
@@ -2,6 +2,7 @@
 // Copyright by contributors to this project.
 // SPDX-License-Identifier: (Apache-2.0 OR MIT)
 
+mod export;
 mod framing;
 mod passive_client;
 mod serialization;
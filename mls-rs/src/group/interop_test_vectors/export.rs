@@ -0,0 +1,315 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// Copyright by contributors to this project.
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+use mls_rs_codec::{MlsDecode, MlsEncode};
+use mls_rs_core::crypto::CipherSuiteProvider;
+
+use crate::{
+    client::test_utils::TEST_CIPHER_SUITE,
+    client_config::ClientConfig,
+    crypto::test_utils::test_cipher_suite_provider,
+    group::{
+        confirmation_tag::ConfirmationTag,
+        framing::{Content, WireFormat},
+        key_schedule::test_utils::get_test_key_schedule,
+        proposal::{Proposal, RemoveProposal},
+        test_utils::get_test_groups_with_features,
+        AuthenticatedContent, Commit, Group, GroupContext, Sender,
+    },
+    test_utils::is_edwards,
+    tree_kem::node::LeafIndex,
+    ExtensionList,
+};
+
+use super::framing::{process_message, FramingTestCase, InteropGroupContext, FRAMING_N_LEAVES};
+
+const EXPORTER_LABEL: &str = "exported test vector";
+const EXPORTER_LENGTH: usize = 32;
+
+/// Key schedule and message protection test vectors for a single epoch of a
+/// group, in the format of
+/// https://github.com/mlswg/mls-implementations/tree/main/test-vectors
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone)]
+pub(crate) struct ExportedTestVector {
+    pub key_schedule: ExportedKeyScheduleEpoch,
+    pub message_protection: FramingTestCase,
+}
+
+/// Secrets of the key-schedule test vector epoch that are known to a group
+/// member. Inputs to the key schedule (commit secret, psk secret, joiner
+/// secret...) are not kept by the group after the epoch is created.
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone)]
+pub(crate) struct ExportedKeyScheduleEpoch {
+    pub cipher_suite: u16,
+    #[serde(with = "hex::serde")]
+    pub group_context: Vec<u8>,
+    #[serde(with = "hex::serde")]
+    pub init_secret: Vec<u8>,
+    #[serde(with = "hex::serde")]
+    pub sender_data_secret: Vec<u8>,
+    #[serde(with = "hex::serde")]
+    pub encryption_secret: Vec<u8>,
+    #[serde(with = "hex::serde")]
+    pub exporter_secret: Vec<u8>,
+    #[serde(with = "hex::serde")]
+    pub epoch_authenticator: Vec<u8>,
+    #[serde(with = "hex::serde")]
+    pub external_secret: Vec<u8>,
+    #[serde(with = "hex::serde")]
+    pub membership_key: Vec<u8>,
+    #[serde(with = "hex::serde")]
+    pub resumption_psk: Vec<u8>,
+    #[serde(with = "hex::serde")]
+    pub external_pub: Vec<u8>,
+    pub exporter: ExportedExporter,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone)]
+pub(crate) struct ExportedExporter {
+    pub label: String,
+    #[serde(with = "hex::serde")]
+    pub context: Vec<u8>,
+    pub length: usize,
+    #[serde(with = "hex::serde")]
+    pub secret: Vec<u8>,
+}
+
+impl<C: ClientConfig + Clone> Group<C> {
+    /// Export the key schedule and message protection test vectors of the
+    /// current epoch.
+    ///
+    /// The message protection vector format requires the group to have two
+    /// members, no group context extensions and the exporting member to be at
+    /// index 1. The secret tree must not have been used in the current epoch.
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    pub(crate) async fn export_test_vector(&self) -> ExportedTestVector {
+        assert_eq!(self.current_member_index(), 1);
+        assert_eq!(self.state.public_tree.total_leaf_count(), FRAMING_N_LEAVES);
+        assert!(self.context().extensions.is_empty());
+
+        let cs = &self.cipher_suite_provider;
+        let context = self.context();
+        let encryption_secret = self.epoch_secrets.secret_tree.get_root_secret();
+
+        let (_, external_pub) = self.key_schedule.get_external_key_pair(cs).await.unwrap();
+
+        let exporter_secret = self
+            .key_schedule
+            .export_secret(
+                EXPORTER_LABEL.as_bytes(),
+                &context.group_id,
+                EXPORTER_LENGTH,
+                cs,
+            )
+            .await
+            .unwrap();
+
+        let key_schedule = ExportedKeyScheduleEpoch {
+            cipher_suite: context.cipher_suite.into(),
+            group_context: context.mls_encode_to_vec().unwrap(),
+            init_secret: self.key_schedule.init_secret().to_vec(),
+            sender_data_secret: self.epoch_secrets.sender_data_secret.to_vec(),
+            encryption_secret: encryption_secret.clone(),
+            exporter_secret: self.key_schedule.exporter_secret().to_vec(),
+            epoch_authenticator: self.key_schedule.authentication_secret.to_vec(),
+            external_secret: self.key_schedule.external_secret().to_vec(),
+            membership_key: self.key_schedule.membership_key().to_vec(),
+            resumption_psk: self.epoch_secrets.resumption_secret.to_vec(),
+            external_pub: external_pub.to_vec(),
+            exporter: ExportedExporter {
+                label: EXPORTER_LABEL.to_string(),
+                context: context.group_id.clone(),
+                length: EXPORTER_LENGTH,
+                secret: exporter_secret.to_vec(),
+            },
+        };
+
+        let signature_pub = self
+            .current_user_leaf_node()
+            .unwrap()
+            .signing_identity
+            .signature_key
+            .to_vec();
+
+        let mut signature_priv = self.signer.to_vec();
+
+        if is_edwards(context.cipher_suite.into()) {
+            signature_priv.truncate(signature_priv.len() / 2);
+        }
+
+        let mut test_case = FramingTestCase {
+            context: InteropGroupContext {
+                cipher_suite: context.cipher_suite.into(),
+                group_id: context.group_id.clone(),
+                epoch: context.epoch,
+                tree_hash: context.tree_hash.clone(),
+                confirmed_transcript_hash: context.confirmed_transcript_hash.to_vec(),
+            },
+            signature_priv,
+            signature_pub,
+            encryption_secret,
+            sender_data_secret: key_schedule.sender_data_secret.clone(),
+            membership_key: key_schedule.membership_key.clone(),
+            ..Default::default()
+        };
+
+        // Messages are created by a copy of the group so that the secret tree
+        // of this group is left untouched.
+        let mut sender = self.clone();
+
+        test_case.application = b"exported application message".to_vec();
+
+        test_case.application_priv = sender
+            .encrypt_application_message(&test_case.application, vec![])
+            .await
+            .unwrap()
+            .mls_encode_to_vec()
+            .unwrap();
+
+        let proposal = Proposal::Remove(RemoveProposal {
+            to_remove: LeafIndex(2),
+        });
+
+        test_case.proposal = proposal.mls_encode_to_vec().unwrap();
+
+        let commit = Commit {
+            proposals: vec![],
+            path: None,
+        };
+
+        test_case.commit = commit.mls_encode_to_vec().unwrap();
+
+        for wire_format in [WireFormat::PublicMessage, WireFormat::PrivateMessage] {
+            let proposal = sender
+                .export_test_vector_message(Content::Proposal(proposal.clone().into()), wire_format)
+                .await;
+
+            let commit = sender
+                .export_test_vector_message(Content::Commit(commit.clone().into()), wire_format)
+                .await;
+
+            if wire_format == WireFormat::PublicMessage {
+                test_case.proposal_pub = proposal;
+                test_case.commit_pub = commit;
+            } else {
+                test_case.proposal_priv = proposal;
+                test_case.commit_priv = commit;
+            }
+        }
+
+        ExportedTestVector {
+            key_schedule,
+            message_protection: test_case,
+        }
+    }
+
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    async fn export_test_vector_message(
+        &mut self,
+        content: Content,
+        wire_format: WireFormat,
+    ) -> Vec<u8> {
+        let is_commit = matches!(content, Content::Commit(_));
+
+        let mut auth_content = AuthenticatedContent::new_signed(
+            &self.cipher_suite_provider,
+            self.context(),
+            Sender::Member(self.current_member_index()),
+            content,
+            &self.signer,
+            wire_format,
+            vec![],
+        )
+        .await
+        .unwrap();
+
+        if is_commit {
+            auth_content.auth.confirmation_tag =
+                Some(ConfirmationTag::empty(&self.cipher_suite_provider).await);
+        }
+
+        self.format_for_wire(auth_content)
+            .await
+            .unwrap()
+            .mls_encode_to_vec()
+            .unwrap()
+    }
+}
+
+#[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+async fn exported_test_vector_can_be_reimported() {
+    let groups = get_test_groups_with_features(2, ExtensionList::new(), ExtensionList::new()).await;
+
+    let exported = groups[1].export_test_vector().await;
+    let json = serde_json::to_vec_pretty(&exported).unwrap();
+    let imported: ExportedTestVector = serde_json::from_slice(&json).unwrap();
+
+    let cs = test_cipher_suite_provider(TEST_CIPHER_SUITE);
+
+    // Key schedule
+    let key_schedule = imported.key_schedule;
+
+    let context = GroupContext::mls_decode(&mut &*key_schedule.group_context).unwrap();
+    assert_eq!(&context, groups[1].context());
+
+    let (_, external_pub) = cs.kem_derive(&key_schedule.external_secret).await.unwrap();
+    assert_eq!(external_pub.to_vec(), key_schedule.external_pub);
+
+    let mut imported_key_schedule = get_test_key_schedule(TEST_CIPHER_SUITE);
+    imported_key_schedule.set_exporter_secret(key_schedule.exporter_secret);
+
+    let exporter = key_schedule.exporter;
+
+    let exported_secret = imported_key_schedule
+        .export_secret(
+            exporter.label.as_bytes(),
+            &exporter.context,
+            exporter.length,
+            &cs,
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(exported_secret.to_vec(), exporter.secret);
+
+    let group_secret = groups[0]
+        .export_secret(
+            exporter.label.as_bytes(),
+            &exporter.context,
+            exporter.length,
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(group_secret.as_bytes(), exporter.secret);
+
+    // Message protection
+    let test_case = imported.message_protection;
+
+    match process_message(&test_case, &test_case.application_priv, &cs).await {
+        Content::Application(data) => assert_eq!(data.as_bytes(), test_case.application),
+        _ => panic!("decrypted value not application data"),
+    }
+
+    let proposal = Proposal::mls_decode(&mut &*test_case.proposal).unwrap();
+
+    for message in [&test_case.proposal_pub, &test_case.proposal_priv] {
+        match process_message(&test_case, message, &cs).await {
+            Content::Proposal(p) => assert_eq!(p.as_ref(), &proposal),
+            _ => panic!("received value not proposal"),
+        }
+    }
+
+    let commit = Commit::mls_decode(&mut &*test_case.commit).unwrap();
+
+    for message in [&test_case.commit_pub, &test_case.commit_priv] {
+        match process_message(&test_case, message, &cs).await {
+            Content::Commit(c) => assert_eq!(&*c, &commit),
+            _ => panic!("received value not commit"),
+        }
+    }
+}
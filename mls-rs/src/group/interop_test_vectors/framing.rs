@@ -27,10 +27,10 @@ use crate::{
     tree_kem::{leaf_node::test_utils::get_basic_test_node, node::LeafIndex},
 };
 
-const FRAMING_N_LEAVES: u32 = 2;
+pub(super) const FRAMING_N_LEAVES: u32 = 2;
 
 #[derive(serde::Serialize, serde::Deserialize, Debug, Default, Clone)]
-struct FramingTestCase {
+pub(super) struct FramingTestCase {
     #[serde(flatten)]
     pub context: InteropGroupContext,
 
@@ -444,7 +444,7 @@ async fn make_group<P: CipherSuiteProvider>(
 }
 
 #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
-async fn process_message<P: CipherSuiteProvider>(
+pub(super) async fn process_message<P: CipherSuiteProvider>(
     test_case: &FramingTestCase,
     message: &[u8],
     cs: &P,
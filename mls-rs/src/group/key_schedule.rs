@@ -189,7 +189,7 @@ impl KeySchedule {
     }
 
     #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
-    async fn from_epoch_secret<P: CipherSuiteProvider>(
+    pub(crate) async fn from_epoch_secret<P: CipherSuiteProvider>(
         cipher_suite_provider: &P,
         epoch_secret: &[u8],
         #[cfg(any(feature = "secret_tree_access", feature = "private_message"))]
@@ -319,6 +319,33 @@ pub(crate) async fn kdf_expand_with_label<P: CipherSuiteProvider>(
         .map_err(|e| MlsError::CryptoProviderError(e.into_any_error()))
 }
 
+/// Same as [`kdf_expand_with_label`] for each element of `contexts`, with the
+/// expansions computed in one call to the crypto provider.
+#[cfg(any(feature = "secret_tree_access", feature = "private_message"))]
+#[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+pub(crate) async fn kdf_expand_with_label_batch<P: CipherSuiteProvider>(
+    cipher_suite_provider: &P,
+    secret: &[u8],
+    label: &[u8],
+    contexts: &[&[u8]],
+    len: Option<usize>,
+) -> Result<Vec<Zeroizing<Vec<u8>>>, MlsError> {
+    let extract_size = cipher_suite_provider.kdf_extract_size();
+    let len = len.unwrap_or(extract_size);
+
+    let labels = contexts
+        .iter()
+        .map(|context| Label::new(len as u16, label, context).mls_encode_to_vec())
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let infos = labels.iter().map(Vec::as_slice).collect::<Vec<_>>();
+
+    cipher_suite_provider
+        .kdf_expand_batch(secret, &infos, len)
+        .await
+        .map_err(|e| MlsError::CryptoProviderError(e.into_any_error()))
+}
+
 #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
 pub(crate) async fn kdf_derive_secret<P: CipherSuiteProvider>(
     cipher_suite_provider: &P,
@@ -452,21 +479,23 @@ impl<'a, P: CipherSuiteProvider> WelcomeSecret<'a, P> {
         psk_secret: &PskSecret,
     ) -> Result<WelcomeSecret<'a, P>, MlsError> {
         let welcome_secret = get_welcome_secret(cipher_suite, joiner_secret, psk_secret).await?;
+        Self::new(cipher_suite, &welcome_secret).await
+    }
 
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    pub(crate) async fn new(
+        cipher_suite: &'a P,
+        welcome_secret: &[u8],
+    ) -> Result<WelcomeSecret<'a, P>, MlsError> {
         let key_len = cipher_suite.aead_key_size();
-        let key = kdf_expand_with_label(cipher_suite, &welcome_secret, b"key", &[], Some(key_len))
-            .await?;
+        let key =
+            kdf_expand_with_label(cipher_suite, welcome_secret, b"key", &[], Some(key_len)).await?;
 
         let nonce_len = cipher_suite.aead_nonce_size();
 
-        let nonce = kdf_expand_with_label(
-            cipher_suite,
-            &welcome_secret,
-            b"nonce",
-            &[],
-            Some(nonce_len),
-        )
-        .await?;
+        let nonce =
+            kdf_expand_with_label(cipher_suite, welcome_secret, b"nonce", &[], Some(nonce_len))
+                .await?;
 
         Ok(Self {
             cipher_suite,
@@ -493,7 +522,7 @@ impl<'a, P: CipherSuiteProvider> WelcomeSecret<'a, P> {
 }
 
 #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
-async fn get_welcome_secret<P: CipherSuiteProvider>(
+pub(crate) async fn get_welcome_secret<P: CipherSuiteProvider>(
     cipher_suite: &P,
     joiner_secret: &JoinerSecret,
     psk_secret: &PskSecret,
@@ -559,6 +588,26 @@ pub(crate) mod test_utils {
         pub fn set_membership_key(&mut self, key: Vec<u8>) {
             self.membership_key = Zeroizing::new(key)
         }
+
+        pub fn set_exporter_secret(&mut self, secret: Vec<u8>) {
+            self.exporter_secret = Zeroizing::new(secret)
+        }
+
+        pub fn exporter_secret(&self) -> &[u8] {
+            &self.exporter_secret
+        }
+
+        pub fn external_secret(&self) -> &[u8] {
+            &self.external_secret
+        }
+
+        pub fn membership_key(&self) -> &[u8] {
+            &self.membership_key
+        }
+
+        pub fn init_secret(&self) -> &[u8] {
+            &self.init_secret.0
+        }
     }
 }
 
@@ -985,4 +1034,32 @@ mod tests {
             }
         }
     }
+
+    #[cfg(any(feature = "secret_tree_access", feature = "private_message"))]
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn batched_expand_with_label_equals_individual_expansions() {
+        use crate::{
+            client::test_utils::TEST_CIPHER_SUITE,
+            crypto::test_utils::test_cipher_suite_provider,
+            group::{key_schedule::kdf_expand_with_label_batch, test_utils::random_bytes},
+        };
+
+        let cs = test_cipher_suite_provider(TEST_CIPHER_SUITE);
+        let secret = random_bytes(cs.kdf_extract_size());
+        let contexts: [&[u8]; 3] = [b"left", b"right", b""];
+
+        let batched = kdf_expand_with_label_batch(&cs, &secret, b"tree", &contexts, None)
+            .await
+            .unwrap();
+
+        assert_eq!(batched.len(), contexts.len());
+
+        for (context, batched) in contexts.into_iter().zip(batched) {
+            let individual = kdf_expand_with_label(&cs, &secret, b"tree", context, None)
+                .await
+                .unwrap();
+
+            assert_eq!(batched, individual);
+        }
+    }
 }
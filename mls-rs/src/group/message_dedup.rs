@@ -0,0 +1,112 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// Copyright by contributors to this project.
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+use alloc::collections::VecDeque;
+
+use crate::{client::MlsError, tree_kem::node::LeafIndex};
+
+/// Bounded record of the sender and generation of application messages
+/// received in the current epoch.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct MessageDedupCache {
+    epoch: u64,
+    seen: VecDeque<(LeafIndex, u32)>,
+}
+
+impl MessageDedupCache {
+    /// Fail with [`MlsError::DuplicateMessage`] if a message was already
+    /// recorded in the same epoch.
+    pub(crate) fn check(
+        &self,
+        epoch: u64,
+        sender: LeafIndex,
+        generation: u32,
+    ) -> Result<(), MlsError> {
+        if epoch == self.epoch && self.seen.contains(&(sender, generation)) {
+            return Err(MlsError::DuplicateMessage);
+        }
+
+        Ok(())
+    }
+
+    /// Record a message, failing with [`MlsError::DuplicateMessage`] if it was
+    /// already recorded in the same epoch. The oldest entry is evicted once
+    /// `capacity` entries are stored.
+    pub(crate) fn insert(
+        &mut self,
+        epoch: u64,
+        sender: LeafIndex,
+        generation: u32,
+        capacity: usize,
+    ) -> Result<(), MlsError> {
+        self.check(epoch, sender, generation)?;
+
+        if epoch != self.epoch {
+            self.seen.clear();
+            self.epoch = epoch;
+        }
+
+        if capacity == 0 {
+            return Ok(());
+        }
+
+        if self.seen.len() >= capacity {
+            self.seen.pop_front();
+        }
+
+        self.seen.push_back((sender, generation));
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_matches::assert_matches;
+
+    use crate::{client::MlsError, tree_kem::node::LeafIndex};
+
+    use super::MessageDedupCache;
+
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::wasm_bindgen_test as test;
+
+    #[test]
+    fn duplicate_is_rejected() {
+        let mut cache = MessageDedupCache::default();
+
+        cache.insert(1, LeafIndex(0), 0, 10).unwrap();
+        cache.insert(1, LeafIndex(1), 0, 10).unwrap();
+        cache.insert(1, LeafIndex(0), 1, 10).unwrap();
+
+        assert_matches!(
+            cache.insert(1, LeafIndex(0), 0, 10),
+            Err(MlsError::DuplicateMessage)
+        );
+    }
+
+    #[test]
+    fn cache_is_reset_on_new_epoch() {
+        let mut cache = MessageDedupCache::default();
+
+        cache.insert(1, LeafIndex(0), 0, 10).unwrap();
+        cache.insert(2, LeafIndex(0), 0, 10).unwrap();
+    }
+
+    #[test]
+    fn oldest_entry_is_evicted() {
+        let mut cache = MessageDedupCache::default();
+
+        cache.insert(1, LeafIndex(0), 0, 2).unwrap();
+        cache.insert(1, LeafIndex(0), 1, 2).unwrap();
+        cache.insert(1, LeafIndex(0), 2, 2).unwrap();
+
+        cache.insert(1, LeafIndex(0), 0, 2).unwrap();
+
+        assert_matches!(
+            cache.insert(1, LeafIndex(0), 2, 2),
+            Err(MlsError::DuplicateMessage)
+        );
+    }
+}
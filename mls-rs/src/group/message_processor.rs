@@ -33,6 +33,7 @@ use mls_rs_codec::{MlsDecode, MlsEncode, MlsSize};
 
 use alloc::boxed::Box;
 use alloc::vec::Vec;
+use core::cmp::Ordering;
 use core::fmt::{self, Debug};
 use mls_rs_core::{
     identity::IdentityProvider, protocol_version::ProtocolVersion, psk::PreSharedKeyStorage,
@@ -164,6 +165,14 @@ pub enum ReceivedMessage {
     Welcome,
     /// Validated key package
     KeyPackage(KeyPackage),
+    /// A commit for a future epoch was buffered. It is applied once the group
+    /// reaches `epoch`, see
+    /// [`Group::take_applied_buffered_commits`](crate::group::Group::take_applied_buffered_commits).
+    ///
+    /// This variant is only returned if
+    /// [`ClientBuilder::future_commit_buffer_size`](crate::client_builder::ClientBuilder::future_commit_buffer_size)
+    /// is set.
+    BufferedCommit { epoch: u64 },
 }
 
 impl TryFrom<ApplicationMessageDescription> for ReceivedMessage {
@@ -582,9 +591,16 @@ pub(crate) trait MessageProcessor: Send + Sync {
         )
         .await?;
 
+        let max_pending_proposals = self.max_pending_proposals();
         let group_state = self.group_state_mut();
 
         if cache_proposal {
+            if let Some(max) = max_pending_proposals {
+                if group_state.proposals.len() >= max {
+                    return Err(MlsError::TooManyPendingProposals(max));
+                }
+            }
+
             group_state.proposals.insert(
                 proposal.proposal_ref.clone(),
                 proposal.proposal.clone(),
@@ -648,6 +664,17 @@ pub(crate) trait MessageProcessor: Send + Sync {
 
         let sender = commit_sender(&auth_content.content.sender, &provisional_state)?;
 
+        #[cfg(feature = "psk")]
+        if matches!(auth_content.content.sender, Sender::NewMemberCommit)
+            && self.external_commit_psk_required()
+            && provisional_state
+                .applied_proposals
+                .psk_proposals()
+                .is_empty()
+        {
+            return Err(MlsError::ExternalCommitMissingPsk);
+        }
+
         //Verify that the path value is populated if the proposals vector contains any Update
         // or Remove proposals, or if it's empty. Otherwise, the path value MAY be omitted.
         if path_update_required(&provisional_state.applied_proposals) && commit.path.is_none() {
@@ -753,6 +780,19 @@ pub(crate) trait MessageProcessor: Send + Sync {
     #[cfg(feature = "private_message")]
     fn min_epoch_available(&self) -> Option<u64>;
 
+    /// Whether external commits must include a pre-shared key proposal.
+    #[cfg(feature = "psk")]
+    fn external_commit_psk_required(&self) -> bool {
+        false
+    }
+
+    /// Maximum number of received proposals cached until the next commit, or
+    /// `None` if the cache is unbounded.
+    #[cfg(feature = "by_ref_proposal")]
+    fn max_pending_proposals(&self) -> Option<usize> {
+        None
+    }
+
     fn check_metadata(&self, message: &MlsMessage) -> Result<(), MlsError> {
         let context = &self.group_state().context;
 
@@ -775,25 +815,17 @@ pub(crate) trait MessageProcessor: Send + Sync {
             _ => None,
         } {
             if group_id != &context.group_id {
-                return Err(MlsError::GroupIdMismatch);
+                return Err(MlsError::GroupIdMismatch {
+                    expected: context.group_id.clone(),
+                    found: group_id.clone(),
+                });
             }
 
+            // Proposal and commit messages must be sent in the current epoch
             match content_type {
-                ContentType::Commit => {
-                    if context.epoch != epoch {
-                        Err(MlsError::InvalidEpoch)
-                    } else {
-                        Ok(())
-                    }
-                }
+                ContentType::Commit => check_handshake_epoch(epoch, context.epoch),
                 #[cfg(feature = "by_ref_proposal")]
-                ContentType::Proposal => {
-                    if context.epoch != epoch {
-                        Err(MlsError::InvalidEpoch)
-                    } else {
-                        Ok(())
-                    }
-                }
+                ContentType::Proposal => check_handshake_epoch(epoch, context.epoch),
                 #[cfg(feature = "private_message")]
                 ContentType::Application => {
                     if let Some(min) = self.min_epoch_available() {
@@ -808,16 +840,6 @@ pub(crate) trait MessageProcessor: Send + Sync {
                 }
             }?;
 
-            // Proposal and commit messages must be sent in the current epoch
-            let check_epoch = content_type == ContentType::Commit;
-
-            #[cfg(feature = "by_ref_proposal")]
-            let check_epoch = check_epoch || content_type == ContentType::Proposal;
-
-            if check_epoch && epoch != context.epoch {
-                return Err(MlsError::InvalidEpoch);
-            }
-
             // Unencrypted application messages are not allowed
             #[cfg(feature = "private_message")]
             if !matches!(&message.payload, MlsMessagePayload::Cipher(_))
@@ -893,6 +915,20 @@ pub(crate) trait MessageProcessor: Send + Sync {
     ) -> Result<(), MlsError>;
 }
 
+fn check_handshake_epoch(message_epoch: u64, current_epoch: u64) -> Result<(), MlsError> {
+    match message_epoch.cmp(&current_epoch) {
+        Ordering::Less => Err(MlsError::StaleEpoch {
+            message_epoch,
+            current_epoch,
+        }),
+        Ordering::Greater => Err(MlsError::FutureEpoch {
+            message_epoch,
+            current_epoch,
+        }),
+        Ordering::Equal => Ok(()),
+    }
+}
+
 #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
 pub(crate) async fn validate_key_package<C: CipherSuiteProvider, I: IdentityProvider>(
     key_package: &KeyPackage,
@@ -900,15 +936,32 @@ pub(crate) async fn validate_key_package<C: CipherSuiteProvider, I: IdentityProv
     cs: &C,
     id: &I,
 ) -> Result<(), MlsError> {
-    let validator = LeafNodeValidator::new(cs, id, None);
+    validate_key_package_at(key_package, version, cs, id, key_package_validation_time()).await
+}
 
+/// The time at which key package lifetimes are checked by default, or `None`
+/// if the current time is not available.
+pub(crate) fn key_package_validation_time() -> Option<MlsTime> {
     #[cfg(feature = "std")]
-    let context = Some(MlsTime::now());
+    let time = Some(MlsTime::now());
 
     #[cfg(not(feature = "std"))]
-    let context = None;
+    let time = None;
+
+    time
+}
+
+#[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+pub(crate) async fn validate_key_package_at<C: CipherSuiteProvider, I: IdentityProvider>(
+    key_package: &KeyPackage,
+    version: ProtocolVersion,
+    cs: &C,
+    id: &I,
+    time: Option<MlsTime>,
+) -> Result<(), MlsError> {
+    let validator = LeafNodeValidator::new(cs, id, None);
 
-    let context = ValidationContext::Add(context);
+    let context = ValidationContext::Add(time);
 
     validator
         .check_if_valid(&key_package.leaf_node, context)
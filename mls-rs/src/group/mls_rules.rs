@@ -2,7 +2,11 @@
 // Copyright by contributors to this project.
 // SPDX-License-Identifier: (Apache-2.0 OR MIT)
 
-use crate::group::{proposal_filter::ProposalBundle, Roster};
+use crate::group::{
+    proposal::Proposal,
+    proposal_filter::{ProposalBundle, ProposalInfo},
+    GroupContext, Roster,
+};
 
 #[cfg(feature = "private_message")]
 use crate::{
@@ -10,7 +14,7 @@ use crate::{
     WireFormat,
 };
 
-use alloc::boxed::Box;
+use alloc::{boxed::Box, vec::Vec};
 use core::convert::Infallible;
 use mls_rs_core::{
     error::IntoAnyError, extension::ExtensionList, group::Member, identity::SigningIdentity,
@@ -56,6 +60,11 @@ impl CommitOptions {
         Self::default()
     }
 
+    /// Require every commit to include an update path.
+    ///
+    /// When `false` (the default), the path is omitted whenever the RFC
+    /// permits it, e.g. for commits that only add members. This avoids
+    /// encrypting path secrets to every copath node in large groups.
     pub fn with_path_required(self, path_required: bool) -> Self {
         Self {
             path_required,
@@ -147,6 +156,29 @@ pub trait MlsRules: Send + Sync {
         proposals: ProposalBundle,
     ) -> Result<ProposalBundle, Self::Error>;
 
+    /// Same as [filter_proposals](MlsRules::filter_proposals) but with access to the whole
+    /// current group context, e.g. to make decisions based on the epoch or the cipher suite.
+    ///
+    /// This is the function called by the library. The default implementation calls
+    /// [filter_proposals](MlsRules::filter_proposals) with the extensions of `current_context`.
+    async fn filter_proposals_with_context(
+        &self,
+        direction: CommitDirection,
+        source: CommitSource,
+        current_roster: &Roster,
+        current_context: &GroupContext,
+        proposals: ProposalBundle,
+    ) -> Result<ProposalBundle, Self::Error> {
+        self.filter_proposals(
+            direction,
+            source,
+            current_roster,
+            &current_context.extensions,
+            proposals,
+        )
+        .await
+    }
+
     /// This is called when preparing a commit to determine various options: whether to enforce an update
     /// path in case it is not mandated by MLS, whether to include the ratchet tree in the welcome
     /// message (if the commit adds members) and whether to generate a single welcome message, or one
@@ -193,6 +225,26 @@ macro_rules! delegate_mls_rules {
                     .await
             }
 
+            #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+            async fn filter_proposals_with_context(
+                &self,
+                direction: CommitDirection,
+                source: CommitSource,
+                current_roster: &Roster,
+                current_context: &GroupContext,
+                proposals: ProposalBundle,
+            ) -> Result<ProposalBundle, Self::Error> {
+                (**self)
+                    .filter_proposals_with_context(
+                        direction,
+                        source,
+                        current_roster,
+                        current_context,
+                        proposals,
+                    )
+                    .await
+            }
+
             fn commit_options(
                 &self,
                 roster: &Roster,
@@ -281,3 +333,356 @@ impl MlsRules for DefaultMlsRules {
         Ok(self.encryption_options)
     }
 }
+
+/// Change to the committed proposals that the rules wrapped by an
+/// [`AuditProposalFilter`] would have made.
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub enum ProposalAudit<E> {
+    /// The wrapped rules returned an error.
+    Rejected(E),
+    /// The wrapped rules removed these proposals.
+    Filtered(Vec<ProposalInfo<Proposal>>),
+}
+
+/// MLS rules running the proposal filter of other rules in audit mode.
+///
+/// Rejections and removals by the wrapped proposal filter are reported to an
+/// observer but all proposals are passed through unchanged. This allows
+/// observing custom rules in a live group before enforcing them. Commit and
+/// encryption options are taken from the wrapped rules.
+///
+/// The proposal filter runs each time a commit is processed. For a commit
+/// made by this member, that is once when building the commit and again when
+/// applying it with [`Group::apply_pending_commit`](crate::Group::apply_pending_commit),
+/// so the observer is called twice for the same proposals.
+#[derive(Clone, Debug)]
+pub struct AuditProposalFilter<R, F> {
+    inner: R,
+    observer: F,
+}
+
+impl<R: MlsRules> AuditProposalFilter<R, fn(ProposalAudit<R::Error>)> {
+    /// Wrap `inner` without an observer.
+    pub fn new(inner: R) -> Self {
+        Self {
+            inner,
+            observer: |_| (),
+        }
+    }
+}
+
+impl<R: MlsRules, F> AuditProposalFilter<R, F> {
+    /// Set the observer called each time the wrapped rules would reject or
+    /// filter out proposals.
+    pub fn with_observer<G>(self, observer: G) -> AuditProposalFilter<R, G>
+    where
+        G: Fn(ProposalAudit<R::Error>) + Send + Sync,
+    {
+        AuditProposalFilter {
+            inner: self.inner,
+            observer,
+        }
+    }
+
+    /// The wrapped rules.
+    pub fn inner(&self) -> &R {
+        &self.inner
+    }
+}
+
+impl<R, F> AuditProposalFilter<R, F>
+where
+    R: MlsRules,
+    F: Fn(ProposalAudit<R::Error>) + Send + Sync,
+{
+    fn audit(
+        &self,
+        proposals: ProposalBundle,
+        filtered: Result<ProposalBundle, R::Error>,
+    ) -> ProposalBundle {
+        match filtered {
+            Ok(filtered) => {
+                let kept = filtered.into_proposals().collect::<Vec<_>>();
+
+                let removed = proposals
+                    .clone()
+                    .into_proposals()
+                    .filter(|p| !kept.contains(p))
+                    .collect::<Vec<_>>();
+
+                if !removed.is_empty() {
+                    (self.observer)(ProposalAudit::Filtered(removed));
+                }
+            }
+            Err(e) => (self.observer)(ProposalAudit::Rejected(e)),
+        }
+
+        proposals
+    }
+}
+
+#[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+#[cfg_attr(mls_build_async, maybe_async::must_be_async)]
+impl<R, F> MlsRules for AuditProposalFilter<R, F>
+where
+    R: MlsRules,
+    F: Fn(ProposalAudit<R::Error>) + Send + Sync,
+{
+    type Error = R::Error;
+
+    async fn filter_proposals(
+        &self,
+        direction: CommitDirection,
+        source: CommitSource,
+        current_roster: &Roster,
+        extension_list: &ExtensionList,
+        proposals: ProposalBundle,
+    ) -> Result<ProposalBundle, Self::Error> {
+        let filtered = self
+            .inner
+            .filter_proposals(
+                direction,
+                source,
+                current_roster,
+                extension_list,
+                proposals.clone(),
+            )
+            .await;
+
+        Ok(self.audit(proposals, filtered))
+    }
+
+    async fn filter_proposals_with_context(
+        &self,
+        direction: CommitDirection,
+        source: CommitSource,
+        current_roster: &Roster,
+        current_context: &GroupContext,
+        proposals: ProposalBundle,
+    ) -> Result<ProposalBundle, Self::Error> {
+        let filtered = self
+            .inner
+            .filter_proposals_with_context(
+                direction,
+                source,
+                current_roster,
+                current_context,
+                proposals.clone(),
+            )
+            .await;
+
+        Ok(self.audit(proposals, filtered))
+    }
+
+    fn commit_options(
+        &self,
+        new_roster: &Roster,
+        new_extension_list: &ExtensionList,
+        proposals: &ProposalBundle,
+    ) -> Result<CommitOptions, Self::Error> {
+        self.inner
+            .commit_options(new_roster, new_extension_list, proposals)
+    }
+
+    fn encryption_options(
+        &self,
+        current_roster: &Roster,
+        current_extension_list: &ExtensionList,
+    ) -> Result<EncryptionOptions, Self::Error> {
+        self.inner
+            .encryption_options(current_roster, current_extension_list)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(mls_build_async)]
+    use alloc::boxed::Box;
+    use alloc::{sync::Arc, vec, vec::Vec};
+    use assert_matches::assert_matches;
+    use core::convert::Infallible;
+    use mls_rs_core::extension::ExtensionList;
+    use spin::Mutex;
+
+    use crate::{
+        client::{test_utils::TEST_CIPHER_SUITE, MlsError},
+        client_builder::{ClientBuilder, MlsConfig},
+        crypto::test_utils::TestCryptoProvider,
+        group::{proposal::Proposal, proposal_filter::ProposalBundle, GroupContext, Roster},
+        identity::{basic::BasicIdentityProvider, test_utils::get_test_signing_identity},
+        Client,
+    };
+
+    use super::{
+        AuditProposalFilter, CommitDirection, CommitOptions, CommitSource, EncryptionOptions,
+        MlsRules, ProposalAudit,
+    };
+
+    #[derive(Clone, Debug)]
+    struct MaxEpochRules {
+        max_epoch: u64,
+    }
+
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    #[cfg_attr(mls_build_async, maybe_async::must_be_async)]
+    impl MlsRules for MaxEpochRules {
+        type Error = MlsError;
+
+        #[cfg_attr(coverage_nightly, coverage(off))]
+        async fn filter_proposals(
+            &self,
+            _: CommitDirection,
+            _: CommitSource,
+            _: &Roster,
+            _: &ExtensionList,
+            proposals: ProposalBundle,
+        ) -> Result<ProposalBundle, Self::Error> {
+            Ok(proposals)
+        }
+
+        async fn filter_proposals_with_context(
+            &self,
+            _: CommitDirection,
+            _: CommitSource,
+            _: &Roster,
+            current_context: &GroupContext,
+            proposals: ProposalBundle,
+        ) -> Result<ProposalBundle, Self::Error> {
+            (current_context.epoch < self.max_epoch)
+                .then_some(proposals)
+                .ok_or(MlsError::InvalidEpoch)
+        }
+
+        fn commit_options(
+            &self,
+            _: &Roster,
+            _: &ExtensionList,
+            _: &ProposalBundle,
+        ) -> Result<CommitOptions, Self::Error> {
+            Ok(Default::default())
+        }
+
+        fn encryption_options(
+            &self,
+            _: &Roster,
+            _: &ExtensionList,
+        ) -> Result<EncryptionOptions, Self::Error> {
+            Ok(Default::default())
+        }
+    }
+
+    #[derive(Clone, Debug)]
+    struct DropAllRules;
+
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    #[cfg_attr(mls_build_async, maybe_async::must_be_async)]
+    impl MlsRules for DropAllRules {
+        type Error = Infallible;
+
+        async fn filter_proposals(
+            &self,
+            _: CommitDirection,
+            _: CommitSource,
+            _: &Roster,
+            _: &ExtensionList,
+            _: ProposalBundle,
+        ) -> Result<ProposalBundle, Self::Error> {
+            Ok(ProposalBundle::default())
+        }
+
+        fn commit_options(
+            &self,
+            _: &Roster,
+            _: &ExtensionList,
+            _: &ProposalBundle,
+        ) -> Result<CommitOptions, Self::Error> {
+            Ok(Default::default())
+        }
+
+        fn encryption_options(
+            &self,
+            _: &Roster,
+            _: &ExtensionList,
+        ) -> Result<EncryptionOptions, Self::Error> {
+            Ok(Default::default())
+        }
+    }
+
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    async fn client_with_rules<R: MlsRules + Clone>(
+        mls_rules: R,
+        name: &[u8],
+    ) -> Client<impl MlsConfig> {
+        let (signing_identity, signer) = get_test_signing_identity(TEST_CIPHER_SUITE, name).await;
+
+        ClientBuilder::new()
+            .crypto_provider(TestCryptoProvider::new())
+            .identity_provider(BasicIdentityProvider::new())
+            .signing_identity(signing_identity, signer, TEST_CIPHER_SUITE)
+            .mls_rules(mls_rules)
+            .build()
+    }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn rules_can_read_epoch_from_group_context() {
+        let mut group = client_with_rules(MaxEpochRules { max_epoch: 1 }, b"alice")
+            .await
+            .create_group(Default::default())
+            .await
+            .unwrap();
+
+        group.commit(vec![]).await.unwrap();
+        group.apply_pending_commit().await.unwrap();
+
+        let res = group.commit(vec![]).await.map(|_| ());
+
+        assert_matches!(res, Err(MlsError::MlsRulesError(_)));
+        assert_eq!(group.current_epoch(), 1);
+    }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn audit_filter_reports_filtered_proposals_without_removing_them() {
+        let audits = Arc::new(Mutex::new(Vec::new()));
+        let observed = audits.clone();
+
+        let rules = AuditProposalFilter::new(DropAllRules)
+            .with_observer(move |audit| observed.lock().push(audit));
+
+        let mut alice = client_with_rules(rules, b"alice")
+            .await
+            .create_group(Default::default())
+            .await
+            .unwrap();
+
+        let key_package = client_with_rules(DropAllRules, b"bob")
+            .await
+            .generate_key_package_message()
+            .await
+            .unwrap();
+
+        alice
+            .commit_builder()
+            .add_member(key_package)
+            .unwrap()
+            .build()
+            .await
+            .unwrap();
+
+        alice.apply_pending_commit().await.unwrap();
+
+        assert_eq!(alice.roster().members_iter().count(), 2);
+
+        // The filter runs when building the commit and again when applying it.
+        let audits = audits.lock();
+        assert_eq!(audits.len(), 2);
+
+        audits.iter().for_each(|audit| {
+            assert_matches!(
+                audit,
+                ProposalAudit::Filtered(removed)
+                    if matches!(&removed[..], [p] if matches!(p.proposal, Proposal::Add(_)))
+            )
+        });
+    }
+}
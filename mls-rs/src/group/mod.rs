@@ -11,6 +11,7 @@ use mls_rs_core::error::IntoAnyError;
 use mls_rs_core::extension::MlsExtension;
 use mls_rs_core::secret::Secret;
 use mls_rs_core::time::MlsTime;
+use zeroize::Zeroizing;
 
 use crate::cipher_suite::CipherSuite;
 use crate::client::MlsError;
@@ -23,6 +24,8 @@ use crate::identity::SigningIdentity;
 use crate::key_package::{KeyPackage, KeyPackageGeneration, KeyPackageRef};
 use crate::protocol_version::ProtocolVersion;
 use crate::psk::secret::PskSecret;
+#[cfg(feature = "psk")]
+use crate::psk::PreSharedKey;
 use crate::psk::PreSharedKeyID;
 use crate::signer::Signable;
 use crate::tree_kem::hpke_encryption::HpkeEncryptable;
@@ -31,7 +34,7 @@ use crate::tree_kem::node::LeafIndex;
 use crate::tree_kem::path_secret::PathSecret;
 pub use crate::tree_kem::Capabilities;
 use crate::tree_kem::{
-    leaf_node::LeafNode,
+    leaf_node::{LeafNode, LeafNodeSigningContext, LeafNodeSource},
     leaf_node_validator::{LeafNodeValidator, ValidationContext},
 };
 use crate::tree_kem::{math as tree_math, ValidatedUpdatePath};
@@ -42,6 +45,9 @@ pub use state::GroupState;
 #[cfg(feature = "by_ref_proposal")]
 use crate::crypto::{HpkePublicKey, HpkeSecretKey};
 
+#[cfg(feature = "by_ref_proposal")]
+use crate::extension::ExternalSendersExt;
+
 use crate::extension::ExternalPubExt;
 
 use self::message_hash::MessageHash;
@@ -97,8 +103,10 @@ use self::state_repo::GroupStateRepository;
 pub use group_info::GroupInfo;
 
 pub use self::framing::{ContentType, Sender};
+pub use capability_gap::CapabilityGap;
 pub use commit::*;
 pub use context::GroupContext;
+pub use extension_compliance::ExtensionComplianceReport;
 pub use roster::*;
 
 pub(crate) use transcript_hash::ConfirmedTranscriptHash;
@@ -108,16 +116,20 @@ pub(crate) use util::*;
 pub use self::message_processor::CachedProposal;
 
 #[cfg(feature = "private_message")]
-mod ciphertext_processor;
+pub(crate) mod ciphertext_processor;
 
+mod capability_gap;
 mod commit;
 pub(crate) mod confirmation_tag;
 mod context;
 pub(crate) mod epoch;
+mod extension_compliance;
 pub(crate) mod framing;
 mod group_info;
 pub(crate) mod key_schedule;
 mod membership_tag;
+#[cfg(feature = "private_message")]
+mod message_dedup;
 pub(crate) mod message_hash;
 pub(crate) mod message_processor;
 pub(crate) mod message_signature;
@@ -163,6 +175,8 @@ mod exported_tree;
 
 pub use exported_tree::ExportedTree;
 
+const NONCE_LABEL_PREFIX: &[u8] = b"nonce ";
+
 #[derive(Clone, Debug, PartialEq, MlsSize, MlsEncode, MlsDecode)]
 struct GroupSecrets {
     joiner_secret: JoinerSecret,
@@ -191,11 +205,18 @@ pub(crate) struct EncryptedGroupSecrets {
 
 #[derive(Clone, Eq, PartialEq, MlsSize, MlsEncode, MlsDecode)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
-pub(crate) struct Welcome {
-    pub cipher_suite: CipherSuite,
-    pub secrets: Vec<EncryptedGroupSecrets>,
+#[cfg_attr(
+    all(feature = "ffi", not(test)),
+    safer_ffi_gen::ffi_type(clone, opaque)
+)]
+/// A welcome message allowing new members to join a group.
+///
+/// Obtained from an [`MlsMessage`] with [`MlsMessage::into_welcome`].
+pub struct Welcome {
+    pub(crate) cipher_suite: CipherSuite,
+    pub(crate) secrets: Vec<EncryptedGroupSecrets>,
     #[mls_codec(with = "mls_rs_codec::byte_vec")]
-    pub encrypted_group_info: Vec<u8>,
+    pub(crate) encrypted_group_info: Vec<u8>,
 }
 
 impl Debug for Welcome {
@@ -211,6 +232,26 @@ impl Debug for Welcome {
     }
 }
 
+#[cfg_attr(all(feature = "ffi", not(test)), ::safer_ffi_gen::safer_ffi_gen)]
+impl Welcome {
+    /// Cipher suite of the group to join.
+    pub fn cipher_suite(&self) -> CipherSuite {
+        self.cipher_suite
+    }
+
+    /// Key package references of all members who can join using this
+    /// message.
+    #[cfg_attr(all(feature = "ffi", not(test)), safer_ffi_gen::safer_ffi_gen_ignore)]
+    pub fn key_package_references(&self) -> Vec<&KeyPackageRef> {
+        self.secrets.iter().map(|s| &s.new_member).collect()
+    }
+
+    /// Number of encrypted group secrets, one for each joining member.
+    pub fn secret_count(&self) -> usize {
+        self.secrets.len()
+    }
+}
+
 #[derive(Clone, Debug)]
 #[cfg_attr(
     all(feature = "ffi", not(test)),
@@ -266,6 +307,7 @@ where
     C: ClientConfig,
 {
     config: C,
+    mls_rules_override: Option<C::MlsRules>,
     cipher_suite_provider: <C::CryptoProvider as CryptoProvider>::CipherSuiteProvider,
     state_repo: GroupStateRepository<C::GroupStateStorage, C::KeyPackageRepository>,
     pub(crate) state: GroupState,
@@ -278,6 +320,14 @@ where
     pending_commit: Option<CommitGeneration>,
     #[cfg(feature = "psk")]
     previous_psk: Option<PskSecretInput>,
+    #[cfg(feature = "psk")]
+    epoch_external_psks: Vec<ExternalPskId>,
+    #[cfg(feature = "private_message")]
+    received_messages: message_dedup::MessageDedupCache,
+    buffered_commits: Vec<(MessageHash, MlsMessage)>,
+    applied_buffered_commits: Vec<ReceivedMessage>,
+    removed: bool,
+    last_own_commit: Option<CommitMessageDescription>,
     #[cfg(test)]
     pub(crate) commit_modifiers: CommitModifiers,
     pub(crate) signer: SignatureSecretKey,
@@ -382,6 +432,13 @@ where
             #[cfg(feature = "by_ref_proposal")]
             pending_updates: Default::default(),
             pending_commit: None,
+            mls_rules_override: None,
+            #[cfg(feature = "private_message")]
+            received_messages: Default::default(),
+            buffered_commits: Default::default(),
+            applied_buffered_commits: Default::default(),
+            removed: false,
+            last_own_commit: None,
             #[cfg(test)]
             commit_modifiers: Default::default(),
             epoch_secrets: key_schedule_result.epoch_secrets,
@@ -389,6 +446,8 @@ where
             cipher_suite_provider,
             #[cfg(feature = "psk")]
             previous_psk: None,
+            #[cfg(feature = "psk")]
+            epoch_external_psks: Vec::new(),
             signer,
         })
     }
@@ -439,6 +498,7 @@ where
             welcome.version,
             &group_info,
             tree_data,
+            config.max_ratchet_tree_size(),
             &id_provider,
             &cipher_suite_provider,
         )
@@ -504,7 +564,8 @@ where
             return Err(MlsError::InvalidConfirmationTag);
         }
 
-        Self::join_with(
+        #[cfg_attr(not(feature = "psk"), allow(unused_mut))]
+        let (mut group, new_member_info) = Self::join_with(
             config,
             group_info,
             public_tree,
@@ -514,7 +575,14 @@ where
             used_key_package_ref,
             signer,
         )
-        .await
+        .await?;
+
+        #[cfg(feature = "psk")]
+        {
+            group.epoch_external_psks = external_psk_ids(&group_secrets.psks);
+        }
+
+        Ok((group, new_member_info))
     }
 
     #[allow(clippy::too_many_arguments)]
@@ -566,6 +634,13 @@ where
             #[cfg(feature = "by_ref_proposal")]
             pending_updates: Default::default(),
             pending_commit: None,
+            mls_rules_override: None,
+            #[cfg(feature = "private_message")]
+            received_messages: Default::default(),
+            buffered_commits: Default::default(),
+            applied_buffered_commits: Default::default(),
+            removed: false,
+            last_own_commit: None,
             #[cfg(test)]
             commit_modifiers: Default::default(),
             epoch_secrets,
@@ -573,6 +648,8 @@ where
             cipher_suite_provider: cs,
             #[cfg(feature = "psk")]
             previous_psk: None,
+            #[cfg(feature = "psk")]
+            epoch_external_psks: Vec::new(),
             signer,
         };
 
@@ -622,6 +699,41 @@ where
         self.group_state().member_at_index(index)
     }
 
+    /// The way the current leaf node of the member at `index` was
+    /// established, or `None` if there is no member at `index`.
+    #[cfg_attr(all(feature = "ffi", not(test)), safer_ffi_gen::safer_ffi_gen_ignore)]
+    pub fn member_leaf_source(&self, index: u32) -> Option<LeafNodeSourceKind> {
+        self.state
+            .public_tree
+            .get_leaf_node(LeafIndex(index))
+            .ok()
+            .map(|leaf_node| (&leaf_node.leaf_node_source).into())
+    }
+
+    /// Verify the signature of the leaf node of the member at `index`
+    /// against the signing identity contained in that leaf node.
+    ///
+    /// This only checks the signature. The credential itself is validated
+    /// by the [`IdentityProvider`](crate::IdentityProvider) when the leaf
+    /// node is added to the tree.
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    pub async fn verify_leaf_signature(&self, index: u32) -> Result<(), MlsError> {
+        let leaf_node = self.state.public_tree.get_leaf_node(LeafIndex(index))?;
+
+        let context = match leaf_node.leaf_node_source {
+            LeafNodeSource::KeyPackage(_) => LeafNodeSigningContext::default(),
+            _ => (self.group_id(), index).into(),
+        };
+
+        leaf_node
+            .verify(
+                &self.cipher_suite_provider,
+                &leaf_node.signing_identity.signature_key,
+                &context,
+            )
+            .await
+    }
+
     #[cfg(feature = "by_ref_proposal")]
     #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
     async fn proposal_message(
@@ -661,6 +773,23 @@ where
         Ok(message)
     }
 
+    /// Replace the [`MlsRules`](crate::MlsRules) used by this group for subsequent commits and
+    /// message processing.
+    ///
+    /// The rules given to the client builder are used until this function is
+    /// called. The replacement only applies to this instance of the group and
+    /// is not persisted with the group state.
+    #[cfg_attr(all(feature = "ffi", not(test)), safer_ffi_gen::safer_ffi_gen_ignore)]
+    pub fn set_mls_rules(&mut self, mls_rules: C::MlsRules) {
+        self.mls_rules_override = Some(mls_rules);
+    }
+
+    pub(crate) fn current_mls_rules(&self) -> C::MlsRules {
+        self.mls_rules_override
+            .clone()
+            .unwrap_or_else(|| self.config.mls_rules())
+    }
+
     /// Unique identifier for this group.
     pub fn group_id(&self) -> &[u8] {
         &self.context().group_id
@@ -942,6 +1071,9 @@ where
     /// In particular, the members who joined between `psk_epoch` and the
     /// current epoch cannot process a commit containing this proposal.
     ///
+    /// Fails with [`MlsError::OldGroupStateNotFound`] if the secret state from
+    /// `psk_epoch` is not available to this member.
+    ///
     /// `authenticated_data` will be sent unencrypted along with the contents
     /// of the proposal message.
     #[cfg(all(feature = "by_ref_proposal", feature = "psk"))]
@@ -957,6 +1089,17 @@ where
             psk_group_id: PskGroupId(self.group_id().to_vec()),
         };
 
+        let epoch_available = psk_epoch == self.current_epoch();
+
+        #[cfg(feature = "prior_epoch")]
+        let epoch_available = epoch_available
+            || (psk_epoch < self.current_epoch()
+                && self.state_repo.resumption_secret(&key_id).await?.is_some());
+
+        if !epoch_available {
+            return Err(MlsError::OldGroupStateNotFound);
+        }
+
         let proposal = self.psk_proposal(JustPreSharedKeyID::Resumption(key_id))?;
         self.proposal_message(proposal, authenticated_data).await
     }
@@ -1029,6 +1172,41 @@ where
         self.proposal_message(proposal, authenticated_data).await
     }
 
+    /// Create a proposal message that replaces the group's
+    /// [`ExternalSendersExt`] with `external_senders`.
+    ///
+    /// All other group context extensions are kept. Each new external
+    /// sender is validated by the
+    /// [`IdentityProvider`](crate::IdentityProvider) before the proposal is
+    /// created.
+    ///
+    /// `authenticated_data` will be sent unencrypted along with the contents
+    /// of the proposal message.
+    #[cfg(feature = "by_ref_proposal")]
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    pub async fn propose_external_senders(
+        &mut self,
+        external_senders: ExternalSendersExt,
+        authenticated_data: Vec<u8>,
+    ) -> Result<MlsMessage, MlsError> {
+        let mut extensions = self.context().extensions.clone();
+        extensions.set_from(external_senders.clone())?;
+
+        #[cfg(feature = "std")]
+        let time = Some(MlsTime::now());
+
+        #[cfg(not(feature = "std"))]
+        let time = None;
+
+        external_senders
+            .verify_all(&self.config.identity_provider(), time, &extensions)
+            .await
+            .map_err(|e| MlsError::IdentityProviderError(e.into_any_error()))?;
+
+        self.propose_group_context_extensions(extensions, authenticated_data)
+            .await
+    }
+
     fn group_context_extensions_proposal(&self, extensions: ExtensionList) -> Proposal {
         Proposal::GroupContextExtensions(extensions)
     }
@@ -1048,6 +1226,40 @@ where
             .await
     }
 
+    /// Create a proposal message for each of the given `proposals`.
+    ///
+    /// The MLS framing carries a single proposal per message, so one message
+    /// is returned for each proposal, in the same order. They can be delivered
+    /// together to reduce round trips. Proposals that were previously
+    /// constructed, such as the ones returned by
+    /// [`NewEpoch::unused_proposals`](crate::group::NewEpoch::unused_proposals),
+    /// can be proposed again this way.
+    ///
+    /// If creating a message fails, the messages created before the failure
+    /// remain in the proposal cache.
+    ///
+    /// `authenticated_data` will be sent unencrypted along with the contents
+    /// of each proposal message.
+    #[cfg(feature = "by_ref_proposal")]
+    #[cfg_attr(all(feature = "ffi", not(test)), safer_ffi_gen::safer_ffi_gen_ignore)]
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    pub async fn propose_batch(
+        &mut self,
+        proposals: Vec<Proposal>,
+        authenticated_data: Vec<u8>,
+    ) -> Result<Vec<MlsMessage>, MlsError> {
+        let mut messages = Vec::with_capacity(proposals.len());
+
+        for proposal in proposals {
+            messages.push(
+                self.proposal_message(proposal, authenticated_data.clone())
+                    .await?,
+            );
+        }
+
+        Ok(messages)
+    }
+
     /// Delete all sent and received proposals cached for commit.
     #[cfg(feature = "by_ref_proposal")]
     pub fn clear_proposal_cache(&mut self) {
@@ -1059,6 +1271,10 @@ where
         &mut self,
         content: AuthenticatedContent,
     ) -> Result<MlsMessage, MlsError> {
+        if self.removed {
+            return Err(MlsError::RemovedFromGroup);
+        }
+
         #[cfg(feature = "private_message")]
         let payload = if content.wire_format == WireFormat::PrivateMessage {
             MlsMessagePayload::Cipher(self.create_ciphertext(content).await?)
@@ -1148,9 +1364,10 @@ where
         let epoch_id = message.epoch;
 
         let auth_content = if epoch_id == self.context().epoch {
-            let content = CiphertextProcessor::new(self, self.cipher_suite_provider.clone())
-                .open(message)
-                .await?;
+            let (content, generation) =
+                CiphertextProcessor::new(self, self.cipher_suite_provider.clone())
+                    .open_with_generation(message)
+                    .await?;
 
             verify_auth_content_signature(
                 &self.cipher_suite_provider,
@@ -1162,6 +1379,20 @@ where
             )
             .await?;
 
+            // Only authenticated messages count towards duplicate detection
+            if let (Some(capacity), Sender::Member(sender), Content::Application(_)) = (
+                self.config.message_dedup_capacity(),
+                content.content.sender,
+                &content.content.content,
+            ) {
+                self.received_messages.insert(
+                    self.context().epoch,
+                    LeafIndex(sender),
+                    generation,
+                    capacity,
+                )?;
+            }
+
             Ok::<_, MlsError>(content)
         } else {
             #[cfg(feature = "prior_epoch")]
@@ -1207,7 +1438,22 @@ where
             .content
             .clone();
 
-        self.process_commit(content, None).await
+        let description = self.process_commit(content, None).await?;
+        self.last_own_commit = Some(description.clone());
+
+        Ok(description)
+    }
+
+    /// Description of the last commit created and applied by this member
+    /// with [`Group::apply_pending_commit`], including the applied
+    /// proposals and the resulting epoch.
+    ///
+    /// This is available regardless of the wire format used to send the
+    /// commit, allowing the committer to log encrypted commits. It is kept in
+    /// memory only.
+    #[cfg_attr(all(feature = "ffi", not(test)), safer_ffi_gen::safer_ffi_gen_ignore)]
+    pub fn last_commit_description(&self) -> Option<CommitMessageDescription> {
+        self.last_own_commit.clone()
     }
 
     /// Apply a detached commit that was created by [`Group::commit_detached`] or
@@ -1257,6 +1503,24 @@ where
     pub async fn process_incoming_message(
         &mut self,
         message: MlsMessage,
+    ) -> Result<ReceivedMessage, MlsError> {
+        if let Some(epoch) = self.future_commit_epoch(&message) {
+            return self.buffer_commit(message, epoch).await;
+        }
+
+        let received = self.process_current_message(message).await?;
+
+        if matches!(received, ReceivedMessage::Commit(_)) {
+            self.apply_buffered_commits().await;
+        }
+
+        Ok(received)
+    }
+
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    async fn process_current_message(
+        &mut self,
+        message: MlsMessage,
     ) -> Result<ReceivedMessage, MlsError> {
         if let Some(pending) = &self.pending_commit {
             let message_hash = MessageHash::compute(&self.cipher_suite_provider, &message).await?;
@@ -1281,13 +1545,17 @@ where
             }
         }
 
-        MessageProcessor::process_incoming_message(
+        let received = MessageProcessor::process_incoming_message(
             self,
             message,
             #[cfg(feature = "by_ref_proposal")]
             true,
         )
-        .await
+        .await?;
+
+        self.removed |= is_own_removal(&received);
+
+        Ok(received)
     }
 
     /// Process an inbound message for this group, providing additional context
@@ -1312,14 +1580,131 @@ where
         message: MlsMessage,
         time: MlsTime,
     ) -> Result<ReceivedMessage, MlsError> {
-        MessageProcessor::process_incoming_message_with_time(
+        if let Some(epoch) = self.future_commit_epoch(&message) {
+            return self.buffer_commit(message, epoch).await;
+        }
+
+        let received = MessageProcessor::process_incoming_message_with_time(
             self,
             message,
             #[cfg(feature = "by_ref_proposal")]
             true,
             Some(time),
         )
-        .await
+        .await?;
+
+        self.removed |= is_own_removal(&received);
+
+        if matches!(received, ReceivedMessage::Commit(_)) {
+            self.apply_buffered_commits().await;
+        }
+
+        Ok(received)
+    }
+
+    /// Returns `false` once this member processed a commit removing it from
+    /// the group.
+    ///
+    /// Such a commit is reported with
+    /// [`CommitEffect::Removed`]. An
+    /// inactive group can no longer send messages and any attempt fails with
+    /// [`MlsError::RemovedFromGroup`]. This status is persisted by
+    /// [`Group::write_to_storage`].
+    pub fn is_active(&self) -> bool {
+        !self.removed
+    }
+
+    /// Take the results of commits that were applied from the future commit
+    /// buffer, oldest first.
+    ///
+    /// Commits received for a future epoch are buffered if enabled by
+    /// [`ClientBuilder::future_commit_buffer_size`](crate::client_builder::ClientBuilder::future_commit_buffer_size).
+    /// Each time a commit is processed, buffered commits for the new epoch
+    /// are applied until none is left, and buffered commits for past epochs
+    /// are discarded. A buffered commit that fails to process is discarded
+    /// as well.
+    ///
+    /// Buffered commits and their results are kept in memory only and are
+    /// lost when the group is written with [`Group::write_to_storage`] and
+    /// loaded again.
+    #[cfg_attr(all(feature = "ffi", not(test)), safer_ffi_gen::safer_ffi_gen_ignore)]
+    pub fn take_applied_buffered_commits(&mut self) -> Vec<ReceivedMessage> {
+        core::mem::take(&mut self.applied_buffered_commits)
+    }
+
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    async fn apply_buffered_commits(&mut self) {
+        loop {
+            let epoch = self.context().epoch;
+
+            self.buffered_commits
+                .retain(|(_, commit)| matches!(commit.epoch(), Some(e) if e >= epoch));
+
+            let Some(index) = self
+                .buffered_commits
+                .iter()
+                .position(|(_, commit)| commit.epoch() == Some(epoch))
+            else {
+                return;
+            };
+
+            let (_, commit) = self.buffered_commits.remove(index);
+
+            if let Ok(received) = self.process_current_message(commit).await {
+                self.applied_buffered_commits.push(received);
+            }
+        }
+    }
+
+    fn future_commit_epoch(&self, message: &MlsMessage) -> Option<u64> {
+        let capacity = self.config.future_commit_buffer_size()?;
+
+        let epoch = message.epoch()?;
+        let current_epoch = self.context().epoch;
+
+        let is_future_commit = message.content_type() == Some(ContentType::Commit)
+            && message.group_id() == Some(self.group_id())
+            && epoch > current_epoch
+            && epoch - current_epoch <= capacity as u64;
+
+        is_future_commit.then_some(epoch)
+    }
+
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    async fn buffer_commit(
+        &mut self,
+        message: MlsMessage,
+        epoch: u64,
+    ) -> Result<ReceivedMessage, MlsError> {
+        let message_hash = MessageHash::compute(&self.cipher_suite_provider, &message).await?;
+
+        if self
+            .buffered_commits
+            .iter()
+            .any(|(h, _)| h == &message_hash)
+        {
+            return Ok(ReceivedMessage::BufferedCommit { epoch });
+        }
+
+        let capacity = self.config.future_commit_buffer_size().unwrap_or_default();
+
+        if self.buffered_commits.len() >= capacity {
+            // Evict the commit the group would reach last, unless that is the new one
+            let farthest = self
+                .buffered_commits
+                .iter()
+                .enumerate()
+                .max_by_key(|(_, (_, commit))| commit.epoch())
+                .filter(|(_, (_, commit))| commit.epoch() > Some(epoch))
+                .map(|(index, _)| index)
+                .ok_or(MlsError::CommitBufferFull(capacity))?;
+
+            self.buffered_commits.remove(farthest);
+        }
+
+        self.buffered_commits.push((message_hash, message));
+
+        Ok(ReceivedMessage::BufferedCommit { epoch })
     }
 
     /// Find a group member by
@@ -1374,6 +1759,22 @@ where
             .await
     }
 
+    /// Serialized public state of the group, for servers that cache it and
+    /// serve it to external observers and external joiners.
+    ///
+    /// This is the encoding of the message returned by
+    /// [`group_info_message_allowing_ext_commit`](Self::group_info_message_allowing_ext_commit)
+    /// with the ratchet tree included. It is signed by the current member and
+    /// contains no secrets. It can be loaded with
+    /// [`ExternalClient::observe_group_bytes`](crate::external_client::ExternalClient::observe_group_bytes)
+    /// or parsed with [`MlsMessage::from_bytes`].
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    pub async fn public_group_state_bytes(&self) -> Result<Vec<u8>, MlsError> {
+        self.group_info_message_allowing_ext_commit(true)
+            .await?
+            .to_bytes()
+    }
+
     /// Create a group info message that can be used for external proposals.
     #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
     pub async fn group_info_message(
@@ -1441,6 +1842,72 @@ where
             .map(Into::into)
     }
 
+    /// Derive a `len` bytes long nonce bound to `label` and the current epoch.
+    ///
+    /// The nonce is exported from the current epoch's exporter secret, with
+    /// `label` prefixed by `"nonce "` and the epoch number as context. The
+    /// prefix keeps nonces distinct from secrets obtained with
+    /// [`export_secret`](Self::export_secret) for the same label. All members
+    /// of the group derive the same nonce for the same label within an epoch,
+    /// and nonces from different epochs are unrelated.
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    pub async fn derive_nonce(&self, label: &[u8], len: usize) -> Result<Vec<u8>, MlsError> {
+        let label = [NONCE_LABEL_PREFIX, label].concat();
+        let context = self.current_epoch().to_be_bytes();
+
+        self.key_schedule
+            .export_secret(&label, &context, len, &self.cipher_suite_provider)
+            .await
+            .map(|nonce| nonce.to_vec())
+    }
+
+    /// Verify the parent hash of every non-blank parent node in the current
+    /// epoch's ratchet tree.
+    ///
+    /// This check is already performed when a tree is imported. It is exposed
+    /// separately to help debug tree integrity issues.
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    pub async fn verify_parent_hashes(&self) -> Result<(), MlsError> {
+        self.current_epoch_tree()
+            .validate_parent_hashes(&self.cipher_suite_provider)
+            .await
+    }
+
+    /// Hash of the current epoch's group context.
+    ///
+    /// The group context includes the tree hash and the confirmed transcript
+    /// hash. Members that processed different commits for the same epoch
+    /// number therefore get different hashes, which can be compared to detect
+    /// that they diverged.
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    pub async fn epoch_hash(&self) -> Result<Vec<u8>, MlsError> {
+        self.cipher_suite_provider
+            .hash(&self.context().mls_encode_to_vec()?)
+            .await
+            .map_err(|e| MlsError::CryptoProviderError(e.into_any_error()))
+    }
+
+    /// The resumption PSK derived for the current epoch.
+    ///
+    /// This is the value used when a later epoch or another group refers to
+    /// this epoch with a resumption PSK. It can be provided to other
+    /// subsystems that need to share a secret bound to this epoch.
+    #[cfg(feature = "psk")]
+    pub fn current_resumption_psk(&self) -> Result<PreSharedKey, MlsError> {
+        Ok(self.epoch_secrets.resumption_secret.clone())
+    }
+
+    /// External PSKs injected into the key schedule of the current epoch.
+    ///
+    /// These PSKs were required to process the commit or Welcome message
+    /// that started the current epoch. This list is persisted by
+    /// [`Group::write_to_storage`].
+    #[cfg(feature = "psk")]
+    #[cfg_attr(all(feature = "ffi", not(test)), safer_ffi_gen::safer_ffi_gen_ignore)]
+    pub fn referenced_external_psk_ids(&self) -> Vec<ExternalPskId> {
+        self.epoch_external_psks.clone()
+    }
+
     /// Export the current epoch's ratchet tree in serialized format.
     ///
     /// This function is used to provide the current group tree to new members
@@ -1449,6 +1916,21 @@ where
         ExportedTree::new_borrowed(&self.current_epoch_tree().nodes)
     }
 
+    /// Export the current epoch's ratchet tree in serialized format together
+    /// with its tree hash.
+    ///
+    /// GroupInfo messages reference the tree through the tree hash in their
+    /// group context, so the tree can be delivered separately as a blob
+    /// addressed by this hash. See
+    /// [`ExternalClient::observe_group_with_tree`](crate::external_client::ExternalClient::observe_group_with_tree).
+    #[cfg_attr(all(feature = "ffi", not(test)), safer_ffi_gen::safer_ffi_gen_ignore)]
+    pub fn export_tree_with_hash(&self) -> Result<(Vec<u8>, Vec<u8>), MlsError> {
+        Ok((
+            self.export_tree().to_bytes()?,
+            self.context().tree_hash.clone(),
+        ))
+    }
+
     /// Current version of the MLS protocol in use by this group.
     pub fn protocol_version(&self) -> ProtocolVersion {
         self.context().protocol_version
@@ -1502,10 +1984,19 @@ where
         }
     }
 
+    /// Returns `true` if control messages sent by this member are encrypted
+    /// according to [`MlsRules::encryption_options`](crate::MlsRules::encryption_options).
+    ///
+    /// Groups using encrypted control messages can not be followed by an
+    /// [`ExternalClient`](crate::external_client::ExternalClient).
+    #[cfg(feature = "private_message")]
+    pub fn encrypts_control_messages(&self) -> Result<bool, MlsError> {
+        Ok(self.encryption_options()?.encrypt_control_messages)
+    }
+
     #[cfg(feature = "private_message")]
     pub(crate) fn encryption_options(&self) -> Result<EncryptionOptions, MlsError> {
-        self.config
-            .mls_rules()
+        self.current_mls_rules()
             .encryption_options(&self.roster(), self.group_context().extensions())
             .map_err(|e| MlsError::MlsRulesError(e.into_any_error()))
     }
@@ -1619,16 +2110,24 @@ impl<C: ClientConfig> Group<C> {
     ) -> Result<(GroupInfo, KeyPackageGeneration, GroupSecrets, PskSecret), MlsError> {
         let protocol_version = welcome.version;
 
-        if !config.version_supported(protocol_version) {
-            return Err(MlsError::UnsupportedProtocolVersion(protocol_version));
-        }
-
         let MlsMessagePayload::Welcome(welcome) = &welcome.payload else {
             return Err(MlsError::UnexpectedMessageType);
         };
 
-        let cipher_suite_provider =
-            cipher_suite_provider(config.crypto_provider(), welcome.cipher_suite)?;
+        check_min_protocol_version(protocol_version, config.min_protocol_version())?;
+
+        let crypto_provider = config.crypto_provider();
+
+        let cipher_suite_provider = config
+            .version_supported(protocol_version)
+            .then(|| crypto_provider.cipher_suite_provider(welcome.cipher_suite))
+            .flatten()
+            .ok_or_else(|| MlsError::UnsupportedWelcome {
+                cipher_suite: welcome.cipher_suite,
+                protocol_version,
+                supported_cipher_suites: crypto_provider.supported_cipher_suites(),
+                supported_protocol_versions: config.supported_protocol_versions(),
+            })?;
 
         let (encrypted_group_secrets, key_package_generation) =
             find_key_package_generation(&config.key_package_repo(), &welcome.secrets).await?;
@@ -1685,39 +2184,126 @@ impl<C: ClientConfig> Group<C> {
             psk_secret,
         ))
     }
-}
 
-#[cfg(feature = "private_message")]
-impl<C> GroupStateProvider for Group<C>
-where
-    C: ClientConfig + Clone,
-{
-    fn group_context(&self) -> &GroupContext {
-        self.context()
-    }
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    pub(crate) async fn welcome_secret(
+        welcome: &MlsMessage,
+        config: &C,
+        key_package_ref: &KeyPackageRef,
+    ) -> Result<Zeroizing<Vec<u8>>, MlsError> {
+        let MlsMessagePayload::Welcome(welcome) = &welcome.payload else {
+            return Err(MlsError::UnexpectedMessageType);
+        };
 
-    fn self_index(&self) -> LeafIndex {
-        self.private_tree.self_index
-    }
+        let cipher_suite_provider =
+            cipher_suite_provider(config.crypto_provider(), welcome.cipher_suite)?;
 
-    fn epoch_secrets_mut(&mut self) -> &mut EpochSecrets {
-        &mut self.epoch_secrets
-    }
+        let secrets = welcome
+            .secrets
+            .iter()
+            .find(|secrets| &secrets.new_member == key_package_ref)
+            .map(core::slice::from_ref)
+            .ok_or(MlsError::WelcomeKeyPackageNotFound)?;
 
-    fn epoch_secrets(&self) -> &EpochSecrets {
-        &self.epoch_secrets
-    }
-}
+        let (encrypted_group_secrets, key_package_generation) =
+            find_key_package_generation(&config.key_package_repo(), secrets).await?;
 
-#[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
-#[cfg_attr(all(target_arch = "wasm32", mls_build_async), maybe_async::must_be_async(?Send))]
-#[cfg_attr(
-    all(not(target_arch = "wasm32"), mls_build_async),
-    maybe_async::must_be_async
-)]
-impl<C> MessageProcessor for Group<C>
-where
-    C: ClientConfig + Clone,
+        let group_secrets = GroupSecrets::decrypt(
+            &cipher_suite_provider,
+            &key_package_generation.init_secret_key,
+            &key_package_generation.key_package.hpke_init_key,
+            &welcome.encrypted_group_info,
+            &encrypted_group_secrets.encrypted_group_secrets,
+        )
+        .await?;
+
+        let psk_secret = Self::psk_secret(
+            config,
+            &cipher_suite_provider,
+            &group_secrets.psks,
+            #[cfg(feature = "psk")]
+            None,
+        )
+        .await?;
+
+        get_welcome_secret(
+            &cipher_suite_provider,
+            &group_secrets.joiner_secret,
+            &psk_secret,
+        )
+        .await
+    }
+}
+
+#[cfg(feature = "psk")]
+fn external_psk_ids(psks: &[PreSharedKeyID]) -> Vec<ExternalPskId> {
+    psks.iter()
+        .filter_map(|psk| match &psk.key_id {
+            JustPreSharedKeyID::External(id) => Some(id.clone()),
+            JustPreSharedKeyID::Resumption(_) => None,
+        })
+        .collect()
+}
+
+fn is_own_removal(message: &ReceivedMessage) -> bool {
+    matches!(
+        message,
+        ReceivedMessage::Commit(CommitMessageDescription {
+            effect: CommitEffect::Removed { .. },
+            ..
+        })
+    )
+}
+
+#[cfg(feature = "private_message")]
+impl<C> GroupStateProvider for Group<C>
+where
+    C: ClientConfig + Clone,
+{
+    fn group_context(&self) -> &GroupContext {
+        self.context()
+    }
+
+    fn self_index(&self) -> LeafIndex {
+        self.private_tree.self_index
+    }
+
+    fn epoch_secrets_mut(&mut self) -> &mut EpochSecrets {
+        &mut self.epoch_secrets
+    }
+
+    fn epoch_secrets(&self) -> &EpochSecrets {
+        &self.epoch_secrets
+    }
+
+    fn check_application_message(
+        &self,
+        sender: LeafIndex,
+        generation: u32,
+    ) -> Result<(), MlsError> {
+        if self.config.message_dedup_capacity().is_none() {
+            return Ok(());
+        }
+
+        self.received_messages
+            .check(self.context().epoch, sender, generation)
+    }
+
+    #[cfg(feature = "out_of_order")]
+    fn secret_tree_cache_size(&self) -> Option<usize> {
+        self.config.secret_tree_cache_size()
+    }
+}
+
+#[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+#[cfg_attr(all(target_arch = "wasm32", mls_build_async), maybe_async::must_be_async(?Send))]
+#[cfg_attr(
+    all(not(target_arch = "wasm32"), mls_build_async),
+    maybe_async::must_be_async
+)]
+impl<C> MessageProcessor for Group<C>
+where
+    C: ClientConfig + Clone,
 {
     type MlsRules = C::MlsRules;
     type IdentityProvider = C::IdentityProvider;
@@ -1843,7 +2429,7 @@ where
         };
 
         #[cfg(feature = "psk")]
-        let (psk, _) = self
+        let (psk, psk_ids) = self
             .get_psk(&provisional_state.applied_proposals.psks)
             .await?;
 
@@ -1901,6 +2487,11 @@ where
         self.state.public_tree = provisional_state.public_tree;
         self.state.confirmation_tag = new_confirmation_tag;
 
+        #[cfg(feature = "psk")]
+        {
+            self.epoch_external_psks = external_psk_ids(&psk_ids);
+        }
+
         // Clear the proposals list
         #[cfg(feature = "by_ref_proposal")]
         self.state.proposals.clear();
@@ -1917,7 +2508,7 @@ where
     }
 
     fn mls_rules(&self) -> Self::MlsRules {
-        self.config.mls_rules()
+        self.current_mls_rules()
     }
 
     fn identity_provider(&self) -> Self::IdentityProvider {
@@ -1956,6 +2547,16 @@ where
         None
     }
 
+    #[cfg(feature = "by_ref_proposal")]
+    fn max_pending_proposals(&self) -> Option<usize> {
+        self.config.max_pending_proposals()
+    }
+
+    #[cfg(feature = "psk")]
+    fn external_commit_psk_required(&self) -> bool {
+        self.config.external_commit_psk_required()
+    }
+
     fn cipher_suite_provider(&self) -> &Self::CipherSuiteProvider {
         &self.cipher_suite_provider
     }
@@ -1978,6 +2579,7 @@ mod tests {
         mls_rules::CommitOptions,
         tree_kem::{
             leaf_node::{test_utils::get_test_capabilities, LeafNodeSource},
+            parent_hash::ParentHash,
             UpdatePathNode,
         },
     };
@@ -1985,7 +2587,7 @@ mod tests {
     #[cfg(feature = "by_ref_proposal")]
     use crate::{
         client::test_utils::{test_client_with_key_pkg_custom, TEST_CUSTOM_PROPOSAL_TYPE},
-        client_builder::{ClientBuilder, MlsConfig},
+        client_builder::{ClientBuilder, WithMlsRules},
         group::{
             mls_rules::{CommitDirection, CommitSource},
             proposal_filter::ProposalBundle,
@@ -2006,7 +2608,7 @@ mod tests {
     use super::test_utils::test_group_custom_config;
 
     #[cfg(feature = "psk")]
-    use crate::{client::Client, psk::PreSharedKey};
+    use crate::client::Client;
 
     #[cfg(any(feature = "by_ref_proposal", feature = "private_message"))]
     use crate::group::test_utils::random_bytes;
@@ -2596,6 +3198,74 @@ mod tests {
         assert!(with_padding.mls_encoded_len() > without_padding.mls_encoded_len());
     }
 
+    #[cfg(feature = "private_message")]
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn encrypted_commit_description_is_available_to_sender() {
+        let mut alice = test_group_custom_config(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE, |b| {
+            b.mls_rules(
+                DefaultMlsRules::default()
+                    .with_encryption_options(EncryptionOptions::new(true, PaddingMode::None)),
+            )
+        })
+        .await;
+
+        assert!(alice.last_commit_description().is_none());
+
+        // Builds and applies a commit adding bob
+        let (_bob, commit) = alice.join("bob").await;
+
+        assert_eq!(commit.wire_format(), WireFormat::PrivateMessage);
+
+        let description = alice.last_commit_description().unwrap();
+
+        let CommitEffect::NewEpoch(new_epoch) = description.effect else {
+            panic!("unexpected commit effect");
+        };
+
+        assert_eq!(new_epoch.epoch, alice.current_epoch());
+
+        assert_matches!(
+            &new_epoch.applied_proposals[..],
+            [p] if matches!(&p.proposal, Proposal::Add(_))
+        );
+    }
+
+    #[cfg(feature = "private_message")]
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn encrypted_handshake_is_detected() {
+        let mut plain_group = test_group(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE).await;
+
+        assert!(!plain_group.encrypts_control_messages().unwrap());
+
+        let commit = plain_group.commit(vec![]).await.unwrap().commit_message;
+
+        assert_eq!(commit.wire_format(), WireFormat::PublicMessage);
+        assert_eq!(commit.is_encrypted_handshake(), Some(false));
+
+        let mut encrypted_group =
+            test_group_custom_config(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE, |b| {
+                b.mls_rules(
+                    DefaultMlsRules::default()
+                        .with_encryption_options(EncryptionOptions::new(true, PaddingMode::None)),
+                )
+            })
+            .await;
+
+        assert!(encrypted_group.encrypts_control_messages().unwrap());
+
+        let commit = encrypted_group.commit(vec![]).await.unwrap().commit_message;
+
+        assert_eq!(commit.wire_format(), WireFormat::PrivateMessage);
+        assert_eq!(commit.is_encrypted_handshake(), Some(true));
+
+        let application_message = encrypted_group
+            .encrypt_application_message(b"hello", vec![])
+            .await
+            .unwrap();
+
+        assert_eq!(application_message.is_encrypted_handshake(), None);
+    }
+
     #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
     async fn external_commit_requires_external_pub_extension() {
         let protocol_version = TEST_PROTOCOL_VERSION;
@@ -2651,6 +3321,44 @@ mod tests {
             .unwrap();
     }
 
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn external_commit_requires_required_capabilities() {
+        const EXTENSION_TYPE: ExtensionType = ExtensionType::new(33);
+
+        let group_extensions = ExtensionList::from(vec![RequiredCapabilitiesExt {
+            extensions: vec![EXTENSION_TYPE],
+            ..Default::default()
+        }
+        .into_extension()
+        .unwrap()]);
+
+        let alice = TestClientBuilder::new_for_test()
+            .with_random_signing_identity("alice", TEST_CIPHER_SUITE)
+            .await
+            .extension_type(EXTENSION_TYPE)
+            .build()
+            .create_group(group_extensions)
+            .await
+            .unwrap();
+
+        let group_info = alice
+            .group_info_message_allowing_ext_commit(true)
+            .await
+            .unwrap();
+
+        let (bob, _) =
+            test_client_with_key_pkg(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE, "bob").await;
+
+        let res = bob
+            .external_commit_builder()
+            .unwrap()
+            .build(group_info)
+            .await
+            .map(|_| ());
+
+        assert_matches!(res, Err(MlsError::RequiredExtensionNotFound(ext)) if ext == EXTENSION_TYPE);
+    }
+
     #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
     async fn test_path_update_preference() {
         let protocol_version = TEST_PROTOCOL_VERSION;
@@ -2753,6 +3461,61 @@ mod tests {
         assert_matches!(res, Err(MlsError::UnencryptedApplicationMessage));
     }
 
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn group_rejects_commit_with_cipher_suite_mismatch() {
+        let mut alice = test_group(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE).await;
+        let (mut bob, _) = alice.join("bob").await;
+
+        let (mut key_package, signer) =
+            crate::key_package::test_utils::test_key_package_with_signer(
+                TEST_PROTOCOL_VERSION,
+                TEST_CIPHER_SUITE,
+                "carol",
+            )
+            .await;
+
+        // Validly signed, but claiming a different cipher suite than the group's.
+        // A commit cannot change the group's cipher suite otherwise, and key
+        // package validation rejects this one while the proposals are applied.
+        key_package.cipher_suite = CipherSuite::CURVE25519_AES128;
+
+        key_package
+            .sign(
+                &crate::crypto::test_utils::test_cipher_suite_provider(TEST_CIPHER_SUITE),
+                &signer,
+                &(),
+            )
+            .await
+            .unwrap();
+
+        let add = Proposal::Add(alloc::boxed::Box::new(AddProposal { key_package }));
+
+        let commit = Commit {
+            proposals: vec![ProposalOrRef::Proposal(alloc::boxed::Box::new(add))],
+            path: None,
+        };
+
+        let mut auth_content = AuthenticatedContent::new_signed(
+            &alice.cipher_suite_provider,
+            alice.context(),
+            Sender::Member(*alice.private_tree.self_index),
+            Content::Commit(alloc::boxed::Box::new(commit)),
+            &alice.signer,
+            WireFormat::PublicMessage,
+            Vec::new(),
+        )
+        .await
+        .unwrap();
+
+        auth_content.auth.confirmation_tag =
+            Some(ConfirmationTag::empty(&alice.cipher_suite_provider).await);
+
+        let message = alice.format_for_wire(auth_content).await.unwrap();
+        let res = bob.process_incoming_message(message).await;
+
+        assert_matches!(res, Err(MlsError::CipherSuiteMismatch));
+    }
+
     #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
     async fn commit_description_external_commit() {
         let mut alice_group = test_group(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE).await;
@@ -2868,6 +3631,39 @@ mod tests {
         assert!(commit.path.is_none());
     }
 
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn add_only_commit_without_path_is_joinable_and_smaller() {
+        let mut commit_sizes = Vec::new();
+
+        for mut alice in [
+            test_group(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE).await,
+            group_with_path_required().await,
+        ] {
+            alice.join("bob").await;
+            alice.join("charlie").await;
+
+            let (dave, commit) = alice.join("dave").await;
+
+            assert_eq!(
+                dave.epoch_authenticator().unwrap(),
+                alice.epoch_authenticator().unwrap()
+            );
+
+            commit_sizes.push(commit.to_bytes().unwrap().len());
+
+            let Content::Commit(commit) = commit.into_plaintext().unwrap().content.content else {
+                panic!("Expected commit")
+            };
+
+            assert_eq!(
+                commit.path.is_some(),
+                alice.config.0.mls_rules.commit_options.path_required
+            );
+        }
+
+        assert!(commit_sizes[0] < commit_sizes[1]);
+    }
+
     #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
     async fn group_with_path_required() -> TestGroup {
         let mut alice = test_group(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE).await;
@@ -3000,8 +3796,29 @@ mod tests {
 
         assert_matches!(
             res,
-            Err(MlsError::UnsupportedProtocolVersion(v)) if v ==
-                TEST_PROTOCOL_VERSION
+            Err(MlsError::UnsupportedWelcome {
+                cipher_suite: TEST_CIPHER_SUITE,
+                protocol_version: TEST_PROTOCOL_VERSION,
+                supported_protocol_versions,
+                ..
+            }) if supported_protocol_versions.is_empty()
+        );
+    }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn joining_group_fails_if_protocol_version_is_below_minimum() {
+        let minimum = ProtocolVersion::new(TEST_PROTOCOL_VERSION.raw_value() + 1);
+
+        let res = joining_group_fails_if_unsupported(|config| {
+            config.0.settings.min_protocol_version = Some(minimum);
+        })
+        .await
+        .map(|_| ());
+
+        assert_matches!(
+            res,
+            Err(MlsError::ProtocolVersionTooOld { version, minimum: m })
+                if version == TEST_PROTOCOL_VERSION && m == minimum
         );
     }
 
@@ -3021,7 +3838,13 @@ mod tests {
 
         assert_matches!(
             res,
-            Err(MlsError::UnsupportedCipherSuite(TEST_CIPHER_SUITE))
+            Err(MlsError::UnsupportedWelcome {
+                cipher_suite: TEST_CIPHER_SUITE,
+                protocol_version: TEST_PROTOCOL_VERSION,
+                supported_cipher_suites,
+                supported_protocol_versions,
+            }) if !supported_cipher_suites.contains(&TEST_CIPHER_SUITE)
+                && supported_protocol_versions.contains(&TEST_PROTOCOL_VERSION)
         );
     }
 
@@ -3084,53 +3907,470 @@ mod tests {
         assert_matches!(res, Err(MlsError::KeyMissing(0)));
     }
 
+    #[cfg(all(feature = "private_message", feature = "out_of_order"))]
     #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
-    async fn removing_requirements_allows_to_add() {
-        let mut alice_group = test_group_custom(
-            TEST_PROTOCOL_VERSION,
-            TEST_CIPHER_SUITE,
-            vec![17.into()],
-            None,
-            None,
-        )
-        .await;
+    async fn out_of_order_messages_are_decrypted_within_cache_size() {
+        let mut alice_group = test_group(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE).await;
 
-        alice_group
-            .commit_builder()
-            .set_group_context_ext(
-                vec![RequiredCapabilitiesExt {
-                    extensions: vec![17.into()],
-                    ..Default::default()
-                }
-                .into_extension()
-                .unwrap()]
-                .try_into()
-                .unwrap(),
-            )
-            .unwrap()
-            .build()
+        let (mut bob_group, _) = alice_group
+            .join_with_custom_config("bob", false, |c| {
+                c.0.settings.secret_tree_cache_size = Some(2)
+            })
             .await
             .unwrap();
 
-        alice_group.process_pending_commit().await.unwrap();
-
-        let test_key_package =
-            test_key_package(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE, "bob").await;
+        let mut messages = Vec::new();
 
-        let test_key_package = MlsMessage::new(
-            TEST_PROTOCOL_VERSION,
-            MlsMessagePayload::KeyPackage(test_key_package),
-        );
+        for _ in 0..4 {
+            let message = alice_group
+                .encrypt_application_message(b"foobar", Vec::new())
+                .await
+                .unwrap();
 
-        alice_group
-            .commit_builder()
-            .add_member(test_key_package.clone())
-            .unwrap()
-            .set_group_context_ext(Default::default())
-            .unwrap()
-            .build()
-            .await
-            .unwrap();
+            messages.push(message);
+        }
+
+        // Keys for generations 1 and 2 are kept, generation 0 is evicted
+        for message in messages[1..].iter().rev() {
+            bob_group
+                .process_incoming_message(message.clone())
+                .await
+                .unwrap();
+        }
+
+        let res = bob_group
+            .process_incoming_message(messages[0].clone())
+            .await;
+
+        assert_matches!(res, Err(MlsError::KeyMissing(0)));
+    }
+
+    #[cfg(feature = "private_message")]
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn duplicate_message_is_detected_when_enabled() {
+        let mut alice_group = test_group(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE).await;
+
+        let (mut bob_group, _) = alice_group
+            .join_with_custom_config("bob", false, |c| {
+                c.0.settings.message_dedup_capacity = Some(16)
+            })
+            .await
+            .unwrap();
+
+        let message = alice_group
+            .encrypt_application_message(b"foobar", Vec::new())
+            .await
+            .unwrap();
+
+        bob_group
+            .process_incoming_message(message.clone())
+            .await
+            .unwrap();
+
+        let res = bob_group.process_incoming_message(message).await;
+
+        assert_matches!(res, Err(MlsError::DuplicateMessage));
+    }
+
+    #[cfg(feature = "out_of_order")]
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn tampered_message_does_not_block_original() {
+        let mut alice_group = test_group(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE).await;
+
+        let (mut bob_group, _) = alice_group
+            .join_with_custom_config("bob", false, |c| {
+                c.0.settings.message_dedup_capacity = Some(16)
+            })
+            .await
+            .unwrap();
+
+        let message = alice_group
+            .encrypt_application_message(b"foobar", Vec::new())
+            .await
+            .unwrap();
+
+        let mut tampered = message.clone();
+
+        let MlsMessagePayload::Cipher(ref mut ciphertext) = tampered.payload else {
+            panic!("expected private message")
+        };
+
+        *ciphertext.ciphertext.last_mut().unwrap() ^= 1;
+
+        let res = bob_group.process_incoming_message(tampered).await;
+        assert_matches!(res, Err(MlsError::CryptoProviderError(_)));
+
+        let res = bob_group.process_incoming_message(message.clone()).await;
+
+        assert_matches!(
+            res,
+            Ok(ReceivedMessage::ApplicationMessage(m)) if m.data() == b"foobar"
+        );
+
+        let res = bob_group.process_incoming_message(message).await;
+        assert_matches!(res, Err(MlsError::DuplicateMessage));
+    }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn future_commit_is_applied_once_buffered() {
+        let mut alice_group = test_group(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE).await;
+
+        let (mut bob_group, _) = alice_group
+            .join_with_custom_config("bob", false, |c| {
+                c.0.settings.future_commit_buffer_size = Some(1)
+            })
+            .await
+            .unwrap();
+
+        let first_commit = alice_group.commit(vec![]).await.unwrap().commit_message;
+        alice_group.apply_pending_commit().await.unwrap();
+
+        let second_commit = alice_group.commit(vec![]).await.unwrap().commit_message;
+        alice_group.apply_pending_commit().await.unwrap();
+
+        let res = bob_group.process_incoming_message(second_commit).await;
+        assert_matches!(res, Ok(ReceivedMessage::BufferedCommit { epoch: 2 }));
+
+        let res = bob_group.process_incoming_message(first_commit).await;
+        assert_matches!(res, Ok(ReceivedMessage::Commit(_)));
+
+        assert_eq!(bob_group.current_epoch(), alice_group.current_epoch());
+
+        assert_matches!(
+            bob_group.take_applied_buffered_commits().as_slice(),
+            [ReceivedMessage::Commit(_)]
+        );
+
+        assert!(bob_group.take_applied_buffered_commits().is_empty());
+    }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn future_commit_beyond_buffer_window_is_rejected() {
+        let mut alice_group = test_group(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE).await;
+
+        let (mut bob_group, _) = alice_group
+            .join_with_custom_config("bob", false, |c| {
+                c.0.settings.future_commit_buffer_size = Some(1)
+            })
+            .await
+            .unwrap();
+
+        let mut commits = Vec::new();
+
+        for _ in 0..3 {
+            commits.push(alice_group.commit(vec![]).await.unwrap().commit_message);
+            alice_group.apply_pending_commit().await.unwrap();
+        }
+
+        let res = bob_group.process_incoming_message(commits[2].clone()).await;
+
+        assert_matches!(res, Err(MlsError::FutureEpoch { .. }));
+    }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn future_commit_buffer_overflow_is_reported() {
+        let mut alice_group = test_group(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE).await;
+
+        let (mut bob_group, _) = alice_group
+            .join_with_custom_config("bob", false, |c| {
+                c.0.settings.future_commit_buffer_size = Some(1)
+            })
+            .await
+            .unwrap();
+
+        alice_group.commit(vec![]).await.unwrap();
+        alice_group.apply_pending_commit().await.unwrap();
+
+        let commit = alice_group.commit(vec![]).await.unwrap().commit_message;
+        alice_group.clear_pending_commit();
+
+        let other_commit = alice_group.commit(vec![]).await.unwrap().commit_message;
+
+        for _ in 0..2 {
+            let res = bob_group.process_incoming_message(commit.clone()).await;
+            assert_matches!(res, Ok(ReceivedMessage::BufferedCommit { epoch: 2 }));
+        }
+
+        let res = bob_group.process_incoming_message(other_commit).await;
+
+        assert_matches!(res, Err(MlsError::CommitBufferFull(1)));
+    }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn future_commit_buffer_evicts_farthest_commit() {
+        let mut alice_group = test_group(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE).await;
+
+        let (mut bob_group, _) = alice_group
+            .join_with_custom_config("bob", false, |c| {
+                c.0.settings.future_commit_buffer_size = Some(2)
+            })
+            .await
+            .unwrap();
+
+        let first_commit = alice_group.commit(vec![]).await.unwrap().commit_message;
+        alice_group.apply_pending_commit().await.unwrap();
+
+        let discarded_commit = alice_group.commit(vec![]).await.unwrap().commit_message;
+        alice_group.clear_pending_commit();
+
+        let second_commit = alice_group.commit(vec![]).await.unwrap().commit_message;
+        alice_group.apply_pending_commit().await.unwrap();
+
+        let third_commit = alice_group.commit(vec![]).await.unwrap().commit_message;
+        alice_group.apply_pending_commit().await.unwrap();
+
+        for commit in [third_commit.clone(), second_commit, discarded_commit] {
+            bob_group.process_incoming_message(commit).await.unwrap();
+        }
+
+        bob_group
+            .process_incoming_message(first_commit)
+            .await
+            .unwrap();
+
+        // The third commit was evicted and the conflicting commit for epoch 2
+        // was dropped once the second commit was applied
+        assert_eq!(bob_group.current_epoch(), 3);
+        assert_eq!(bob_group.take_applied_buffered_commits().len(), 1);
+
+        bob_group
+            .process_incoming_message(third_commit)
+            .await
+            .unwrap();
+
+        assert_eq!(bob_group.current_epoch(), alice_group.current_epoch());
+    }
+
+    #[cfg(feature = "by_ref_proposal")]
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn proposals_beyond_pending_limit_are_rejected() {
+        let mut alice_group = test_group(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE).await;
+
+        let (mut bob_group, _) = alice_group
+            .join_with_custom_config("bob", false, |c| {
+                c.0.settings.max_pending_proposals = Some(2)
+            })
+            .await
+            .unwrap();
+
+        for _ in 0..2 {
+            let proposal = alice_group.propose_update(vec![]).await.unwrap();
+            bob_group.process_incoming_message(proposal).await.unwrap();
+        }
+
+        let proposal = alice_group.propose_update(vec![]).await.unwrap();
+        let res = bob_group.process_incoming_message(proposal).await;
+
+        assert_matches!(res, Err(MlsError::TooManyPendingProposals(2)));
+        assert_eq!(bob_group.state.proposals.len(), 2);
+    }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn derived_nonces_are_bound_to_label_and_epoch() {
+        let mut groups = test_n_member_group(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE, 2).await;
+
+        let nonce = groups[0].derive_nonce(b"a", 12).await.unwrap();
+
+        let other_member_nonce = groups[1].derive_nonce(b"a", 12).await.unwrap();
+        let other_label_nonce = groups[0].derive_nonce(b"b", 12).await.unwrap();
+
+        let exported = groups[0]
+            .export_secret(b"a", &groups[0].current_epoch().to_be_bytes(), 12)
+            .await
+            .unwrap();
+
+        assert_eq!(nonce.len(), 12);
+        assert_eq!(nonce, other_member_nonce);
+        assert_ne!(nonce, other_label_nonce);
+        assert_ne!(nonce, exported.to_vec());
+
+        let commit = groups[0].commit(vec![]).await.unwrap().commit_message;
+        groups[0].apply_pending_commit().await.unwrap();
+        groups[1].process_incoming_message(commit).await.unwrap();
+
+        let next_nonce = groups[0].derive_nonce(b"a", 12).await.unwrap();
+        let other_member_nonce = groups[1].derive_nonce(b"a", 12).await.unwrap();
+
+        assert_ne!(nonce, next_nonce);
+        assert_eq!(next_nonce, other_member_nonce);
+    }
+
+    #[cfg(feature = "psk")]
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn members_derive_same_resumption_psk() {
+        let mut groups = test_n_member_group(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE, 2).await;
+
+        let psk = groups[0].current_resumption_psk().unwrap();
+        assert_eq!(psk, groups[1].current_resumption_psk().unwrap());
+
+        let commit = groups[0].commit(vec![]).await.unwrap().commit_message;
+        groups[0].apply_pending_commit().await.unwrap();
+        groups[1].process_incoming_message(commit).await.unwrap();
+
+        let next_psk = groups[0].current_resumption_psk().unwrap();
+        assert_eq!(next_psk, groups[1].current_resumption_psk().unwrap());
+        assert_ne!(psk, next_psk);
+    }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn diverged_members_have_different_epoch_hashes() {
+        let mut groups = test_n_member_group(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE, 3).await;
+
+        let hash_0 = groups[0].epoch_hash().await.unwrap();
+        let hash_1 = groups[1].epoch_hash().await.unwrap();
+        assert_eq!(hash_0, hash_1);
+
+        // Members 1 and 2 commit concurrently in the same epoch. Member 0 follows member 1.
+        let commit = groups[1].commit(vec![]).await.unwrap().commit_message;
+        groups[1].apply_pending_commit().await.unwrap();
+        groups[0].process_incoming_message(commit).await.unwrap();
+
+        groups[2].commit(vec![]).await.unwrap();
+        groups[2].apply_pending_commit().await.unwrap();
+
+        assert_eq!(groups[0].current_epoch(), groups[2].current_epoch());
+
+        let hash_0 = groups[0].epoch_hash().await.unwrap();
+        let hash_1 = groups[1].epoch_hash().await.unwrap();
+        let hash_2 = groups[2].epoch_hash().await.unwrap();
+
+        assert_eq!(hash_0, hash_1);
+        assert_ne!(hash_0, hash_2);
+    }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn tampered_parent_hash_is_detected() {
+        let mut groups = test_n_member_group(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE, 2).await;
+
+        // An empty commit includes a path, which sets the parent node above both leaves.
+        groups[0].commit(vec![]).await.unwrap();
+        groups[0].apply_pending_commit().await.unwrap();
+
+        groups[0].verify_parent_hashes().await.unwrap();
+
+        groups[0]
+            .state
+            .public_tree
+            .nodes
+            .borrow_as_parent_mut(1)
+            .unwrap()
+            .parent_hash = ParentHash::from(vec![0u8; 32]);
+
+        let res = groups[0].verify_parent_hashes().await;
+
+        assert_matches!(res, Err(MlsError::ParentHashMismatch));
+    }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn each_member_reports_its_own_leaf() {
+        let groups = test_n_member_group(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE, 3).await;
+
+        for (i, group) in groups.iter().enumerate() {
+            assert_eq!(group.current_member_index(), i as u32);
+
+            let member = groups[0].member_at_index(i as u32).unwrap();
+
+            assert_eq!(
+                group.current_member_signing_identity().unwrap(),
+                &member.signing_identity
+            );
+        }
+    }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn removed_member_group_becomes_inactive() {
+        let mut alice_group = test_group(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE).await;
+        let (mut bob_group, _) = alice_group.join("bob").await;
+
+        assert!(bob_group.is_active());
+
+        let commit = alice_group
+            .commit_builder()
+            .remove_member(1)
+            .unwrap()
+            .build()
+            .await
+            .unwrap()
+            .commit_message;
+
+        let res = bob_group.process_incoming_message(commit).await.unwrap();
+
+        assert_matches!(
+            res,
+            ReceivedMessage::Commit(CommitMessageDescription {
+                effect: CommitEffect::Removed { .. },
+                ..
+            })
+        );
+
+        assert!(!bob_group.is_active());
+
+        let res = bob_group.commit(vec![]).await;
+        assert_matches!(res, Err(MlsError::RemovedFromGroup));
+
+        #[cfg(feature = "private_message")]
+        {
+            let res = bob_group
+                .encrypt_application_message(b"hello", vec![])
+                .await;
+
+            assert_matches!(res, Err(MlsError::RemovedFromGroup));
+        }
+
+        let restored = Group::from_snapshot(bob_group.config.clone(), bob_group.snapshot())
+            .await
+            .unwrap();
+
+        assert!(!restored.is_active());
+    }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn removing_requirements_allows_to_add() {
+        let mut alice_group = test_group_custom(
+            TEST_PROTOCOL_VERSION,
+            TEST_CIPHER_SUITE,
+            vec![17.into()],
+            None,
+            None,
+        )
+        .await;
+
+        alice_group
+            .commit_builder()
+            .set_group_context_ext(
+                vec![RequiredCapabilitiesExt {
+                    extensions: vec![17.into()],
+                    ..Default::default()
+                }
+                .into_extension()
+                .unwrap()]
+                .try_into()
+                .unwrap(),
+            )
+            .unwrap()
+            .build()
+            .await
+            .unwrap();
+
+        alice_group.process_pending_commit().await.unwrap();
+
+        let test_key_package =
+            test_key_package(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE, "bob").await;
+
+        let test_key_package = MlsMessage::new(
+            TEST_PROTOCOL_VERSION,
+            MlsMessagePayload::KeyPackage(test_key_package),
+        );
+
+        alice_group
+            .commit_builder()
+            .add_member(test_key_package.clone())
+            .unwrap()
+            .set_group_context_ext(Default::default())
+            .unwrap()
+            .build()
+            .await
+            .unwrap();
 
         let CommitEffect::NewEpoch(new_epoch) =
             alice_group.process_pending_commit().await.unwrap().effect
@@ -3149,6 +4389,61 @@ mod tests {
         assert_eq!(alice_group.roster().members_iter().count(), 2);
     }
 
+    #[cfg(feature = "by_ref_proposal")]
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn member_leaf_source_follows_updates() {
+        let mut groups = test_n_member_group(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE, 2).await;
+
+        assert_eq!(
+            groups[0].member_leaf_source(1),
+            Some(LeafNodeSourceKind::KeyPackage)
+        );
+
+        let proposal = groups[1].propose_update(vec![]).await.unwrap();
+        groups[0].process_message(proposal).await.unwrap();
+
+        groups[0].commit(vec![]).await.unwrap();
+        groups[0].apply_pending_commit().await.unwrap();
+
+        assert_eq!(
+            groups[0].member_leaf_source(1),
+            Some(LeafNodeSourceKind::Update)
+        );
+
+        assert_eq!(
+            groups[0].member_leaf_source(0),
+            Some(LeafNodeSourceKind::Commit)
+        );
+
+        assert_eq!(groups[0].member_leaf_source(2), None);
+    }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn leaf_signatures_can_be_verified() {
+        let mut groups = test_n_member_group(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE, 3).await;
+
+        // An empty commit includes a path, giving leaf 0 a commit source
+        let commit_output = groups[0].commit(vec![]).await.unwrap();
+        groups[0].apply_pending_commit().await.unwrap();
+        process_commit(&mut groups, commit_output.commit_message, 0).await;
+
+        for index in 0..3 {
+            groups[0].verify_leaf_signature(index).await.unwrap();
+        }
+
+        groups[0]
+            .state
+            .public_tree
+            .nodes
+            .borrow_as_leaf_mut(LeafIndex(1))
+            .unwrap()
+            .signature[0] ^= 1;
+
+        let res = groups[0].verify_leaf_signature(1).await;
+
+        assert_matches!(res, Err(MlsError::InvalidSignature));
+    }
+
     #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
     async fn commit_leaf_wrong_source() {
         // RFC, 13.4.2. "The leaf_node_source field MUST be set to commit."
@@ -3528,6 +4823,37 @@ mod tests {
         );
     }
 
+    #[cfg(feature = "by_ref_proposal")]
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn external_sender_can_be_added_and_removed() {
+        let mut groups = test_n_member_group(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE, 2).await;
+        let (ted, _) = get_test_signing_identity(TEST_CIPHER_SUITE, b"ted").await;
+
+        for allowed_senders in [vec![ted], vec![]] {
+            let proposal = groups[0]
+                .propose_external_senders(ExternalSendersExt::new(allowed_senders.clone()), vec![])
+                .await
+                .unwrap();
+
+            groups[1].process_incoming_message(proposal).await.unwrap();
+
+            let commit = groups[1].commit(vec![]).await.unwrap().commit_message;
+            groups[1].apply_pending_commit().await.unwrap();
+            groups[0].process_incoming_message(commit).await.unwrap();
+
+            for group in groups.iter() {
+                let ext = group
+                    .context()
+                    .extensions
+                    .get_as::<ExternalSendersExt>()
+                    .unwrap()
+                    .unwrap();
+
+                assert_eq!(ext.allowed_senders, allowed_senders);
+            }
+        }
+    }
+
     #[cfg(feature = "by_ref_proposal")]
     #[cfg(not(target_arch = "wasm32"))]
     #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
@@ -3873,6 +5199,130 @@ mod tests {
             .unwrap();
     }
 
+    #[cfg(feature = "psk")]
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn external_psk_ids_of_current_epoch_are_reported() {
+        let mut alice = test_group(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE).await;
+
+        let (bob_client, key_pkg) =
+            test_client_with_key_pkg(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE, "bob").await;
+
+        let psk_id = ExternalPskId::new(vec![0]);
+        let psk = PreSharedKey::from(vec![0]);
+
+        alice
+            .config
+            .secret_store()
+            .insert(psk_id.clone(), psk.clone());
+
+        bob_client.config.secret_store().insert(psk_id.clone(), psk);
+
+        assert!(alice.referenced_external_psk_ids().is_empty());
+
+        let commit = alice
+            .commit_builder()
+            .add_member(key_pkg)
+            .unwrap()
+            .add_external_psk(psk_id.clone())
+            .unwrap()
+            .build()
+            .await
+            .unwrap();
+
+        alice.apply_pending_commit().await.unwrap();
+
+        let (mut bob, _) = bob_client
+            .join_group(None, &commit.welcome_messages[0])
+            .await
+            .unwrap();
+
+        bob.write_to_storage().await.unwrap();
+        let mut bob = bob_client.load_group(bob.group_id()).await.unwrap();
+
+        assert_eq!(alice.referenced_external_psk_ids(), [psk_id.clone()]);
+        assert_eq!(bob.referenced_external_psk_ids(), [psk_id]);
+
+        let commit = alice.commit(vec![]).await.unwrap().commit_message;
+        alice.apply_pending_commit().await.unwrap();
+        bob.process_incoming_message(commit).await.unwrap();
+
+        assert!(alice.referenced_external_psk_ids().is_empty());
+        assert!(bob.referenced_external_psk_ids().is_empty());
+    }
+
+    #[cfg(feature = "psk")]
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn commit_with_unknown_external_psk_reports_missing_id() {
+        let mut groups = test_n_member_group(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE, 2).await;
+
+        let psk_id = ExternalPskId::new(vec![1]);
+
+        groups[0]
+            .config
+            .secret_store()
+            .insert(psk_id.clone(), PreSharedKey::from(vec![1]));
+
+        let commit = groups[0]
+            .commit_builder()
+            .add_external_psk(psk_id.clone())
+            .unwrap()
+            .build()
+            .await
+            .unwrap()
+            .commit_message;
+
+        let res = groups[1].process_incoming_message(commit).await;
+
+        assert_matches!(res, Err(MlsError::MissingPsk(id)) if id == psk_id);
+    }
+
+    #[cfg(all(feature = "by_ref_proposal", feature = "psk", feature = "prior_epoch"))]
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn can_commit_resumption_psk_from_prior_epoch() {
+        let mut groups = test_n_member_group(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE, 2).await;
+
+        let commit = groups[0].commit(vec![]).await.unwrap().commit_message;
+        groups[0].apply_pending_commit().await.unwrap();
+        groups[1].process_incoming_message(commit).await.unwrap();
+
+        let psk_epoch = groups[0].current_epoch() - 1;
+
+        let proposal = groups[0]
+            .propose_resumption_psk(psk_epoch, vec![])
+            .await
+            .unwrap();
+
+        groups[1].process_incoming_message(proposal).await.unwrap();
+
+        let commit = groups[0].commit(vec![]).await.unwrap().commit_message;
+        groups[0].apply_pending_commit().await.unwrap();
+
+        let ReceivedMessage::Commit(CommitMessageDescription {
+            effect: CommitEffect::NewEpoch(new_epoch),
+            ..
+        }) = groups[1].process_incoming_message(commit).await.unwrap()
+        else {
+            panic!("unexpected commit effect");
+        };
+
+        assert_matches!(
+            &new_epoch.applied_proposals[..],
+            [p] if matches!(&p.proposal, Proposal::Psk(_))
+        );
+
+        assert_eq!(groups[0].current_epoch(), groups[1].current_epoch());
+    }
+
+    #[cfg(all(feature = "by_ref_proposal", feature = "psk"))]
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn resumption_psk_from_future_epoch_is_rejected() {
+        let mut group = test_group(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE).await;
+        let psk_epoch = group.current_epoch() + 1;
+
+        let res = group.propose_resumption_psk(psk_epoch, vec![]).await;
+        assert_matches!(res, Err(MlsError::OldGroupStateNotFound));
+    }
+
     #[cfg(feature = "by_ref_proposal")]
     #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
     async fn invalid_update_does_not_prevent_other_updates() {
@@ -4154,12 +5604,57 @@ mod tests {
         alice.process_incoming_message(commit).await.unwrap();
     }
 
+    #[cfg(feature = "custom_proposal")]
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn stricter_mls_rules_can_be_set_on_existing_group() {
+        let lenient_rules = CustomMlsRules {
+            path_required_for_custom: true,
+            external_joiner_can_send_custom: true,
+        };
+
+        let mut alice = client_with_custom_rules(b"alice", lenient_rules.clone())
+            .await
+            .create_group(Default::default())
+            .await
+            .unwrap();
+
+        let group_info = alice
+            .group_info_message_allowing_ext_commit(true)
+            .await
+            .unwrap();
+
+        let (_, commit) = client_with_custom_rules(b"bob", lenient_rules)
+            .await
+            .external_commit_builder()
+            .unwrap()
+            .with_custom_proposal(CustomProposal::new(TEST_CUSTOM_PROPOSAL_TYPE, vec![]))
+            .build(group_info)
+            .await
+            .unwrap();
+
+        let mut strict_alice = alice.clone();
+
+        alice
+            .process_incoming_message(commit.clone())
+            .await
+            .unwrap();
+
+        strict_alice.set_mls_rules(CustomMlsRules {
+            path_required_for_custom: true,
+            external_joiner_can_send_custom: false,
+        });
+
+        let res = strict_alice.process_incoming_message(commit).await;
+
+        assert_matches!(res, Err(MlsError::MlsRulesError(_)));
+    }
+
     #[cfg(feature = "custom_proposal")]
     #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
     async fn client_with_custom_rules(
         name: &[u8],
         mls_rules: CustomMlsRules,
-    ) -> Client<impl MlsConfig> {
+    ) -> Client<WithMlsRules<CustomMlsRules, TestClientConfig>> {
         let (signing_identity, signer) = get_test_signing_identity(TEST_CIPHER_SUITE, name).await;
 
         ClientBuilder::new()
@@ -4306,6 +5801,98 @@ mod tests {
         assert_eq!(new_epoch.applied_proposals[0].sender, Sender::Member(0));
     }
 
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn message_from_other_group_is_rejected() {
+        let mut alice = test_group(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE).await;
+
+        let mut other_group = TestClientBuilder::new_for_test()
+            .with_random_signing_identity("carol", TEST_CIPHER_SUITE)
+            .await
+            .build()
+            .create_group_with_id(b"other group".to_vec(), Default::default())
+            .await
+            .unwrap();
+
+        let commit = other_group.commit(vec![]).await.unwrap().commit_message;
+        let res = alice.process_incoming_message(commit).await;
+
+        assert_matches!(
+            res,
+            Err(MlsError::GroupIdMismatch { expected, found })
+                if expected == TEST_GROUP && found == b"other group"
+        );
+    }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn commit_for_past_epoch_is_stale() {
+        let mut groups = test_n_member_group(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE, 2).await;
+
+        let commit = groups[0].commit(vec![]).await.unwrap().commit_message;
+        groups[0].apply_pending_commit().await.unwrap();
+
+        groups[1]
+            .process_incoming_message(commit.clone())
+            .await
+            .unwrap();
+
+        let res = groups[1].process_incoming_message(commit).await;
+
+        assert_matches!(
+            res,
+            Err(MlsError::StaleEpoch {
+                message_epoch: 1,
+                current_epoch: 2
+            })
+        );
+    }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn commit_for_future_epoch_is_reported() {
+        let mut groups = test_n_member_group(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE, 2).await;
+
+        groups[0].commit(vec![]).await.unwrap();
+        groups[0].apply_pending_commit().await.unwrap();
+
+        let commit = groups[0].commit(vec![]).await.unwrap().commit_message;
+        let res = groups[1].process_incoming_message(commit).await;
+
+        assert_matches!(
+            res,
+            Err(MlsError::FutureEpoch {
+                message_epoch: 2,
+                current_epoch: 1
+            })
+        );
+    }
+
+    #[cfg(feature = "by_ref_proposal")]
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn batch_of_proposals_becomes_pending() {
+        let mut groups = test_n_member_group(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE, 3).await;
+
+        let dave = test_key_package(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE, "dave").await;
+        let erin = test_key_package(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE, "erin").await;
+
+        let proposals = vec![
+            Proposal::Add(alloc::boxed::Box::new(AddProposal { key_package: dave })),
+            Proposal::Add(alloc::boxed::Box::new(AddProposal { key_package: erin })),
+            Proposal::Remove(RemoveProposal {
+                to_remove: LeafIndex(2),
+            }),
+        ];
+
+        let messages = groups[0].propose_batch(proposals, vec![]).await.unwrap();
+
+        assert_eq!(messages.len(), 3);
+        assert_eq!(groups[0].state.proposals.proposals.len(), 3);
+
+        for message in messages {
+            groups[1].process_message(message).await.unwrap();
+        }
+
+        assert_eq!(groups[1].state.proposals.proposals.len(), 3);
+    }
+
     #[cfg(feature = "by_ref_proposal")]
     #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
     async fn commit_clears_proposals() {
@@ -22,7 +22,15 @@ pub use mls_rs_core::extension::ExtensionList;
 pub use mls_rs_core::group::ProposalType;
 
 #[cfg(feature = "psk")]
-use crate::psk::{ExternalPskId, JustPreSharedKeyID, PreSharedKeyID};
+use mls_rs_core::crypto::CipherSuiteProvider;
+
+#[cfg(feature = "custom_proposal")]
+use mls_rs_core::error::IntoAnyError;
+
+#[cfg(feature = "psk")]
+use crate::psk::{
+    ExternalPskId, JustPreSharedKeyID, PreSharedKeyID, ResumptionPSKUsage, ResumptionPsk,
+};
 
 #[derive(Clone, Debug, PartialEq, MlsSize, MlsEncode, MlsDecode)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
@@ -160,6 +168,39 @@ impl PreSharedKeyProposal {
             JustPreSharedKeyID::Resumption(_) => None,
         }
     }
+
+    /// Check that the pre-shared key referenced by this proposal may be used
+    /// in a PreSharedKeyProposal.
+    ///
+    /// MLS only allows `External` keys and `Resumption` keys with usage
+    /// `Application` to be proposed. Other resumption usages are reserved
+    /// for reinitialization and branching.
+    pub fn validate_type_usage(&self) -> Result<(), MlsError> {
+        match self.psk.key_id {
+            JustPreSharedKeyID::External(_)
+            | JustPreSharedKeyID::Resumption(ResumptionPsk {
+                usage: ResumptionPSKUsage::Application,
+                ..
+            }) => Ok(()),
+            _ => Err(MlsError::InvalidTypeOrUsageInPreSharedKeyProposal),
+        }
+    }
+
+    /// Check that the nonce of this proposal is as long as the output of the
+    /// KDF used by `cipher_suite_provider`.
+    pub fn validate_nonce_length<CS: CipherSuiteProvider>(
+        &self,
+        cipher_suite_provider: &CS,
+    ) -> Result<(), MlsError> {
+        let expected = cipher_suite_provider.kdf_extract_size();
+        let found = self.psk.psk_nonce.0.len();
+
+        if found == expected {
+            Ok(())
+        } else {
+            Err(MlsError::InvalidPskNonceLength { expected, found })
+        }
+    }
 }
 
 #[derive(Clone, PartialEq, MlsSize, MlsEncode, MlsDecode)]
@@ -315,6 +356,69 @@ pub trait MlsCustomProposal: MlsSize + MlsEncode + MlsDecode + Sized {
     }
 }
 
+/// Trait used to convert a type to and from a [`CustomProposal`].
+///
+/// This trait is implemented for every [`MlsCustomProposal`].
+#[cfg(feature = "custom_proposal")]
+pub trait CustomProposalCodec: Sized {
+    /// Error type of the underlying serializer that can convert this type into a `Vec<u8>`.
+    type SerializationError: IntoAnyError;
+
+    /// Error type of the underlying deserializer that can convert a `Vec<u8>` into this type.
+    type DeserializationError: IntoAnyError;
+
+    /// Proposal type value that this type represents.
+    fn proposal_type() -> ProposalType;
+
+    /// Convert this type to opaque bytes.
+    fn to_bytes(&self) -> Result<Vec<u8>, Self::SerializationError>;
+
+    /// Create this type from opaque bytes.
+    fn from_bytes(data: &[u8]) -> Result<Self, Self::DeserializationError>;
+
+    /// Convert this type into a [`CustomProposal`].
+    fn into_custom_proposal(self) -> Result<CustomProposal, MlsError> {
+        Ok(CustomProposal::new(
+            Self::proposal_type(),
+            self.to_bytes()
+                .map_err(|e| MlsError::SerializationError(e.into_any_error()))?,
+        ))
+    }
+
+    /// Create this type from a [`CustomProposal`].
+    fn try_from_custom_proposal(proposal: &CustomProposal) -> Result<Self, MlsError> {
+        if proposal.proposal_type() != Self::proposal_type() {
+            return Err(MlsError::IncorrectCustomProposalType(
+                proposal.proposal_type(),
+            ));
+        }
+
+        Self::from_bytes(proposal.data())
+            .map_err(|e| MlsError::SerializationError(e.into_any_error()))
+    }
+}
+
+#[cfg(feature = "custom_proposal")]
+impl<T> CustomProposalCodec for T
+where
+    T: MlsCustomProposal,
+{
+    type SerializationError = mls_rs_codec::Error;
+    type DeserializationError = mls_rs_codec::Error;
+
+    fn proposal_type() -> ProposalType {
+        <Self as MlsCustomProposal>::proposal_type()
+    }
+
+    fn to_bytes(&self) -> Result<Vec<u8>, Self::SerializationError> {
+        self.mls_encode_to_vec()
+    }
+
+    fn from_bytes(data: &[u8]) -> Result<Self, Self::DeserializationError> {
+        Self::mls_decode(&mut &*data)
+    }
+}
+
 #[allow(clippy::large_enum_variant)]
 #[derive(Clone, Debug, PartialEq)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
@@ -414,7 +518,7 @@ impl MlsDecode for Proposal {
             }),
             // TODO fix test dependency on openssl loading codec with default features
             #[cfg(not(feature = "custom_proposal"))]
-            _ => return Err(mls_rs_codec::Error::Custom(3)),
+            _ => return Err(mls_rs_codec::Error::Custom(INVALID_PROPOSAL_DISCRIMINANT)),
         })
     }
 }
@@ -560,16 +664,36 @@ impl<'a> From<&'a CustomProposal> for BorrowedProposal<'a> {
     }
 }
 
-#[derive(Clone, Debug, PartialEq, MlsSize, MlsEncode, MlsDecode)]
+/// Codec error code for a proposal or proposal reference with an unknown
+/// discriminant. Surfaced as [`MlsError::InvalidProposalDiscriminant`].
+pub(crate) const INVALID_PROPOSAL_DISCRIMINANT: u8 = 3;
+
+/// A proposal as included in a commit: either by value or by reference to a
+/// proposal sent earlier in the epoch.
+///
+/// This is the input of
+/// [`ProposalBundle::from_proposals_or_refs`](crate::mls_rules::ProposalBundle::from_proposals_or_refs).
+#[derive(Clone, Debug, PartialEq, MlsSize, MlsEncode)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[repr(u8)]
-pub(crate) enum ProposalOrRef {
+pub enum ProposalOrRef {
     Proposal(Box<Proposal>) = 1u8,
     #[cfg(feature = "by_ref_proposal")]
     Reference(ProposalRef) = 2u8,
 }
 
+impl MlsDecode for ProposalOrRef {
+    fn mls_decode(reader: &mut &[u8]) -> Result<Self, mls_rs_codec::Error> {
+        match u8::mls_decode(reader)? {
+            1 => Ok(Self::Proposal(Box::new(Proposal::mls_decode(reader)?))),
+            #[cfg(feature = "by_ref_proposal")]
+            2 => Ok(Self::Reference(ProposalRef::mls_decode(reader)?)),
+            _ => Err(mls_rs_codec::Error::Custom(INVALID_PROPOSAL_DISCRIMINANT)),
+        }
+    }
+}
+
 impl From<Proposal> for ProposalOrRef {
     fn from(proposal: Proposal) -> Self {
         Self::Proposal(Box::new(proposal))
@@ -582,3 +706,142 @@ impl From<ProposalRef> for ProposalOrRef {
         Self::Reference(r)
     }
 }
+
+#[cfg(all(test, feature = "custom_proposal"))]
+mod tests {
+    use alloc::vec;
+    use assert_matches::assert_matches;
+
+    use crate::{
+        client::test_utils::{TEST_CIPHER_SUITE, TEST_PROTOCOL_VERSION},
+        key_package::test_utils::test_key_package,
+    };
+
+    #[cfg(feature = "by_ref_proposal")]
+    use crate::tree_kem::leaf_node::test_utils::get_basic_test_node;
+
+    #[cfg(feature = "psk")]
+    use crate::psk::test_utils::make_nonce;
+
+    use super::*;
+
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::wasm_bindgen_test as test;
+
+    #[derive(Clone, Debug, PartialEq, MlsSize, MlsEncode, MlsDecode)]
+    struct RenameProposal {
+        name: Vec<u8>,
+    }
+
+    impl MlsCustomProposal for RenameProposal {
+        fn proposal_type() -> ProposalType {
+            ProposalType::new(65000)
+        }
+    }
+
+    #[test]
+    fn typed_custom_proposal_round_trips() {
+        let proposal = RenameProposal {
+            name: b"group".to_vec(),
+        };
+
+        let custom = proposal.clone().into_custom_proposal().unwrap();
+
+        assert_eq!(custom.proposal_type(), ProposalType::new(65000));
+        assert_eq!(custom.data(), proposal.to_bytes().unwrap());
+
+        assert_eq!(
+            RenameProposal::try_from_custom_proposal(&custom).unwrap(),
+            proposal
+        );
+    }
+
+    #[test]
+    fn typed_custom_proposal_rejects_other_proposal_type() {
+        let custom = CustomProposal::new(ProposalType::new(65001), Vec::new());
+
+        assert_matches!(
+            RenameProposal::try_from_custom_proposal(&custom),
+            Err(MlsError::IncorrectCustomProposalType(_))
+        );
+    }
+
+    #[test]
+    fn proposal_or_ref_with_invalid_discriminant_is_rejected() {
+        let res = ProposalOrRef::mls_decode(&mut &[3u8, 0, 1][..]);
+
+        assert_matches!(
+            res,
+            Err(mls_rs_codec::Error::Custom(INVALID_PROPOSAL_DISCRIMINANT))
+        );
+
+        assert_matches!(
+            MlsError::from(res.unwrap_err()),
+            MlsError::InvalidProposalDiscriminant
+        );
+    }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn borrowed_proposal_from_each_proposal_variant() {
+        let key_package = test_key_package(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE, "alice").await;
+
+        #[allow(unused_mut)]
+        let mut proposals = vec![
+            Proposal::Add(Box::new(AddProposal { key_package })),
+            Proposal::Remove(RemoveProposal {
+                to_remove: LeafIndex(1),
+            }),
+            Proposal::ReInit(ReInitProposal {
+                group_id: vec![1],
+                version: TEST_PROTOCOL_VERSION,
+                cipher_suite: TEST_CIPHER_SUITE,
+                extensions: ExtensionList::new(),
+            }),
+            Proposal::ExternalInit(ExternalInit {
+                kem_output: vec![2],
+            }),
+            Proposal::GroupContextExtensions(ExtensionList::new()),
+            Proposal::Custom(CustomProposal::new(ProposalType::new(65000), vec![3])),
+        ];
+
+        #[cfg(feature = "by_ref_proposal")]
+        proposals.push(Proposal::Update(UpdateProposal {
+            leaf_node: get_basic_test_node(TEST_CIPHER_SUITE, "bob").await,
+        }));
+
+        #[cfg(feature = "psk")]
+        proposals.push(Proposal::Psk(PreSharedKeyProposal {
+            psk: PreSharedKeyID {
+                key_id: JustPreSharedKeyID::External(ExternalPskId::new(vec![4])),
+                psk_nonce: make_nonce(TEST_CIPHER_SUITE),
+            },
+        }));
+
+        for proposal in &proposals {
+            let borrowed = BorrowedProposal::from(proposal);
+
+            let borrows_inner = match (&borrowed, proposal) {
+                (BorrowedProposal::Add(b), Proposal::Add(p)) => core::ptr::eq(*b, p.as_ref()),
+                #[cfg(feature = "by_ref_proposal")]
+                (BorrowedProposal::Update(b), Proposal::Update(p)) => core::ptr::eq(*b, p),
+                (BorrowedProposal::Remove(b), Proposal::Remove(p)) => core::ptr::eq(*b, p),
+                #[cfg(feature = "psk")]
+                (BorrowedProposal::Psk(b), Proposal::Psk(p)) => core::ptr::eq(*b, p),
+                (BorrowedProposal::ReInit(b), Proposal::ReInit(p)) => core::ptr::eq(*b, p),
+                (BorrowedProposal::ExternalInit(b), Proposal::ExternalInit(p)) => {
+                    core::ptr::eq(*b, p)
+                }
+                (
+                    BorrowedProposal::GroupContextExtensions(b),
+                    Proposal::GroupContextExtensions(p),
+                ) => core::ptr::eq(*b, p),
+                (BorrowedProposal::Custom(b), Proposal::Custom(p)) => core::ptr::eq(*b, p),
+                _ => false,
+            };
+
+            assert!(borrows_inner);
+            assert_eq!(borrowed.proposal_type(), proposal.proposal_type());
+            assert_eq!(&Proposal::from(borrowed), proposal);
+        }
+    }
+}
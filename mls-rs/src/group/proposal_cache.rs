@@ -116,6 +116,12 @@ impl ProposalCache {
         self.proposals.is_empty()
     }
 
+    #[cfg(feature = "by_ref_proposal")]
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.proposals.len()
+    }
+
     pub fn insert(&mut self, proposal_ref: ProposalRef, proposal: Proposal, sender: Sender) {
         let cached_proposal = CachedProposal { proposal, sender };
 
@@ -174,32 +180,17 @@ impl ProposalCache {
         sender: Sender,
         proposal_list: Vec<ProposalOrRef>,
     ) -> Result<ProposalBundle, MlsError> {
-        let mut proposals = ProposalBundle::default();
-
-        for p in proposal_list {
-            match p {
-                ProposalOrRef::Proposal(p) => proposals.add(*p, sender, ProposalSource::ByValue),
-                ProposalOrRef::Reference(r) => {
-                    #[cfg(feature = "std")]
-                    let p = self
-                        .proposals
-                        .get(&r)
-                        .ok_or(MlsError::ProposalNotFound)?
-                        .clone();
-                    #[cfg(not(feature = "std"))]
-                    let p = self
-                        .proposals
-                        .iter()
-                        .find_map(|(rr, p)| (rr == &r).then_some(p))
-                        .ok_or(MlsError::ProposalNotFound)?
-                        .clone();
-
-                    proposals.add(p.proposal, p.sender, ProposalSource::ByReference(r));
-                }
-            };
-        }
-
-        Ok(proposals)
+        ProposalBundle::from_proposals_or_refs(sender, proposal_list, |r| {
+            #[cfg(feature = "std")]
+            let p = self.proposals.get(r);
+            #[cfg(not(feature = "std"))]
+            let p = self
+                .proposals
+                .iter()
+                .find_map(|(rr, p)| (rr == r).then_some(p));
+
+            p.map(|p| (p.proposal.clone(), p.sender))
+        })
     }
 
     #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
@@ -287,7 +278,7 @@ impl GroupState {
         }?;
 
         proposals = user_rules
-            .filter_proposals(direction, origin, &roster, group_extensions, proposals)
+            .filter_proposals_with_context(direction, origin, &roster, &self.context, proposals)
             .await
             .map_err(|e| MlsError::MlsRulesError(e.into_any_error()))?;
 
@@ -2321,7 +2312,7 @@ mod tests {
         ))])
         .await;
 
-        assert_matches!(res, Err(MlsError::InvalidPskNonceLength,));
+        assert_matches!(res, Err(MlsError::InvalidPskNonceLength { found: 3, .. }));
     }
 
     #[cfg(feature = "psk")]
@@ -2338,7 +2329,7 @@ mod tests {
             .send()
             .await;
 
-        assert_matches!(res, Err(MlsError::InvalidPskNonceLength));
+        assert_matches!(res, Err(MlsError::InvalidPskNonceLength { found: 3, .. }));
     }
 
     #[cfg(feature = "psk")]
@@ -2434,6 +2425,50 @@ mod tests {
         assert_eq!(processed_proposals.1.unused_proposals, vec![proposal_info]);
     }
 
+    #[cfg(feature = "psk")]
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn psk_nonce_length_must_match_kdf_extract_size() {
+        let cs = test_cipher_suite_provider(TEST_CIPHER_SUITE);
+        let len = cs.kdf_extract_size();
+
+        let psk = make_external_psk(b"foo", PskNonce(vec![0; len]));
+        assert_matches!(psk.validate_nonce_length(&cs), Ok(()));
+
+        for bad_len in [len - 1, len + 1] {
+            let psk = make_external_psk(b"foo", PskNonce(vec![0; bad_len]));
+
+            assert_matches!(
+                psk.validate_nonce_length(&cs),
+                Err(MlsError::InvalidPskNonceLength { expected, found })
+                    if expected == len && found == bad_len
+            );
+        }
+    }
+
+    #[cfg(feature = "psk")]
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn external_psk_has_valid_type_usage() {
+        assert_matches!(new_external_psk(b"abc").validate_type_usage(), Ok(()));
+    }
+
+    #[cfg(feature = "psk")]
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn application_resumption_psk_has_valid_type_usage() {
+        let psk = make_resumption_psk(ResumptionPSKUsage::Application);
+        assert_matches!(psk.validate_type_usage(), Ok(()));
+    }
+
+    #[cfg(feature = "psk")]
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn reinit_and_branch_resumption_psks_have_invalid_type_usage() {
+        for usage in [ResumptionPSKUsage::Reinit, ResumptionPSKUsage::Branch] {
+            assert_matches!(
+                make_resumption_psk(usage).validate_type_usage(),
+                Err(MlsError::InvalidTypeOrUsageInPreSharedKeyProposal)
+            );
+        }
+    }
+
     #[cfg(feature = "psk")]
     #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
     async fn receiving_resumption_psk_with_reinit_usage_fails() {
@@ -3839,7 +3874,7 @@ mod tests {
         .receive([Proposal::Psk(new_external_psk(b"abc"))])
         .await;
 
-        assert_matches!(res, Err(MlsError::MissingRequiredPsk));
+        assert_matches!(res, Err(MlsError::MissingPsk(_)));
     }
 
     #[cfg(feature = "psk")]
@@ -3853,7 +3888,7 @@ mod tests {
             .send()
             .await;
 
-        assert_matches!(res, Err(MlsError::MissingRequiredPsk));
+        assert_matches!(res, Err(MlsError::MissingPsk(_)));
     }
 
     #[cfg(feature = "psk")]
@@ -9,6 +9,7 @@ use alloc::vec::Vec;
 use itertools::Itertools;
 
 use crate::{
+    client::MlsError,
     group::{
         AddProposal, BorrowedProposal, Proposal, ProposalOrRef, ProposalType, ReInitProposal,
         RemoveProposal, Sender,
@@ -316,8 +317,57 @@ impl ProposalBundle {
         )
     }
 
+    /// Iterate over proposals in the bundle in the order in which RFC 9420
+    /// applies them when processing a commit, consuming the bundle.
+    ///
+    /// GroupContextExtensions proposals come first, followed by updates,
+    /// removals, additions and PSKs. ReInit and ExternalInit proposals, which
+    /// are not part of that order, follow. Custom proposals are emitted last.
+    fn into_proposals_in_canonical_order(self) -> impl Iterator<Item = ProposalInfo<Proposal>> {
+        let res = self
+            .group_context_extensions
+            .into_iter()
+            .map(|p| p.map(Proposal::GroupContextExtensions));
+
+        #[cfg(feature = "by_ref_proposal")]
+        let res = res.chain(self.updates.into_iter().map(|p| p.map(Proposal::Update)));
+
+        let res = res
+            .chain(self.removals.into_iter().map(|p| p.map(Proposal::Remove)))
+            .chain(
+                self.additions
+                    .into_iter()
+                    .map(|p| p.map(|p| Proposal::Add(alloc::boxed::Box::new(p)))),
+            );
+
+        #[cfg(feature = "psk")]
+        let res = res.chain(self.psks.into_iter().map(|p| p.map(Proposal::Psk)));
+
+        let res = res
+            .chain(
+                self.reinitializations
+                    .into_iter()
+                    .map(|p| p.map(Proposal::ReInit)),
+            )
+            .chain(
+                self.external_initializations
+                    .into_iter()
+                    .map(|p| p.map(Proposal::ExternalInit)),
+            );
+
+        #[cfg(feature = "custom_proposal")]
+        let res = res.chain(
+            self.custom_proposals
+                .into_iter()
+                .map(|p| p.map(Proposal::Custom)),
+        );
+
+        res
+    }
+
+    /// Proposals of the bundle as listed in a commit, in canonical order.
     pub(crate) fn into_proposals_or_refs(self) -> Vec<ProposalOrRef> {
-        self.into_proposals()
+        self.into_proposals_in_canonical_order()
             .filter_map(|p| match p.source {
                 ProposalSource::ByValue => Some(ProposalOrRef::Proposal(Box::new(p.proposal))),
                 #[cfg(feature = "by_ref_proposal")]
@@ -327,6 +377,33 @@ impl ProposalBundle {
             .collect()
     }
 
+    /// Build a bundle from a list of proposals as found in a commit.
+    ///
+    /// Proposals included by value are attributed to `committer`. Proposals
+    /// included by reference are looked up with `resolver`, which returns the
+    /// referenced proposal along with its sender. Returns
+    /// [`MlsError::ProposalNotFound`] if a reference can't be resolved.
+    #[cfg(feature = "by_ref_proposal")]
+    pub fn from_proposals_or_refs<F>(
+        committer: Sender,
+        items: Vec<ProposalOrRef>,
+        resolver: F,
+    ) -> Result<Self, MlsError>
+    where
+        F: Fn(&ProposalRef) -> Option<(Proposal, Sender)>,
+    {
+        items
+            .into_iter()
+            .map(|item| match item {
+                ProposalOrRef::Proposal(p) => Ok((*p, committer, ProposalSource::ByValue)),
+                ProposalOrRef::Reference(r) => {
+                    let (proposal, sender) = resolver(&r).ok_or(MlsError::ProposalNotFound)?;
+                    Ok((proposal, sender, ProposalSource::ByReference(r)))
+                }
+            })
+            .collect()
+    }
+
     /// Add proposals in the bundle.
     pub fn add_proposals(&self) -> &[ProposalInfo<AddProposal>] {
         &self.additions
@@ -380,6 +457,43 @@ impl ProposalBundle {
         self.group_context_extensions.first()
     }
 
+    /// The group context extensions proposal in the bundle, if any.
+    ///
+    /// Returns [`MlsError::MoreThanOneGroupContextExtensionsProposal`] if the
+    /// bundle contains more than one such proposal.
+    pub fn single_group_context_extensions(
+        &self,
+    ) -> Result<Option<&ProposalInfo<ExtensionList>>, MlsError> {
+        match self.group_context_ext_proposals() {
+            [] => Ok(None),
+            [p] => Ok(Some(p)),
+            _ => Err(MlsError::MoreThanOneGroupContextExtensionsProposal),
+        }
+    }
+
+    /// Check that a ReInit proposal, if present, is the only proposal in the
+    /// bundle.
+    ///
+    /// Returns [`MlsError::OtherProposalWithReInit`] otherwise. This allows
+    /// failing early, before a commit is built.
+    pub fn validate_reinit_exclusivity(&self) -> Result<(), MlsError> {
+        (self.reinitializations.is_empty() || self.length() == 1)
+            .then_some(())
+            .ok_or(MlsError::OtherProposalWithReInit)
+    }
+
+    /// Returns `true` if a Remove proposal in the bundle targets the member at
+    /// leaf index `committer`.
+    ///
+    /// A commit including such a proposal is rejected with
+    /// [`MlsError::CommitterSelfRemoval`]. This allows failing early, before a
+    /// commit is built.
+    pub fn committer_removes_self(&self, committer: u32) -> bool {
+        self.removals
+            .iter()
+            .any(|p| *p.proposal.to_remove == committer)
+    }
+
     /// Custom proposal types that are in use within this bundle.
     #[cfg(feature = "custom_proposal")]
     pub fn custom_proposal_types(&self) -> impl Iterator<Item = ProposalType> + '_ {
@@ -580,6 +694,52 @@ impl<T> ProposalInfo<T> {
     }
 }
 
+#[cfg(feature = "custom_proposal")]
+impl ProposalInfo<CustomProposal> {
+    /// Map this custom proposal into the proposals produced by `f`.
+    ///
+    /// The resulting proposals keep the sender of the custom proposal and
+    /// are marked as [`ProposalSource::Local`], so they are applied but not
+    /// transmitted with a commit.
+    pub fn expand<F, I>(&self, f: F) -> Vec<ProposalInfo<Proposal>>
+    where
+        F: FnOnce(&CustomProposal) -> I,
+        I: IntoIterator<Item = Proposal>,
+    {
+        f(&self.proposal)
+            .into_iter()
+            .map(|proposal| ProposalInfo {
+                proposal,
+                sender: self.sender,
+                source: ProposalSource::Local,
+            })
+            .collect()
+    }
+
+    /// Same as [`expand`](Self::expand), but fails if any of the
+    /// resulting proposals is itself a custom proposal.
+    ///
+    /// This prevents custom rules from expanding proposals recursively.
+    pub fn try_expand<F, I>(&self, f: F) -> Result<Vec<ProposalInfo<Proposal>>, MlsError>
+    where
+        F: FnOnce(&CustomProposal) -> I,
+        I: IntoIterator<Item = Proposal>,
+    {
+        let expanded = self.expand(f);
+
+        if expanded
+            .iter()
+            .any(|p| matches!(p.proposal, Proposal::Custom(_)))
+        {
+            return Err(MlsError::RecursiveCustomProposalExpansion(
+                self.proposal.proposal_type(),
+            ));
+        }
+
+        Ok(expanded)
+    }
+}
+
 #[cfg(all(feature = "ffi", not(test)))]
 safer_ffi_gen::specialize!(ProposalInfoFfi = ProposalInfo<Proposal>);
 
@@ -631,3 +791,278 @@ impl_proposable!(
     GROUP_CONTEXT_EXTENSIONS,
     group_context_extensions
 );
+
+#[cfg(test)]
+mod tests {
+    use alloc::{boxed::Box, vec, vec::Vec};
+
+    use crate::{
+        client::test_utils::{TEST_CIPHER_SUITE, TEST_PROTOCOL_VERSION},
+        group::{
+            proposal::{ExternalInit, ReInitProposal},
+            AddProposal, LeafIndex, Proposal, ProposalOrRef, ProposalType, RemoveProposal, Sender,
+        },
+        key_package::test_utils::test_key_package,
+        ExtensionList,
+    };
+
+    use crate::client::MlsError;
+    use assert_matches::assert_matches;
+
+    #[cfg(feature = "by_ref_proposal")]
+    use crate::group::proposal_ref::ProposalRef;
+
+    #[cfg(feature = "psk")]
+    use crate::{
+        group::proposal::PreSharedKeyProposal,
+        psk::{test_utils::make_nonce, ExternalPskId, JustPreSharedKeyID, PreSharedKeyID},
+    };
+
+    #[cfg(feature = "custom_proposal")]
+    use crate::{client::test_utils::TEST_CUSTOM_PROPOSAL_TYPE, group::proposal::CustomProposal};
+
+    use super::{ProposalBundle, ProposalInfo, ProposalSource};
+
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::wasm_bindgen_test as test;
+
+    #[cfg(feature = "by_ref_proposal")]
+    fn test_reference() -> ProposalRef {
+        ProposalRef::new_fake(vec![1, 2, 3])
+    }
+
+    fn test_remove() -> Proposal {
+        Proposal::Remove(RemoveProposal {
+            to_remove: LeafIndex(2),
+        })
+    }
+
+    #[cfg(feature = "by_ref_proposal")]
+    fn test_resolver(reference: &ProposalRef) -> Option<(Proposal, Sender)> {
+        (reference == &test_reference()).then(|| (test_remove(), Sender::Member(1)))
+    }
+
+    #[cfg(feature = "by_ref_proposal")]
+    #[test]
+    fn bundle_can_be_built_from_proposals_and_references() {
+        let items = vec![
+            ProposalOrRef::Proposal(Box::new(Proposal::GroupContextExtensions(
+                ExtensionList::new(),
+            ))),
+            ProposalOrRef::Reference(test_reference()),
+        ];
+
+        let bundle =
+            ProposalBundle::from_proposals_or_refs(Sender::Member(0), items, test_resolver)
+                .unwrap();
+
+        assert_eq!(bundle.length(), 2);
+
+        let by_value = &bundle.group_context_ext_proposals()[0];
+        assert_eq!(by_value.sender, Sender::Member(0));
+        assert_eq!(by_value.source, ProposalSource::ByValue);
+
+        let by_reference = &bundle.remove_proposals()[0];
+        assert_eq!(by_reference.proposal.to_remove, LeafIndex(2));
+        assert_eq!(by_reference.sender, Sender::Member(1));
+        assert_eq!(
+            by_reference.source,
+            ProposalSource::ByReference(test_reference())
+        );
+    }
+
+    #[cfg(feature = "by_ref_proposal")]
+    #[test]
+    fn unresolved_reference_is_rejected() {
+        let items = vec![ProposalOrRef::Reference(ProposalRef::new_fake(vec![4]))];
+
+        let res = ProposalBundle::from_proposals_or_refs(Sender::Member(0), items, test_resolver);
+
+        assert_matches!(res, Err(MlsError::ProposalNotFound));
+    }
+
+    #[cfg(feature = "by_ref_proposal")]
+    #[test]
+    fn empty_list_gives_empty_bundle() {
+        let bundle =
+            ProposalBundle::from_proposals_or_refs(Sender::Member(0), Vec::new(), test_resolver)
+                .unwrap();
+
+        assert_eq!(bundle.length(), 0);
+    }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn proposals_or_refs_are_in_canonical_order() {
+        let add = Proposal::Add(Box::new(AddProposal {
+            key_package: test_key_package(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE, "bob").await,
+        }));
+
+        let external_init = Proposal::ExternalInit(ExternalInit {
+            kem_output: vec![0; 32],
+        });
+
+        let gce = Proposal::GroupContextExtensions(ExtensionList::new());
+
+        // Insert in reverse of the expected order
+        let mut proposals = vec![external_init, test_reinit()];
+
+        #[cfg(feature = "psk")]
+        proposals.push(Proposal::Psk(PreSharedKeyProposal {
+            psk: PreSharedKeyID {
+                key_id: JustPreSharedKeyID::External(ExternalPskId::new(vec![1])),
+                psk_nonce: make_nonce(TEST_CIPHER_SUITE),
+            },
+        }));
+
+        proposals.extend([add, test_remove(), gce]);
+
+        let mut bundle = ProposalBundle::default();
+
+        proposals
+            .into_iter()
+            .for_each(|p| bundle.add(p, Sender::Member(0), ProposalSource::ByValue));
+
+        let types = bundle
+            .into_proposals_or_refs()
+            .into_iter()
+            .map(|p| match p {
+                ProposalOrRef::Proposal(p) => p.proposal_type(),
+                #[cfg(feature = "by_ref_proposal")]
+                ProposalOrRef::Reference(_) => panic!("unexpected reference"),
+            })
+            .collect::<Vec<_>>();
+
+        let mut expected = vec![
+            ProposalType::GROUP_CONTEXT_EXTENSIONS,
+            ProposalType::REMOVE,
+            ProposalType::ADD,
+        ];
+
+        #[cfg(feature = "psk")]
+        expected.push(ProposalType::PSK);
+
+        expected.extend([ProposalType::RE_INIT, ProposalType::EXTERNAL_INIT]);
+
+        assert_eq!(types, expected);
+    }
+
+    fn test_gce_bundle(count: usize) -> ProposalBundle {
+        let mut bundle = ProposalBundle::default();
+
+        (0..count).for_each(|_| {
+            bundle.add(
+                Proposal::GroupContextExtensions(ExtensionList::new()),
+                Sender::Member(0),
+                ProposalSource::ByValue,
+            )
+        });
+
+        bundle
+    }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn single_group_context_extensions_without_proposal() {
+        assert_matches!(
+            test_gce_bundle(0).single_group_context_extensions(),
+            Ok(None)
+        );
+    }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn single_group_context_extensions_with_one_proposal() {
+        let bundle = test_gce_bundle(1);
+        let proposal = bundle.single_group_context_extensions().unwrap().unwrap();
+
+        assert_eq!(proposal.proposal, ExtensionList::new());
+        assert_eq!(proposal.sender, Sender::Member(0));
+    }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn single_group_context_extensions_with_two_proposals() {
+        assert_matches!(
+            test_gce_bundle(2).single_group_context_extensions(),
+            Err(MlsError::MoreThanOneGroupContextExtensionsProposal)
+        );
+    }
+
+    fn test_reinit() -> Proposal {
+        Proposal::ReInit(ReInitProposal {
+            group_id: b"group".to_vec(),
+            version: TEST_PROTOCOL_VERSION,
+            cipher_suite: TEST_CIPHER_SUITE,
+            extensions: ExtensionList::new(),
+        })
+    }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn lone_reinit_is_exclusive() {
+        let mut bundle = ProposalBundle::default();
+        bundle.add(test_reinit(), Sender::Member(0), ProposalSource::ByValue);
+
+        assert_matches!(bundle.validate_reinit_exclusivity(), Ok(()));
+    }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn reinit_with_add_is_not_exclusive() {
+        let add = Proposal::Add(Box::new(AddProposal {
+            key_package: test_key_package(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE, "bob").await,
+        }));
+
+        let mut bundle = ProposalBundle::default();
+        bundle.add(test_reinit(), Sender::Member(0), ProposalSource::ByValue);
+        bundle.add(add, Sender::Member(0), ProposalSource::ByValue);
+
+        assert_matches!(
+            bundle.validate_reinit_exclusivity(),
+            Err(MlsError::OtherProposalWithReInit)
+        );
+    }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn removal_of_committer_is_detected() {
+        let mut bundle = ProposalBundle::default();
+        bundle.add(test_remove(), Sender::Member(0), ProposalSource::ByValue);
+
+        assert!(bundle.committer_removes_self(2));
+        assert!(!bundle.committer_removes_self(0));
+    }
+
+    #[cfg(feature = "custom_proposal")]
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn custom_proposal_expands_into_standard_proposals() {
+        let custom = ProposalInfo::new(
+            CustomProposal::new(TEST_CUSTOM_PROPOSAL_TYPE, vec![]),
+            Sender::Member(1),
+            true,
+        );
+
+        let expanded = custom
+            .try_expand(|_| [test_remove(), test_remove()])
+            .unwrap();
+
+        assert_eq!(expanded.len(), 2);
+
+        expanded.iter().for_each(|p| {
+            assert_eq!(p.proposal, test_remove());
+            assert_eq!(p.sender, Sender::Member(1));
+            assert_eq!(p.source, ProposalSource::Local);
+        });
+    }
+
+    #[cfg(feature = "custom_proposal")]
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn recursive_custom_proposal_expansion_is_rejected() {
+        let custom = ProposalInfo::new(
+            CustomProposal::new(TEST_CUSTOM_PROPOSAL_TYPE, vec![]),
+            Sender::Member(1),
+            true,
+        );
+
+        let res = custom.try_expand(|p| [test_remove(), Proposal::Custom(p.clone())]);
+
+        assert_matches!(
+            res,
+            Err(MlsError::RecursiveCustomProposalExpansion(t)) if t == TEST_CUSTOM_PROPOSAL_TYPE
+        );
+    }
+}
@@ -36,7 +36,7 @@ use crate::group::{ExternalInit, ProposalType, RemoveProposal};
 use crate::group::proposal::PreSharedKeyProposal;
 
 #[cfg(feature = "psk")]
-use crate::group::{JustPreSharedKeyID, ResumptionPSKUsage, ResumptionPsk};
+use crate::group::JustPreSharedKeyID;
 
 #[cfg(all(feature = "std", feature = "psk"))]
 use std::collections::HashSet;
@@ -372,8 +372,6 @@ where
     P: PreSharedKeyStorage,
     CP: CipherSuiteProvider,
 {
-    let kdf_extract_size = cipher_suite_provider.kdf_extract_size();
-
     #[cfg(feature = "std")]
     let mut ids_seen = HashSet::new();
 
@@ -386,17 +384,9 @@ where
     for i in 0..proposals.psk_proposals().len() {
         let p = &proposals.psks[i];
 
-        let valid = matches!(
-            p.proposal.psk.key_id,
-            JustPreSharedKeyID::External(_)
-                | JustPreSharedKeyID::Resumption(ResumptionPsk {
-                    usage: ResumptionPSKUsage::Application,
-                    ..
-                })
-        );
+        let valid = p.proposal.validate_type_usage().is_ok();
 
-        let nonce_length = p.proposal.psk.psk_nonce.0.len();
-        let nonce_valid = nonce_length == kdf_extract_size;
+        let nonce_valid = p.proposal.validate_nonce_length(cipher_suite_provider);
 
         #[cfg(feature = "std")]
         let is_new_id = ids_seen.insert(p.proposal.psk.clone());
@@ -413,7 +403,7 @@ where
                     if found {
                         Ok(())
                     } else {
-                        Err(MlsError::MissingRequiredPsk)
+                        Err(MlsError::MissingPsk(id.clone()))
                     }
                 }),
             JustPreSharedKeyID::Resumption(_) => Ok(()),
@@ -422,8 +412,8 @@ where
         #[cfg(not(feature = "by_ref_proposal"))]
         if !valid {
             return Err(MlsError::InvalidTypeOrUsageInPreSharedKeyProposal);
-        } else if !nonce_valid {
-            return Err(MlsError::InvalidPskNonceLength);
+        } else if nonce_valid.is_err() {
+            return nonce_valid;
         } else if !is_new_id {
             return Err(MlsError::DuplicatePskIds);
         } else if external_id_is_valid.is_err() {
@@ -434,8 +424,8 @@ where
         {
             let res = if !valid {
                 Err(MlsError::InvalidTypeOrUsageInPreSharedKeyProposal)
-            } else if !nonce_valid {
-                Err(MlsError::InvalidPskNonceLength)
+            } else if nonce_valid.is_err() {
+                nonce_valid
             } else if !is_new_id {
                 Err(MlsError::DuplicatePskIds)
             } else {
@@ -147,13 +147,9 @@ fn filter_out_removal_of_committer(
     commit_sender: LeafIndex,
     proposals: &ProposalBundle,
 ) -> Result<(), MlsError> {
-    for p in &proposals.removals {
-        (p.proposal.to_remove != commit_sender)
-            .then_some(())
-            .ok_or(MlsError::CommitterSelfRemoval)?;
-    }
-
-    Ok(())
+    (!proposals.committer_removes_self(*commit_sender))
+        .then_some(())
+        .ok_or(MlsError::CommitterSelfRemoval)
 }
 
 #[cfg(feature = "by_ref_proposal")]
@@ -178,9 +174,7 @@ where
 }
 
 fn filter_out_extra_group_context_extensions(proposals: &ProposalBundle) -> Result<(), MlsError> {
-    (proposals.group_context_extensions.len() < 2)
-        .then_some(())
-        .ok_or(MlsError::MoreThanOneGroupContextExtensionsProposal)
+    proposals.single_group_context_extensions().map(|_| ())
 }
 
 fn filter_out_invalid_reinit(
@@ -197,9 +191,7 @@ fn filter_out_invalid_reinit(
 }
 
 fn filter_out_reinit_if_other_proposals(proposals: &ProposalBundle) -> Result<(), MlsError> {
-    (proposals.reinitializations.is_empty() || proposals.length() == 1)
-        .then_some(())
-        .ok_or(MlsError::OtherProposalWithReInit)
+    proposals.validate_reinit_exclusivity()
 }
 
 #[cfg(feature = "custom_proposal")]
@@ -289,7 +289,10 @@ async fn resumption_join_group<C: ClientConfig + Clone>(
     } else if group.cipher_suite() != expected_new_group_params.cipher_suite {
         Err(MlsError::CipherSuiteMismatch)
     } else if verify_group_id && group.group_id() != expected_new_group_params.group_id {
-        Err(MlsError::GroupIdMismatch)
+        Err(MlsError::GroupIdMismatch {
+            expected: expected_new_group_params.group_id.to_vec(),
+            found: group.group_id().to_vec(),
+        })
     } else if &group.group_state().context.extensions != expected_new_group_params.extensions {
         Err(MlsError::ReInitExtensionsMismatch)
     } else {
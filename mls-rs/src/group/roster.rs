@@ -84,3 +84,26 @@ impl TreeKemPublic {
         Roster { public_tree: self }
     }
 }
+
+/// How the current leaf node of a member was established.
+///
+/// See [`Group::member_leaf_source`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LeafNodeSourceKind {
+    /// The leaf node comes from the key package used to add the member.
+    KeyPackage,
+    /// The leaf node was set by an update proposal of the member.
+    Update,
+    /// The leaf node was set by a commit of the member.
+    Commit,
+}
+
+impl From<&LeafNodeSource> for LeafNodeSourceKind {
+    fn from(source: &LeafNodeSource) -> Self {
+        match source {
+            LeafNodeSource::KeyPackage(_) => LeafNodeSourceKind::KeyPackage,
+            LeafNodeSource::Update => LeafNodeSourceKind::Update,
+            LeafNodeSource::Commit(_) => LeafNodeSourceKind::Commit,
+        }
+    }
+}
@@ -15,7 +15,7 @@ use crate::{client::MlsError, map::LargeMap, tree_kem::math::TreeIndex, CipherSu
 use mls_rs_codec::{MlsDecode, MlsEncode, MlsSize};
 use mls_rs_core::error::IntoAnyError;
 
-use super::key_schedule::kdf_expand_with_label;
+use super::key_schedule::{kdf_expand_with_label, kdf_expand_with_label_batch};
 
 pub(crate) const MAX_RATCHET_BACK_HISTORY: u32 = 1024;
 
@@ -184,13 +184,17 @@ impl<T: TreeIndex> SecretTree<T> {
             let left_index = index.left().ok_or(MlsError::LeafNodeNoChildren)?;
             let right_index = index.right().ok_or(MlsError::LeafNodeNoChildren)?;
 
-            let left_secret =
-                kdf_expand_with_label(cipher_suite_provider, &secret, b"tree", b"left", None)
-                    .await?;
+            let mut children = kdf_expand_with_label_batch(
+                cipher_suite_provider,
+                &secret,
+                b"tree",
+                &[b"left", b"right"],
+                None,
+            )
+            .await?;
 
-            let right_secret =
-                kdf_expand_with_label(cipher_suite_provider, &secret, b"tree", b"right", None)
-                    .await?;
+            let right_secret = children.pop().ok_or(MlsError::LeafNodeNoChildren)?;
+            let left_secret = children.pop().ok_or(MlsError::LeafNodeNoChildren)?;
 
             self.known_secrets
                 .set_node(left_index, SecretTreeNode::Secret(left_secret.into()));
@@ -269,6 +273,41 @@ impl<T: TreeIndex> SecretTree<T> {
 
         Ok(res)
     }
+
+    /// Return a key obtained with [`Self::message_key_generation`] that did not
+    /// decrypt its message, so that it can be retrieved again.
+    #[cfg(feature = "out_of_order")]
+    pub(crate) fn restore_message_key(
+        &mut self,
+        leaf_index: &T,
+        key_type: KeyType,
+        key: MessageKeyData,
+    ) {
+        if let Some(SecretTreeNode::Ratchet(ratchets)) =
+            self.known_secrets.inner.get_mut(leaf_index)
+        {
+            let ratchet = match key_type {
+                KeyType::Handshake => &mut ratchets.handshake,
+                KeyType::Application => &mut ratchets.application,
+            };
+
+            if key.generation < ratchet.generation {
+                ratchet.history.insert(key.generation, key);
+            }
+        }
+    }
+
+    /// Keep at most `max_keys` skipped message keys in each ratchet of the
+    /// leaf at `leaf_index`, dropping the oldest generations first.
+    #[cfg(feature = "out_of_order")]
+    pub(crate) fn trim_history(&mut self, leaf_index: &T, max_keys: usize) {
+        if let Some(SecretTreeNode::Ratchet(ratchets)) =
+            self.known_secrets.inner.get_mut(leaf_index)
+        {
+            ratchets.application.trim_history(max_keys);
+            ratchets.handshake.trim_history(max_keys);
+        }
+    }
 }
 
 #[derive(Clone, Copy)]
@@ -447,6 +486,22 @@ impl SecretKeyRatchet {
         self.next_message_key(cipher_suite_provider).await
     }
 
+    #[cfg(feature = "out_of_order")]
+    fn trim_history(&mut self, max_keys: usize) {
+        if self.history.len() <= max_keys {
+            return;
+        }
+
+        let mut generations = self.history.keys().copied().collect::<Vec<_>>();
+        generations.sort_unstable();
+
+        generations[..generations.len() - max_keys]
+            .iter()
+            .for_each(|generation| {
+                self.history.remove(generation);
+            });
+    }
+
     #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
     async fn next_message_key<P: CipherSuiteProvider>(
         &mut self,
@@ -771,6 +826,55 @@ mod tests {
         );
     }
 
+    #[cfg(feature = "out_of_order")]
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn out_of_order_keys_within_cache_window() {
+        let cs = test_cipher_suite_provider(TEST_CIPHER_SUITE);
+        let secret = vec![0u8; cs.kdf_extract_size()];
+
+        // Simulate a group with 65536 members and pick a sender far from the left
+        let leaf_count = 1u32 << 16;
+        let leaf = 2 * 50_000u32;
+
+        let mut reference = get_test_tree(secret.clone(), leaf_count);
+        let mut expected = Vec::new();
+
+        for generation in 0..=20 {
+            let key = reference
+                .message_key_generation(&cs, leaf, KeyType::Application, generation)
+                .await
+                .unwrap();
+
+            expected.push(key);
+        }
+
+        let mut tree = get_test_tree(secret, leaf_count);
+
+        let key = tree
+            .message_key_generation(&cs, leaf, KeyType::Application, 20)
+            .await
+            .unwrap();
+
+        assert_eq!(key, expected[20]);
+
+        tree.trim_history(&leaf, 4);
+
+        for generation in (16..20).rev() {
+            let key = tree
+                .message_key_generation(&cs, leaf, KeyType::Application, generation)
+                .await
+                .unwrap();
+
+            assert_eq!(key, expected[generation as usize]);
+        }
+
+        let res = tree
+            .message_key_generation(&cs, leaf, KeyType::Application, 15)
+            .await;
+
+        assert_matches!(res, Err(MlsError::KeyMissing(15)));
+    }
+
     #[cfg(not(feature = "out_of_order"))]
     #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
     async fn out_of_order_keys_should_throw_error() {
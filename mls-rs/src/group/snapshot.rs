@@ -2,6 +2,9 @@
 // Copyright by contributors to this project.
 // SPDX-License-Identifier: (Apache-2.0 OR MIT)
 
+#[cfg(feature = "psk")]
+use alloc::vec::Vec;
+
 use crate::{
     client::MlsError,
     client_config::ClientConfig,
@@ -28,6 +31,8 @@ use mls_rs_codec::{MlsDecode, MlsEncode, MlsSize};
 use mls_rs_core::crypto::SignatureSecretKey;
 #[cfg(feature = "tree_index")]
 use mls_rs_core::identity::IdentityProvider;
+#[cfg(feature = "psk")]
+use mls_rs_core::psk::ExternalPskId;
 
 #[derive(Debug, PartialEq, Clone, MlsEncode, MlsDecode, MlsSize)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -41,6 +46,9 @@ pub(crate) struct Snapshot {
     pending_updates: SmallMap<HpkePublicKey, (HpkeSecretKey, Option<SignatureSecretKey>)>,
     pending_commit: Option<CommitGeneration>,
     signer: SignatureSecretKey,
+    removed: bool,
+    #[cfg(feature = "psk")]
+    epoch_external_psks: Vec<ExternalPskId>,
 }
 
 #[derive(Debug, MlsEncode, MlsDecode, MlsSize, PartialEq, Clone)]
@@ -152,6 +160,21 @@ where
         self.state_repo.write_to_storage(self.snapshot()).await
     }
 
+    /// Smallest and largest ids of the prior epochs kept for this group, or
+    /// `None` if there are none.
+    ///
+    /// Messages from these epochs can still be decrypted. The range includes
+    /// epochs that are not yet written to the
+    /// [`GroupStateStorage`](crate::GroupStateStorage) by
+    /// [`write_to_storage`](Self::write_to_storage). Once they are written,
+    /// the oldest ones may be deleted according to the retention policy of the
+    /// storage.
+    #[cfg(feature = "prior_epoch")]
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    pub async fn stored_epoch_range(&self) -> Result<Option<(u64, u64)>, MlsError> {
+        self.state_repo.epoch_range().await
+    }
+
     pub(crate) fn snapshot(&self) -> Snapshot {
         Snapshot {
             state: RawGroupState::export(&self.state),
@@ -163,6 +186,9 @@ where
             epoch_secrets: self.epoch_secrets.clone(),
             version: 1,
             signer: self.signer.clone(),
+            removed: self.removed,
+            #[cfg(feature = "psk")]
+            epoch_external_psks: self.epoch_external_psks.clone(),
         }
     }
 
@@ -198,6 +224,13 @@ where
             #[cfg(feature = "by_ref_proposal")]
             pending_updates: snapshot.pending_updates,
             pending_commit: snapshot.pending_commit,
+            mls_rules_override: None,
+            #[cfg(feature = "private_message")]
+            received_messages: Default::default(),
+            buffered_commits: Default::default(),
+            applied_buffered_commits: Default::default(),
+            removed: snapshot.removed,
+            last_own_commit: None,
             #[cfg(test)]
             commit_modifiers: Default::default(),
             epoch_secrets: snapshot.epoch_secrets,
@@ -205,6 +238,8 @@ where
             cipher_suite_provider,
             #[cfg(feature = "psk")]
             previous_psk: None,
+            #[cfg(feature = "psk")]
+            epoch_external_psks: snapshot.epoch_external_psks,
             signer: snapshot.signer,
         })
     }
@@ -250,6 +285,9 @@ pub(crate) mod test_utils {
             pending_commit: None,
             version: 1,
             signer: vec![].into(),
+            removed: false,
+            #[cfg(feature = "psk")]
+            epoch_external_psks: Default::default(),
         }
     }
 }
@@ -283,6 +321,45 @@ mod tests {
             .equal_internals(&group.state.public_tree))
     }
 
+    #[cfg(feature = "prior_epoch")]
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn stored_epoch_range_follows_retention() {
+        use mls_rs_core::group::GroupStateStorage;
+
+        use crate::{
+            client_config::ClientConfig, storage_provider::in_memory::DEFAULT_EPOCH_RETENTION_LIMIT,
+        };
+
+        let mut group = test_group(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE).await;
+
+        let range = group.stored_epoch_range().await.unwrap();
+        assert_eq!(range, None);
+
+        for _ in 0..5 {
+            group.commit(vec![]).await.unwrap();
+            group.apply_pending_commit().await.unwrap();
+        }
+
+        // Epochs that are not written yet are all kept
+        let range = group.stored_epoch_range().await.unwrap();
+        assert_eq!(range, Some((0, 4)));
+
+        group.write_to_storage().await.unwrap();
+
+        let max_epoch_id = group
+            .config
+            .group_state_storage()
+            .max_epoch_id(group.group_id())
+            .await
+            .unwrap()
+            .unwrap();
+
+        let min_epoch_id = max_epoch_id + 1 - DEFAULT_EPOCH_RETENTION_LIMIT as u64;
+
+        let range = group.stored_epoch_range().await.unwrap();
+        assert_eq!(range, Some((min_epoch_id, max_epoch_id)));
+    }
+
     #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
     async fn snapshot_with_pending_commit_can_be_serialized_to_json() {
         let mut group = test_group(TEST_PROTOCOL_VERSION, TEST_CIPHER_SUITE).await;
@@ -312,6 +389,44 @@ mod tests {
         snapshot_restore(group).await
     }
 
+    #[cfg(feature = "sqlite")]
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn sqlite_storage_reads_cipher_suite_of_written_group() {
+        use mls_rs_provider_sqlite::{
+            connection_strategy::MemoryStrategy, SqLiteDataStorageEngine,
+        };
+
+        use crate::{
+            client_builder::ClientBuilder,
+            client_config::ClientConfig,
+            crypto::test_utils::TestCryptoProvider,
+            identity::{basic::BasicIdentityProvider, test_utils::get_test_signing_identity},
+        };
+
+        let (signing_identity, signer) =
+            get_test_signing_identity(TEST_CIPHER_SUITE, b"alice").await;
+
+        let storage = SqLiteDataStorageEngine::new(MemoryStrategy).unwrap();
+
+        let client = ClientBuilder::new_sqlite(storage)
+            .unwrap()
+            .crypto_provider(TestCryptoProvider::new())
+            .identity_provider(BasicIdentityProvider)
+            .signing_identity(signing_identity, signer, TEST_CIPHER_SUITE)
+            .build();
+
+        let mut group = client.create_group(Default::default()).await.unwrap();
+        group.write_to_storage().await.unwrap();
+
+        let cipher_suite = client
+            .config
+            .group_state_storage()
+            .group_cipher_suite(group.group_id())
+            .unwrap();
+
+        assert_eq!(cipher_suite, Some(TEST_CIPHER_SUITE));
+    }
+
     #[cfg(feature = "serde")]
     #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
     async fn serde() {
@@ -96,6 +96,24 @@ where
         }
     }
 
+    /// Smallest and largest ids of the prior epochs that are either stored or
+    /// pending to be written.
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    pub async fn epoch_range(&self) -> Result<Option<(u64, u64)>, MlsError> {
+        let Some(max) = self.find_max_id().await? else {
+            return Ok(None);
+        };
+
+        let min = self
+            .storage
+            .min_epoch_id(&self.group_id)
+            .await
+            .map_err(|e| MlsError::GroupStorageError(e.into_any_error()))?
+            .or_else(|| self.pending_commit.inserts.front().map(|e| e.epoch_id()));
+
+        Ok(min.map(|min| (min, max)))
+    }
+
     #[cfg(feature = "psk")]
     #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
     pub async fn resumption_secret(
@@ -175,7 +193,10 @@ where
     #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
     pub async fn insert(&mut self, epoch: PriorEpoch) -> Result<(), MlsError> {
         if epoch.group_id() != self.group_id {
-            return Err(MlsError::GroupIdMismatch);
+            return Err(MlsError::GroupIdMismatch {
+                expected: self.group_id.clone(),
+                found: epoch.group_id().to_vec(),
+            });
         }
 
         let epoch_id = epoch.epoch_id();
@@ -158,6 +158,21 @@ impl TestGroup {
 
         self.format_for_wire(auth_content).await.unwrap()
     }
+
+    #[cfg(all(feature = "external_client", feature = "private_message"))]
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    pub(crate) async fn set_epoch_secret(&mut self, epoch_secret: &[u8]) {
+        let derived = KeySchedule::from_epoch_secret(
+            &self.group.cipher_suite_provider,
+            epoch_secret,
+            self.group.state.public_tree.total_leaf_count(),
+        )
+        .await
+        .unwrap();
+
+        self.group.key_schedule = derived.key_schedule;
+        self.group.epoch_secrets = derived.epoch_secrets;
+    }
 }
 
 #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
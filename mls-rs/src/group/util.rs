@@ -4,6 +4,7 @@
 
 use mls_rs_core::{
     error::IntoAnyError,
+    extension::ExtensionType,
     identity::{IdentityProvider, SigningIdentity},
     key_package::KeyPackageStorage,
 };
@@ -15,7 +16,11 @@ use crate::{
     key_package::KeyPackageGeneration,
     protocol_version::ProtocolVersion,
     signer::Signable,
-    tree_kem::{node::LeafIndex, tree_validator::TreeValidator, TreeKemPublic},
+    tree_kem::{
+        node::{LeafIndex, NodeVec},
+        tree_validator::TreeValidator,
+        TreeKemPublic,
+    },
     CipherSuiteProvider, CryptoProvider,
 };
 
@@ -25,7 +30,7 @@ use crate::extension::ExternalSendersExt;
 use super::{
     framing::Sender, message_signature::AuthenticatedContent,
     transcript_hash::InterimTranscriptHash, ConfirmedTranscriptHash, EncryptedGroupSecrets,
-    ExportedTree, GroupInfo, GroupState,
+    ExportedTree, GroupContext, GroupInfo, GroupState,
 };
 
 use super::message_processor::ProvisionalState;
@@ -81,10 +86,12 @@ pub(crate) async fn validate_tree_and_info_joiner<C: CipherSuiteProvider, I: Ide
     msg_version: ProtocolVersion,
     group_info: &GroupInfo,
     tree: Option<ExportedTree<'_>>,
+    max_tree_size: Option<usize>,
     id_provider: &I,
     cs: &C,
 ) -> Result<TreeKemPublic, MlsError> {
-    let public_tree = validate_tree_joiner(group_info, tree, id_provider, cs).await?;
+    let public_tree =
+        validate_tree_joiner(group_info, tree, max_tree_size, id_provider, cs).await?;
 
     let signer = &public_tree
         .get_leaf_node(group_info.signer)?
@@ -99,16 +106,32 @@ pub(crate) async fn validate_tree_and_info_joiner<C: CipherSuiteProvider, I: Ide
 pub(crate) async fn validate_tree_joiner<C: CipherSuiteProvider, I: IdentityProvider>(
     group_info: &GroupInfo,
     tree: Option<ExportedTree<'_>>,
+    max_tree_size: Option<usize>,
     id_provider: &I,
     cs: &C,
 ) -> Result<TreeKemPublic, MlsError> {
-    let tree = match group_info.extensions.get_as::<RatchetTreeExt>()? {
-        Some(ext) => ext.tree_data,
+    let tree = match group_info.extensions.get(ExtensionType::RATCHET_TREE) {
+        Some(ext) => ExportedTree::new(NodeVec::mls_decode_bounded(
+            &mut &*ext.extension_data,
+            max_tree_size,
+        )?),
         None => tree.ok_or(MlsError::RatchetTreeNotFound)?,
     };
 
-    let context = &group_info.group_context;
+    if max_tree_size.map_or(false, |max_nodes| tree.0.len() > max_nodes) {
+        return Err(MlsError::RatchetTreeTooLarge);
+    }
+
+    validate_tree_data(tree, &group_info.group_context, id_provider, cs).await
+}
 
+#[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+pub(crate) async fn validate_tree_data<C: CipherSuiteProvider, I: IdentityProvider>(
+    tree: ExportedTree<'_>,
+    context: &GroupContext,
+    id_provider: &I,
+    cs: &C,
+) -> Result<TreeKemPublic, MlsError> {
     let mut tree =
         TreeKemPublic::import_node_data(tree.into(), id_provider, &context.extensions).await?;
 
@@ -217,6 +240,18 @@ pub(crate) async fn find_key_package_generation<'a, K: KeyPackageStorage>(
     Err(MlsError::WelcomeKeyPackageNotFound)
 }
 
+pub(crate) fn check_min_protocol_version(
+    version: ProtocolVersion,
+    minimum: Option<ProtocolVersion>,
+) -> Result<(), MlsError> {
+    match minimum {
+        Some(minimum) if version < minimum => {
+            Err(MlsError::ProtocolVersionTooOld { version, minimum })
+        }
+        _ => Ok(()),
+    }
+}
+
 pub(crate) fn cipher_suite_provider<P>(
     crypto: P,
     cipher_suite: CipherSuite,
@@ -5,6 +5,10 @@
 /// Basic credential identity provider.
 pub mod basic;
 
+/// Identity provider wrapper caching successful validations.
+#[cfg(feature = "std")]
+pub mod caching;
+
 /// X.509 certificate identity provider.
 #[cfg(feature = "x509")]
 pub mod x509 {
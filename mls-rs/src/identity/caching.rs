@@ -0,0 +1,346 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// Copyright by contributors to this project.
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+use alloc::sync::Arc;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::time::Duration;
+use std::{collections::HashMap, sync::Mutex};
+
+#[cfg(mls_build_async)]
+use alloc::boxed::Box;
+use mls_rs_codec::MlsEncode;
+use mls_rs_core::{
+    extension::ExtensionList,
+    identity::{CredentialType, IdentityProvider, SigningIdentity},
+    time::MlsTime,
+};
+
+#[derive(Clone, Copy)]
+#[repr(u8)]
+enum ValidationKind {
+    Member = 0,
+    ExternalSender = 1,
+}
+
+#[derive(Clone, Debug)]
+/// An [`IdentityProvider`] that remembers successful validations of the
+/// provider it wraps.
+///
+/// Validating the same signing identity with the same extensions again
+/// within `ttl` of the last successful validation succeeds without calling
+/// the wrapped provider. This avoids repeating expensive checks, e.g.
+/// X.509 chain building, every time an identity is processed.
+///
+/// Validations with a `timestamp` depend on that time and always call the
+/// wrapped provider. Failed validations are never cached, and expired
+/// entries are removed each time a new validation is cached.
+///
+/// All clones of an instance of this type share the same cache.
+pub struct CachingIdentityProvider<I> {
+    inner: I,
+    ttl: Duration,
+    cache: Arc<Mutex<HashMap<Vec<u8>, MlsTime>>>,
+}
+
+impl<I> CachingIdentityProvider<I> {
+    /// Wrap `inner`, caching successful validations for `ttl`.
+    pub fn new(inner: I, ttl: Duration) -> Self {
+        Self {
+            inner,
+            ttl,
+            cache: Default::default(),
+        }
+    }
+
+    /// The wrapped identity provider.
+    pub fn inner(&self) -> &I {
+        &self.inner
+    }
+
+    /// Remove all cached validations.
+    pub fn clear(&self) {
+        self.cache.lock().unwrap().clear();
+    }
+
+    fn cache_key(
+        kind: ValidationKind,
+        signing_identity: &SigningIdentity,
+        timestamp: Option<MlsTime>,
+        extensions: Option<&ExtensionList>,
+    ) -> Option<Vec<u8>> {
+        if timestamp.is_some() {
+            return None;
+        }
+
+        let mut key = vec![kind as u8];
+
+        signing_identity.mls_encode(&mut key).ok()?;
+
+        if let Some(extensions) = extensions {
+            extensions.mls_encode(&mut key).ok()?;
+        }
+
+        Some(key)
+    }
+
+    fn is_valid_at(&self, validated: &MlsTime, now: MlsTime) -> bool {
+        now.seconds_since_epoch()
+            < validated
+                .seconds_since_epoch()
+                .saturating_add(self.ttl.as_secs())
+    }
+
+    fn is_cached(&self, key: &[u8]) -> bool {
+        let now = MlsTime::now();
+
+        self.cache
+            .lock()
+            .unwrap()
+            .get(key)
+            .map_or(false, |validated| self.is_valid_at(validated, now))
+    }
+
+    fn insert(&self, key: Vec<u8>) {
+        let now = MlsTime::now();
+        let mut cache = self.cache.lock().unwrap();
+
+        cache.retain(|_, validated| self.is_valid_at(validated, now));
+        cache.insert(key, now);
+    }
+}
+
+#[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+#[cfg_attr(mls_build_async, maybe_async::must_be_async)]
+impl<I: IdentityProvider> IdentityProvider for CachingIdentityProvider<I> {
+    type Error = I::Error;
+
+    async fn validate_member(
+        &self,
+        signing_identity: &SigningIdentity,
+        timestamp: Option<MlsTime>,
+        extensions: Option<&ExtensionList>,
+    ) -> Result<(), Self::Error> {
+        let key = Self::cache_key(
+            ValidationKind::Member,
+            signing_identity,
+            timestamp,
+            extensions,
+        );
+
+        if key.as_deref().map_or(false, |key| self.is_cached(key)) {
+            return Ok(());
+        }
+
+        self.inner
+            .validate_member(signing_identity, timestamp, extensions)
+            .await?;
+
+        key.into_iter().for_each(|key| self.insert(key));
+
+        Ok(())
+    }
+
+    async fn validate_external_sender(
+        &self,
+        signing_identity: &SigningIdentity,
+        timestamp: Option<MlsTime>,
+        extensions: Option<&ExtensionList>,
+    ) -> Result<(), Self::Error> {
+        let key = Self::cache_key(
+            ValidationKind::ExternalSender,
+            signing_identity,
+            timestamp,
+            extensions,
+        );
+
+        if key.as_deref().map_or(false, |key| self.is_cached(key)) {
+            return Ok(());
+        }
+
+        self.inner
+            .validate_external_sender(signing_identity, timestamp, extensions)
+            .await?;
+
+        key.into_iter().for_each(|key| self.insert(key));
+
+        Ok(())
+    }
+
+    async fn identity(
+        &self,
+        signing_identity: &SigningIdentity,
+        extensions: &ExtensionList,
+    ) -> Result<Vec<u8>, Self::Error> {
+        self.inner.identity(signing_identity, extensions).await
+    }
+
+    async fn valid_successor(
+        &self,
+        predecessor: &SigningIdentity,
+        successor: &SigningIdentity,
+        extensions: &ExtensionList,
+    ) -> Result<bool, Self::Error> {
+        self.inner
+            .valid_successor(predecessor, successor, extensions)
+            .await
+    }
+
+    fn supported_types(&self) -> Vec<CredentialType> {
+        self.inner.supported_types()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::sync::Arc;
+    use alloc::vec::Vec;
+    use core::{
+        sync::atomic::{AtomicUsize, Ordering},
+        time::Duration,
+    };
+
+    #[cfg(mls_build_async)]
+    use alloc::boxed::Box;
+    use mls_rs_core::{
+        extension::ExtensionList,
+        identity::{CredentialType, IdentityProvider, SigningIdentity},
+        time::MlsTime,
+    };
+
+    use crate::{
+        client::test_utils::TEST_CIPHER_SUITE,
+        identity::{
+            basic::{BasicIdentityProvider, BasicIdentityProviderError},
+            test_utils::get_test_signing_identity,
+        },
+    };
+
+    use super::CachingIdentityProvider;
+
+    #[derive(Clone, Default)]
+    struct CountingProvider {
+        validations: Arc<AtomicUsize>,
+    }
+
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    #[cfg_attr(mls_build_async, maybe_async::must_be_async)]
+    impl IdentityProvider for CountingProvider {
+        type Error = BasicIdentityProviderError;
+
+        async fn validate_member(
+            &self,
+            signing_identity: &SigningIdentity,
+            timestamp: Option<MlsTime>,
+            extensions: Option<&ExtensionList>,
+        ) -> Result<(), Self::Error> {
+            self.validations.fetch_add(1, Ordering::SeqCst);
+
+            BasicIdentityProvider
+                .validate_member(signing_identity, timestamp, extensions)
+                .await
+        }
+
+        async fn validate_external_sender(
+            &self,
+            signing_identity: &SigningIdentity,
+            timestamp: Option<MlsTime>,
+            extensions: Option<&ExtensionList>,
+        ) -> Result<(), Self::Error> {
+            self.validations.fetch_add(1, Ordering::SeqCst);
+
+            BasicIdentityProvider
+                .validate_external_sender(signing_identity, timestamp, extensions)
+                .await
+        }
+
+        async fn identity(
+            &self,
+            signing_identity: &SigningIdentity,
+            extensions: &ExtensionList,
+        ) -> Result<Vec<u8>, Self::Error> {
+            BasicIdentityProvider
+                .identity(signing_identity, extensions)
+                .await
+        }
+
+        async fn valid_successor(
+            &self,
+            predecessor: &SigningIdentity,
+            successor: &SigningIdentity,
+            extensions: &ExtensionList,
+        ) -> Result<bool, Self::Error> {
+            BasicIdentityProvider
+                .valid_successor(predecessor, successor, extensions)
+                .await
+        }
+
+        fn supported_types(&self) -> Vec<CredentialType> {
+            BasicIdentityProvider.supported_types()
+        }
+    }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn validation_within_ttl_is_cached() {
+        let counter = CountingProvider::default();
+        let provider = CachingIdentityProvider::new(counter.clone(), Duration::from_secs(3600));
+        let (alice, _) = get_test_signing_identity(TEST_CIPHER_SUITE, b"alice").await;
+        let (bob, _) = get_test_signing_identity(TEST_CIPHER_SUITE, b"bob").await;
+
+        for _ in 0..2 {
+            provider.validate_member(&alice, None, None).await.unwrap();
+        }
+
+        assert_eq!(counter.validations.load(Ordering::SeqCst), 1);
+
+        // Other identities and other kinds of validation are cached separately.
+        provider.validate_member(&bob, None, None).await.unwrap();
+
+        provider
+            .validate_external_sender(&alice, None, None)
+            .await
+            .unwrap();
+
+        assert_eq!(counter.validations.load(Ordering::SeqCst), 3);
+
+        provider.clear();
+        provider.validate_member(&alice, None, None).await.unwrap();
+
+        assert_eq!(counter.validations.load(Ordering::SeqCst), 4);
+    }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn expired_validation_is_repeated() {
+        let counter = CountingProvider::default();
+        let provider = CachingIdentityProvider::new(counter.clone(), Duration::ZERO);
+        let (alice, _) = get_test_signing_identity(TEST_CIPHER_SUITE, b"alice").await;
+
+        for _ in 0..2 {
+            provider.validate_member(&alice, None, None).await.unwrap();
+        }
+
+        assert_eq!(counter.validations.load(Ordering::SeqCst), 2);
+
+        // Only the latest expired validation is left in the cache
+        assert_eq!(provider.cache.lock().unwrap().len(), 1);
+    }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn validation_with_timestamp_is_not_cached() {
+        let counter = CountingProvider::default();
+        let provider = CachingIdentityProvider::new(counter.clone(), Duration::from_secs(3600));
+        let (alice, _) = get_test_signing_identity(TEST_CIPHER_SUITE, b"alice").await;
+
+        provider.validate_member(&alice, None, None).await.unwrap();
+
+        for _ in 0..2 {
+            provider
+                .validate_member(&alice, Some(MlsTime::now()), None)
+                .await
+                .unwrap();
+        }
+
+        assert_eq!(counter.validations.load(Ordering::SeqCst), 3);
+    }
+}
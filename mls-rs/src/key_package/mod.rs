@@ -110,6 +110,11 @@ impl KeyPackage {
         &self.leaf_node.signing_identity
     }
 
+    /// Compute the [`KeyPackageRef`] of this key package as defined in
+    /// [RFC 9420](https://www.rfc-editor.org/rfc/rfc9420.html#name-key-package-reference).
+    ///
+    /// This is the identifier under which the generating client stores the
+    /// key package in its [`KeyPackageStorage`](crate::KeyPackageStorage).
     #[cfg_attr(all(feature = "ffi", not(test)), safer_ffi_gen::safer_ffi_gen_ignore)]
     #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
     pub async fn to_reference<CP: CipherSuiteProvider>(
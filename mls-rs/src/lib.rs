@@ -168,7 +168,8 @@ pub use mls_rs_core::{
 pub mod mls_rules {
     pub use crate::group::{
         mls_rules::{
-            CommitDirection, CommitOptions, CommitSource, DefaultMlsRules, EncryptionOptions,
+            AuditProposalFilter, CommitDirection, CommitOptions, CommitSource, DefaultMlsRules,
+            EncryptionOptions, ProposalAudit,
         },
         proposal_filter::{ProposalBundle, ProposalInfo, ProposalSource},
     };
@@ -59,7 +59,7 @@ impl<GS: GroupStateStorage, K: KeyPackageStorage, PS: PreSharedKeyStorage>
             .get(psk_id)
             .await
             .map_err(|e| MlsError::PskStoreError(e.into_any_error()))?
-            .ok_or(MlsError::MissingRequiredPsk)
+            .ok_or_else(|| MlsError::MissingPsk(psk_id.clone()))
     }
 
     #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
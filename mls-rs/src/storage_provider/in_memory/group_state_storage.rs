@@ -179,6 +179,13 @@ impl GroupStateStorage for InMemoryGroupStateStorage {
             .and_then(|group_data| group_data.epoch_data.back().map(|e| e.id)))
     }
 
+    async fn min_epoch_id(&self, group_id: &[u8]) -> Result<Option<u64>, Self::Error> {
+        Ok(self
+            .lock()
+            .get(group_id)
+            .and_then(|group_data| group_data.epoch_data.front().map(|e| e.id)))
+    }
+
     async fn state(&self, group_id: &[u8]) -> Result<Option<Vec<u8>>, Self::Error> {
         Ok(self
             .lock()
@@ -1,5 +1,5 @@
 use mls_rs_codec::MlsEncode;
-use mls_rs_core::protocol_version::ProtocolVersion;
+use mls_rs_core::{crypto::CryptoProvider, protocol_version::ProtocolVersion};
 
 use crate::{
     cipher_suite::CipherSuite,
@@ -7,6 +7,7 @@ use crate::{
     group::{framing::MlsMessage, Group},
     identity::basic::BasicIdentityProvider,
     test_utils::{generate_basic_client, get_test_groups},
+    tree_kem::{node::LeafIndex, TreeKemPublic},
 };
 
 pub use mls_rs_crypto_openssl::OpensslCryptoProvider as MlsCryptoProvider;
@@ -138,3 +139,136 @@ pub async fn join_group(cs: CipherSuite, group_info: MlsMessage) -> GroupStates<
 
     GroupStates { sender, receiver }
 }
+
+/// Create a group with `size` members as seen by its creator. All members
+/// are added in a single commit, so the next commit of the creator encrypts
+/// its path secrets to every other member.
+#[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+pub async fn create_large_group(cs: CipherSuite, size: usize) -> Group<impl MlsConfig> {
+    let client = generate_basic_client(
+        cs,
+        ProtocolVersion::MLS_10,
+        0,
+        None,
+        false,
+        &MlsCryptoProvider::new(),
+        None,
+    )
+    .await;
+
+    let mut group = client.create_group(Default::default()).await.unwrap();
+    let mut commit_builder = group.commit_builder();
+
+    for i in 1..size {
+        let key_package = generate_basic_client(
+            cs,
+            ProtocolVersion::MLS_10,
+            i,
+            None,
+            false,
+            &MlsCryptoProvider::new(),
+            None,
+        )
+        .await
+        .generate_key_package_message()
+        .await
+        .unwrap();
+
+        commit_builder = commit_builder.add_member(key_package).unwrap();
+    }
+
+    commit_builder.build().await.unwrap();
+    group.apply_pending_commit().await.unwrap();
+
+    group
+}
+
+type MlsCipherSuiteProvider = <MlsCryptoProvider as CryptoProvider>::CipherSuiteProvider;
+
+/// Full ratchet tree used to compare computing the tree hash from scratch
+/// with updating it after a leaf changed.
+#[derive(Clone)]
+pub struct TreeHashBenchmark {
+    tree: TreeKemPublic,
+    cipher_suite_provider: MlsCipherSuiteProvider,
+}
+
+impl TreeHashBenchmark {
+    /// Create a tree with `size` leaves and no blank nodes. All tree hashes
+    /// are computed.
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    pub async fn new(cs: CipherSuite, size: u32) -> Self {
+        let cipher_suite_provider = MlsCryptoProvider::new().cipher_suite_provider(cs).unwrap();
+
+        let group = get_test_groups(
+            ProtocolVersion::MLS_10,
+            cs,
+            1,
+            None,
+            false,
+            &MlsCryptoProvider::new(),
+        )
+        .await
+        .pop()
+        .unwrap();
+
+        // The tree hash does not depend on leaves being distinct.
+        let leaf = group
+            .state
+            .public_tree
+            .get_leaf_node(LeafIndex(0))
+            .unwrap()
+            .clone();
+
+        let mut tree = TreeKemPublic::new();
+
+        for i in 0..size {
+            tree.nodes.insert_leaf(LeafIndex(i), leaf.clone());
+        }
+
+        for parent in (1..2 * size - 1).step_by(2) {
+            tree.update_node(leaf.public_key.clone(), parent).unwrap();
+        }
+
+        let mut bench = Self {
+            tree,
+            cipher_suite_provider,
+        };
+
+        bench.tree_hash().await;
+
+        bench
+    }
+
+    /// Copy of this tree without any cached tree hash.
+    pub fn without_hashes(&self) -> Self {
+        let mut tree = TreeKemPublic::new();
+        tree.nodes = self.tree.nodes.clone();
+
+        Self {
+            tree,
+            cipher_suite_provider: self.cipher_suite_provider.clone(),
+        }
+    }
+
+    /// Compute the tree hash, filling in all missing hashes.
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    pub async fn tree_hash(&mut self) -> Vec<u8> {
+        self.tree
+            .tree_hash(&self.cipher_suite_provider)
+            .await
+            .unwrap()
+    }
+
+    /// Update the hashes on the direct path of `leaf` and compute the tree
+    /// hash.
+    #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
+    pub async fn update_leaf_hash(&mut self, leaf: u32) -> Vec<u8> {
+        self.tree
+            .update_hashes(&[LeafIndex(leaf)], &self.cipher_suite_provider)
+            .await
+            .unwrap();
+
+        self.tree_hash().await
+    }
+}
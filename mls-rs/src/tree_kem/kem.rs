@@ -145,6 +145,7 @@ impl<'a> TreeKem<'a> {
 
         let context_bytes = context.mls_encode_to_vec()?;
 
+        #[cfg(any(mls_build_async, not(feature = "rayon")))]
         let node_updates = self
             .encrypt_path_secrets(
                 path,
@@ -155,6 +156,15 @@ impl<'a> TreeKem<'a> {
             )
             .await?;
 
+        #[cfg(all(not(mls_build_async), feature = "rayon"))]
+        let node_updates = self.encrypt_path_secrets_parallel(
+            path,
+            &path_secrets,
+            &context_bytes,
+            cipher_suite_provider,
+            excluding,
+        )?;
+
         #[cfg(test)]
         let node_updates = (commit_modifiers.modify_path)(node_updates);
 
@@ -171,7 +181,7 @@ impl<'a> TreeKem<'a> {
         })
     }
 
-    #[cfg(any(mls_build_async, not(feature = "rayon")))]
+    #[cfg(any(mls_build_async, not(feature = "rayon"), test))]
     #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
     async fn encrypt_path_secrets<P: CipherSuiteProvider>(
         &self,
@@ -208,8 +218,11 @@ impl<'a> TreeKem<'a> {
         Ok(node_updates)
     }
 
+    /// Same as `encrypt_path_secrets`, with the resolution of each copath node
+    /// encrypted on the rayon thread pool. The ciphertexts of different nodes
+    /// are independent, so the output does not depend on the scheduling.
     #[cfg(all(not(mls_build_async), feature = "rayon"))]
-    fn encrypt_path_secrets<P: CipherSuiteProvider>(
+    fn encrypt_path_secrets_parallel<P: CipherSuiteProvider>(
         &self,
         path: Vec<CopathNode<NodeIndex>>,
         path_secrets: &[Option<PathSecret>],
@@ -696,4 +709,95 @@ mod tests {
 
         encap_decap(cipher_suite, 10, Some(capabilities), Some(extensions)).await;
     }
+
+    #[cfg(all(not(mls_build_async), feature = "rayon"))]
+    #[test]
+    fn parallel_and_sequential_path_encryption_are_decryptable() {
+        use crate::tree_kem::{hpke_encryption::HpkeEncryptable, path_secret::PathSecret};
+
+        let cipher_suite_provider = test_cipher_suite_provider(TEST_CIPHER_SUITE);
+
+        let (encap_node, encap_hpke_secret, encap_signer) =
+            get_basic_test_node_sig_key(TEST_CIPHER_SUITE, "encap");
+
+        let (mut tree, mut private_key) = TreeKemPublic::derive(
+            encap_node,
+            encap_hpke_secret,
+            &BasicIdentityProvider,
+            &Default::default(),
+        )
+        .unwrap();
+
+        let (leaf_nodes, hpke_secrets): (Vec<_>, Vec<_>) = (1..11)
+            .map(|i| {
+                let (leaf_node, hpke_secret, _) =
+                    get_basic_test_node_sig_key(TEST_CIPHER_SUITE, &format!("{i}"));
+
+                (leaf_node, hpke_secret)
+            })
+            .unzip();
+
+        tree.add_leaves(leaf_nodes, &BasicIdentityProvider, &cipher_suite_provider)
+            .unwrap();
+
+        let mut context = get_test_group_context(42, TEST_CIPHER_SUITE);
+
+        // Encap uses the parallel implementation
+        let encap_gen = TreeKem::new(&mut tree, &mut private_key)
+            .encap(
+                &mut context,
+                &[],
+                &encap_signer,
+                ConfigProperties {
+                    capabilities: get_test_capabilities(),
+                    extensions: Default::default(),
+                },
+                None,
+                &cipher_suite_provider,
+                &Default::default(),
+            )
+            .unwrap();
+
+        let context_bytes = context.mls_encode_to_vec().unwrap();
+        let path = tree.nodes.direct_copath(LeafIndex(0));
+
+        let sequential = TreeKem::new(&mut tree, &mut private_key)
+            .encrypt_path_secrets(
+                path.clone(),
+                &encap_gen.path_secrets,
+                &context_bytes,
+                &cipher_suite_provider,
+                &[],
+            )
+            .unwrap();
+
+        for node_updates in [encap_gen.update_path.nodes, sequential] {
+            let encrypted = path
+                .iter()
+                .zip(&encap_gen.path_secrets)
+                .filter_map(|(node, secret)| secret.as_ref().map(|secret| (node.copath, secret)))
+                .zip(node_updates);
+
+            for ((copath, path_secret), update) in encrypted {
+                let resolution = tree.nodes.get_resolution_index(copath).unwrap();
+                assert_eq!(resolution.len(), update.encrypted_path_secret.len());
+
+                for (index, ciphertext) in resolution.iter().zip(&update.encrypted_path_secret) {
+                    let leaf = LeafIndex(index / 2);
+                    let public_key = &tree.get_leaf_node(leaf).unwrap().public_key;
+
+                    let decrypted = PathSecret::decrypt(
+                        &cipher_suite_provider,
+                        &hpke_secrets[*leaf as usize - 1],
+                        public_key,
+                        &context_bytes,
+                        ciphertext,
+                    )
+                    .unwrap();
+
+                    assert_eq!(&decrypted, path_secret);
+                }
+            }
+        }
+    }
 }
@@ -150,10 +150,23 @@ impl NodeTypeResolver for Option<Node> {
     }
 }
 
-#[derive(Clone, Debug, PartialEq, MlsSize, MlsEncode, MlsDecode, Default)]
+/// Largest number of nodes accepted when decoding a [`NodeVec`]. Node indices
+/// are `u32`, so larger trees can not be addressed.
+const MAX_NODE_COUNT: usize = u32::MAX as usize;
+
+/// Codec error returned when a [`NodeVec`] has more nodes than allowed.
+pub(crate) const RATCHET_TREE_TOO_LARGE: u8 = 6;
+
+#[derive(Clone, Debug, PartialEq, MlsSize, MlsEncode, Default)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub(crate) struct NodeVec(Vec<Option<Node>>);
 
+impl MlsDecode for NodeVec {
+    fn mls_decode(reader: &mut &[u8]) -> Result<Self, mls_rs_codec::Error> {
+        Self::mls_decode_bounded(reader, None)
+    }
+}
+
 impl From<Vec<Option<Node>>> for NodeVec {
     fn from(x: Vec<Option<Node>>) -> Self {
         NodeVec(x)
@@ -175,6 +188,30 @@ impl DerefMut for NodeVec {
 }
 
 impl NodeVec {
+    /// Decode nodes one at a time, failing as soon as more than `max_nodes`
+    /// nodes are read instead of first decoding the whole collection. If
+    /// `max_nodes` is `None`, trees are only bounded by the node index range.
+    pub(crate) fn mls_decode_bounded(
+        reader: &mut &[u8],
+        max_nodes: Option<usize>,
+    ) -> Result<Self, mls_rs_codec::Error> {
+        let max_nodes = max_nodes.map_or(MAX_NODE_COUNT, |max| max.min(MAX_NODE_COUNT));
+
+        mls_rs_codec::iter::mls_decode_collection(reader, |data| {
+            let mut nodes = Vec::new();
+
+            while !data.is_empty() {
+                if nodes.len() >= max_nodes {
+                    return Err(mls_rs_codec::Error::Custom(RATCHET_TREE_TOO_LARGE));
+                }
+
+                nodes.push(Option::<Node>::mls_decode(data)?);
+            }
+
+            Ok(NodeVec(nodes))
+        })
+    }
+
     #[cfg(any(test, all(feature = "custom_proposal", feature = "tree_index")))]
     pub fn occupied_leaf_count(&self) -> u32 {
         self.non_empty_leaves().count() as u32
@@ -447,6 +484,7 @@ mod tests {
             leaf_node::test_utils::get_basic_test_node, node::test_utils::get_test_node_vec,
         },
     };
+    use assert_matches::assert_matches;
 
     #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
     async fn node_key_getters() {
@@ -574,4 +612,31 @@ mod tests {
         assert_eq!(test_vec.occupied_leaf_count(), 3);
         assert_eq!(test_vec.total_leaf_count(), 4);
     }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn bounded_decoding_matches_plain_decoding() {
+        let leaf = Some(Node::from(
+            get_basic_test_node(TEST_CIPHER_SUITE, "A").await,
+        ));
+
+        let nodes = (0..63)
+            .map(|i| if i % 2 == 0 { leaf.clone() } else { None })
+            .collect::<Vec<_>>();
+
+        let bytes = NodeVec::from(nodes).mls_encode_to_vec().unwrap();
+
+        let decoded = NodeVec::mls_decode(&mut &*bytes).unwrap();
+        let plain = Vec::<Option<Node>>::mls_decode(&mut &*bytes).unwrap();
+
+        assert_eq!(*decoded, plain);
+        assert_eq!(
+            NodeVec::mls_decode_bounded(&mut &*bytes, Some(63)).unwrap(),
+            decoded
+        );
+
+        assert_matches!(
+            NodeVec::mls_decode_bounded(&mut &*bytes, Some(62)),
+            Err(mls_rs_codec::Error::Custom(6))
+        );
+    }
 }
@@ -270,9 +270,14 @@ async fn tree_hash<P: CipherSuiteProvider>(
     num_leaves: u32,
     cipher_suite_provider: &P,
 ) -> Result<(), MlsError> {
-    let leaves_to_update =
+    let mut leaves_to_update =
         leaves_to_update.unwrap_or_else(|| (0..num_leaves).map(LeafIndex).collect::<Vec<_>>());
 
+    // Sorting the leaves makes the queue below contain the nodes of each level
+    // in order, so that a node shared by several direct paths is only hashed once.
+    leaves_to_update.sort_unstable();
+    leaves_to_update.dedup();
+
     // Resize the array in case the tree was extended or truncated
     hashes.resize(num_leaves as usize * 2 - 1, TreeHash::default());
 
@@ -286,7 +291,7 @@ async fn tree_hash<P: CipherSuiteProvider>(
         hashes[2 * **l as usize] = TreeHash(hash_for_leaf(*l, leaf, cipher_suite_provider).await?);
 
         if let Some(ps) = (2 * **l).parent_sibling(&num_leaves) {
-            node_queue.push_back(ps.parent);
+            push_unique(&mut node_queue, ps.parent);
         }
     }
 
@@ -305,13 +310,19 @@ async fn tree_hash<P: CipherSuiteProvider>(
         hashes[n as usize] = hash;
 
         if let Some(ps) = n.parent_sibling(&num_leaves) {
-            node_queue.push_back(ps.parent);
+            push_unique(&mut node_queue, ps.parent);
         }
     }
 
     Ok(())
 }
 
+fn push_unique(node_queue: &mut VecDeque<u32>, node: u32) {
+    if node_queue.back() != Some(&node) {
+        node_queue.push_back(node);
+    }
+}
+
 #[cfg_attr(not(mls_build_async), maybe_async::must_be_sync)]
 async fn hash_for_leaf<P: CipherSuiteProvider>(
     leaf_index: LeafIndex,
@@ -363,9 +374,13 @@ mod tests {
 
     use crate::{
         cipher_suite::CipherSuite,
+        client::test_utils::TEST_CIPHER_SUITE,
         crypto::test_utils::{test_cipher_suite_provider, try_test_cipher_suite_provider},
         identity::basic::BasicIdentityProvider,
-        tree_kem::{node::NodeVec, parent_hash::test_utils::get_test_tree_fig_12},
+        tree_kem::{
+            node::NodeVec, parent_hash::test_utils::get_test_tree_fig_12,
+            test_utils::TreeWithSigners,
+        },
     };
 
     use super::*;
@@ -434,4 +449,31 @@ mod tests {
             assert_eq!(calculated_hash, one_case.tree_hash);
         }
     }
+
+    #[maybe_async::test(not(mls_build_async), async(mls_build_async, crate::futures_test))]
+    async fn incremental_tree_hash_equals_full_tree_hash() {
+        let cs = test_cipher_suite_provider(TEST_CIPHER_SUITE);
+        let mut tree = TreeWithSigners::make_full_tree(32, &cs).await.tree;
+
+        // Initialize the cached hashes
+        tree.tree_hash(&cs).await.unwrap();
+
+        // Leaves 3 and 4 share most of their direct paths
+        let removed = [LeafIndex(17), LeafIndex(3), LeafIndex(4)];
+
+        for leaf in removed {
+            tree.nodes.blank_leaf_node(leaf).unwrap();
+            tree.nodes.blank_direct_path(leaf).unwrap();
+        }
+
+        tree.update_hashes(&removed, &cs).await.unwrap();
+        let incremental = tree.tree_hash(&cs).await.unwrap();
+
+        let mut full_tree = tree.clone();
+        full_tree.tree_hashes = TreeHashes::default();
+        let full = full_tree.tree_hash(&cs).await.unwrap();
+
+        assert_eq!(incremental, full);
+        assert_eq!(tree.tree_hashes, full_tree.tree_hashes);
+    }
 }